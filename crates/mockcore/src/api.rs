@@ -87,7 +87,12 @@ pub trait Api {
   ) -> Result<Value, jsonrpc_core::Error>;
 
   #[rpc(name = "sendrawtransaction")]
-  fn send_raw_transaction(&self, tx: String) -> Result<String, jsonrpc_core::Error>;
+  fn send_raw_transaction(
+    &self,
+    tx: String,
+    max_fee_rate: Option<f64>,
+    max_burn_amount: Option<f64>,
+  ) -> Result<String, jsonrpc_core::Error>;
 
   #[rpc(name = "sendtoaddress")]
   fn send_to_address(
@@ -120,6 +125,24 @@ pub trait Api {
     blockhash: Option<BlockHash>,
   ) -> Result<Value, jsonrpc_core::Error>;
 
+  #[rpc(name = "getmempooldescendants")]
+  fn get_mempool_descendants(&self, txid: Txid) -> Result<Vec<Txid>, jsonrpc_core::Error>;
+
+  #[rpc(name = "getmempoolentry")]
+  fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntryResult, jsonrpc_core::Error>;
+
+  #[rpc(name = "gettxspendingprevout")]
+  fn get_tx_spending_prevout(
+    &self,
+    outputs: Vec<JsonOutPoint>,
+  ) -> Result<Value, jsonrpc_core::Error>;
+
+  #[rpc(name = "getaddressinfo")]
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error>;
+
   #[rpc(name = "listunspent")]
   fn list_unspent(
     &self,
@@ -194,7 +217,7 @@ pub trait Api {
     &self,
     psbt: String,
     sign: Option<bool>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
     bip32derivs: Option<bool>,
   ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error>;
 
@@ -19,13 +19,14 @@ use {
   bitcoincore_rpc::json::{
     Bip125Replaceable, CreateRawTransactionInput, Descriptor, EstimateMode, FeeRatePercentiles,
     FinalizePsbtResult, GetBalancesResult, GetBalancesResultEntry, GetBlockHeaderResult,
-    GetBlockStatsResult, GetBlockchainInfoResult, GetDescriptorInfoResult, GetNetworkInfoResult,
-    GetRawTransactionResult, GetRawTransactionResultVout, GetRawTransactionResultVoutScriptPubKey,
-    GetTransactionResult, GetTransactionResultDetail, GetTransactionResultDetailCategory,
-    GetTxOutResult, GetWalletInfoResult, ImportDescriptors, ImportMultiResult,
-    ListDescriptorsResult, ListTransactionResult, ListUnspentResultEntry, ListWalletDirItem,
-    ListWalletDirResult, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
-    Timestamp, WalletProcessPsbtResult, WalletTxInfo,
+    GetBlockStatsResult, GetBlockchainInfoResult, GetDescriptorInfoResult, GetMempoolEntryResult,
+    GetMempoolEntryResultFees, GetNetworkInfoResult, GetRawTransactionResult,
+    GetRawTransactionResultVout, GetRawTransactionResultVoutScriptPubKey, GetTransactionResult,
+    GetTransactionResultDetail, GetTransactionResultDetailCategory, GetTxOutResult,
+    GetWalletInfoResult, ImportDescriptors, ImportMultiResult, ListDescriptorsResult,
+    ListTransactionResult, ListUnspentResultEntry, ListWalletDirItem, ListWalletDirResult,
+    LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult, Timestamp,
+    WalletProcessPsbtResult, WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -236,6 +237,16 @@ impl Handle {
     blocks
   }
 
+  #[track_caller]
+  pub fn mine_blocks_to(&self, n: u64, address: &Address) -> Vec<Block> {
+    let mut bitcoin_rpc_data = self.state();
+    let mut blocks = Vec::new();
+    for _ in 0..n {
+      blocks.push(bitcoin_rpc_data.mine_block_to(50 * COIN_VALUE, address.script_pubkey()));
+    }
+    blocks
+  }
+
   pub fn broadcast_tx(&self, template: TransactionTemplate) -> Txid {
     self.state().broadcast_tx(template)
   }
@@ -286,7 +286,11 @@ impl Api for Server {
   }
 
   fn get_wallet_info(&self) -> Result<GetWalletInfoResult, jsonrpc_core::Error> {
-    if let Some(wallet_name) = self.state().loaded_wallets.first().cloned() {
+    let state = self.state();
+
+    if let Some(wallet_name) = state.loaded_wallets.first().cloned() {
+      let private_keys_enabled = !state.watch_only_wallets.contains(&wallet_name);
+
       Ok(GetWalletInfoResult {
         avoid_reuse: None,
         balance: Amount::from_sat(0),
@@ -296,7 +300,7 @@ impl Api for Server {
         keypool_size: 0,
         keypool_size_hd_internal: 0,
         pay_tx_fee: Amount::from_sat(0),
-        private_keys_enabled: true,
+        private_keys_enabled,
         scanning: None,
         tx_count: 0,
         unconfirmed_balance: Amount::from_sat(0),
@@ -346,12 +350,17 @@ impl Api for Server {
   fn create_wallet(
     &self,
     name: String,
-    _disable_private_keys: Option<bool>,
+    disable_private_keys: Option<bool>,
     _blank: Option<bool>,
     _passphrase: Option<String>,
     _avoid_reuse: Option<bool>,
   ) -> Result<LoadWalletResult, jsonrpc_core::Error> {
     self.state().wallets.insert(name.clone());
+
+    if disable_private_keys == Some(true) {
+      self.state().watch_only_wallets.insert(name.clone());
+    }
+
     Ok(LoadWalletResult {
       name,
       warning: None,
@@ -457,7 +466,7 @@ impl Api for Server {
     if change > 0 {
       transaction.output.push(TxOut {
         value: change,
-        script_pubkey: state.new_address(true).into(),
+        script_pubkey: state.new_address(true, None).into(),
       });
     }
 
@@ -504,11 +513,32 @@ impl Api for Server {
     )
   }
 
-  fn send_raw_transaction(&self, tx: String) -> Result<String, jsonrpc_core::Error> {
+  fn send_raw_transaction(
+    &self,
+    tx: String,
+    _max_fee_rate: Option<f64>,
+    max_burn_amount: Option<f64>,
+  ) -> Result<String, jsonrpc_core::Error> {
     let tx: Transaction = deserialize(&hex::decode(tx).unwrap()).unwrap();
 
+    let max_burn_amount = Amount::from_btc(max_burn_amount.unwrap_or_default()).unwrap();
+
+    if tx
+      .output
+      .iter()
+      .any(|output| output.script_pubkey.is_op_return() && output.value > max_burn_amount.to_sat())
+    {
+      return Err(jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::ServerError(-25),
+        message: "Unspendable output exceeds maximum configured by user (maxburnamount)".into(),
+        data: None,
+      });
+    }
+
     let mut state = self.state.lock().unwrap();
 
+    state.evict_conflicts(&tx);
+
     for tx_in in &tx.input {
       if let Some(lock_time) = tx_in.sequence.to_relative_lock_time() {
         match lock_time {
@@ -671,8 +701,13 @@ impl Api for Server {
 
     let blockhash = tx_height.map(|tx_height| state.hashes[usize::try_from(*tx_height).unwrap()]);
 
+    let transaction = state
+      .transactions
+      .get(&txid)
+      .or_else(|| state.mempool.iter().find(|tx| tx.txid() == txid));
+
     if verbose.unwrap_or(false) {
-      match state.transactions.get(&txid) {
+      match transaction {
         Some(transaction) => Ok(
           serde_json::to_value(GetRawTransactionResult {
             in_active_chain: Some(true),
@@ -711,13 +746,161 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match state.transactions.get(&txid) {
+      match transaction {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
     }
   }
 
+  fn get_mempool_descendants(&self, txid: Txid) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    let state = self.state();
+
+    if !state.mempool.iter().any(|tx| tx.txid() == txid) {
+      return Err(Self::not_found());
+    }
+
+    let mut descendants = Vec::new();
+
+    for tx in &state.mempool {
+      if tx.input.iter().any(|txin| {
+        txin.previous_output.txid == txid || descendants.contains(&txin.previous_output.txid)
+      }) {
+        descendants.push(tx.txid());
+      }
+    }
+
+    Ok(descendants)
+  }
+
+  fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntryResult, jsonrpc_core::Error> {
+    let state = self.state();
+
+    let Some(tx) = state.mempool.iter().find(|tx| tx.txid() == txid) else {
+      return Err(jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::ServerError(-5),
+        message: "Transaction not in mempool".into(),
+        data: None,
+      });
+    };
+
+    let fee = |tx: &Transaction| {
+      let input = tx
+        .input
+        .iter()
+        .filter_map(|txin| {
+          state
+            .transactions
+            .get(&txin.previous_output.txid)
+            .or_else(|| {
+              state
+                .mempool
+                .iter()
+                .find(|tx| tx.txid() == txin.previous_output.txid)
+            })
+            .and_then(|tx| {
+              tx.output
+                .get(usize::try_from(txin.previous_output.vout).unwrap())
+            })
+            .map(|output| output.value)
+        })
+        .sum::<u64>();
+
+      let output = tx.output.iter().map(|output| output.value).sum::<u64>();
+
+      Amount::from_sat(input.saturating_sub(output))
+    };
+
+    // unconfirmed ancestors, including the transaction itself
+    let mut ancestors = vec![tx];
+    let mut i = 0;
+    while i < ancestors.len() {
+      for txin in &ancestors[i].input {
+        if let Some(parent) = state
+          .mempool
+          .iter()
+          .find(|tx| tx.txid() == txin.previous_output.txid)
+        {
+          if !ancestors.contains(&parent) {
+            ancestors.push(parent);
+          }
+        }
+      }
+      i += 1;
+    }
+
+    Ok(GetMempoolEntryResult {
+      vsize: tx.vsize().try_into().unwrap(),
+      weight: Some(tx.weight().to_wu()),
+      time: 0,
+      height: (state.hashes.len() - 1).try_into().unwrap(),
+      descendant_count: 1,
+      descendant_size: tx.vsize().try_into().unwrap(),
+      ancestor_count: ancestors.len().try_into().unwrap(),
+      ancestor_size: ancestors
+        .iter()
+        .map(|tx| u64::try_from(tx.vsize()).unwrap())
+        .sum(),
+      wtxid: Txid::from_raw_hash(tx.wtxid().to_raw_hash()),
+      fees: GetMempoolEntryResultFees {
+        base: fee(tx),
+        modified: fee(tx),
+        ancestor: ancestors.iter().map(|tx| fee(tx)).sum(),
+        descendant: fee(tx),
+      },
+      depends: ancestors[1..].iter().map(|tx| tx.txid()).collect(),
+      spent_by: Vec::new(),
+      bip125_replaceable: true,
+      unbroadcast: None,
+    })
+  }
+
+  fn get_tx_spending_prevout(
+    &self,
+    outputs: Vec<JsonOutPoint>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    let state = self.state();
+
+    Ok(
+      outputs
+        .into_iter()
+        .map(|output| {
+          let spending = state.mempool.iter().find(|tx| {
+            tx.input.iter().any(|txin| {
+              txin.previous_output.txid == output.txid && txin.previous_output.vout == output.vout
+            })
+          });
+
+          match spending {
+            Some(tx) => serde_json::json!({
+              "txid": output.txid,
+              "vout": output.vout,
+              "spendingtxid": tx.txid(),
+            }),
+            None => serde_json::json!({
+              "txid": output.txid,
+              "vout": output.vout,
+            }),
+          }
+        })
+        .collect(),
+    )
+  }
+
+  fn get_address_info(
+    &self,
+    address: Address<NetworkUnchecked>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    let state = self.state();
+
+    let address = address.require_network(state.network).unwrap();
+
+    Ok(serde_json::json!({
+      "address": address.to_string(),
+      "ismine": state.is_wallet_address(&address),
+    }))
+  }
+
   fn list_unspent(
     &self,
     minconf: Option<usize>,
@@ -787,9 +970,9 @@ impl Api for Server {
 
   fn get_raw_change_address(
     &self,
-    _address_type: Option<bitcoincore_rpc::json::AddressType>,
+    address_type: Option<bitcoincore_rpc::json::AddressType>,
   ) -> Result<Address, jsonrpc_core::Error> {
-    Ok(self.state().new_address(true))
+    Ok(self.state().new_address(true, address_type))
   }
 
   fn get_descriptor_info(
@@ -809,8 +992,26 @@ impl Api for Server {
     &self,
     req: Vec<ImportDescriptors>,
   ) -> Result<Vec<ImportMultiResult>, jsonrpc_core::Error> {
-    self
-      .state()
+    let mut state = self.state();
+
+    if !state.watch_only_wallets.is_empty()
+      && req.iter().any(|params| {
+        params.descriptor.contains("prv")
+          || params
+            .descriptor
+            .strip_prefix("rawtr(")
+            .and_then(|desc| desc.split(')').next())
+            .is_some_and(|key| key.len() != 64)
+      })
+    {
+      return Err(jsonrpc_core::Error {
+        code: jsonrpc_core::ErrorCode::ServerError(-4),
+        message: "Cannot import private keys to a wallet with private keys disabled".into(),
+        data: None,
+      });
+    }
+
+    state
       .descriptors
       .extend(req.into_iter().map(|params| params.descriptor));
 
@@ -824,9 +1025,9 @@ impl Api for Server {
   fn get_new_address(
     &self,
     _label: Option<String>,
-    _address_type: Option<bitcoincore_rpc::json::AddressType>,
+    address_type: Option<bitcoincore_rpc::json::AddressType>,
   ) -> Result<Address, jsonrpc_core::Error> {
-    Ok(self.state().new_address(false))
+    Ok(self.state().new_address(false, address_type))
   }
 
   fn list_transactions(
@@ -956,11 +1157,19 @@ impl Api for Server {
     &self,
     psbt: String,
     sign: Option<bool>,
-    sighash_type: Option<()>,
+    sighash_type: Option<String>,
     bip32derivs: Option<bool>,
   ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error> {
-    assert!(sighash_type.is_none());
-    assert!(bip32derivs.is_none());
+    let sighash_byte = match sighash_type.as_deref() {
+      None | Some("ALL") => None,
+      Some("SINGLE|ANYONECANPAY") => Some(0x83),
+      _ => panic!("sighash_type param not supported"),
+    };
+    assert_ne!(
+      bip32derivs,
+      Some(false),
+      "bip32derivs=false is not supported"
+    );
 
     let mut psbt = Psbt::deserialize(
       &base64::engine::general_purpose::STANDARD
@@ -970,28 +1179,25 @@ impl Api for Server {
     .unwrap();
 
     for (i, txin) in psbt.unsigned_tx.input.iter().enumerate() {
-      psbt.inputs[i].witness_utxo = Some(
-        self
-          .state()
-          .transactions
-          .get(&txin.previous_output.txid)
-          .unwrap()
-          .output[txin.previous_output.vout as usize]
-          .clone(),
-      );
+      if let Some(tx) = self.state().transactions.get(&txin.previous_output.txid) {
+        psbt.inputs[i].witness_utxo = Some(tx.output[txin.previous_output.vout as usize].clone());
+      }
     }
 
-    if let Some(sign) = sign {
-      if sign {
-        for input in psbt.inputs.iter_mut() {
-          input.final_script_witness = Some(Witness::from_slice(&[&[0; 64]]));
-        }
+    let sign = sign.unwrap_or(true);
+
+    if sign {
+      let mut signature = vec![0; 64];
+      signature.extend(sighash_byte);
+
+      for input in psbt.inputs.iter_mut() {
+        input.final_script_witness = Some(Witness::from_slice(&[&signature]));
       }
     }
 
     Ok(WalletProcessPsbtResult {
       psbt: base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
-      complete: false,
+      complete: sign,
     })
   }
 
@@ -1000,18 +1206,19 @@ impl Api for Server {
     psbt: String,
     _extract: Option<bool>,
   ) -> Result<FinalizePsbtResult, jsonrpc_core::Error> {
-    let mut transaction = Psbt::deserialize(
+    let psbt = Psbt::deserialize(
       &base64::engine::general_purpose::STANDARD
         .decode(psbt)
         .unwrap(),
     )
-    .unwrap()
-    .unsigned_tx;
+    .unwrap();
 
-    for input in &mut transaction.input {
-      if input.witness.is_empty() {
-        input.witness = Witness::from_slice(&[&[0; 64]]);
-      }
+    let mut transaction = psbt.unsigned_tx;
+
+    for (input, psbt_input) in transaction.input.iter_mut().zip(psbt.inputs) {
+      input.witness = psbt_input
+        .final_script_witness
+        .unwrap_or_else(|| Witness::from_slice(&[&[0; 64]]));
     }
 
     Ok(FinalizePsbtResult {
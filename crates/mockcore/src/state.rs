@@ -25,6 +25,7 @@ pub struct State {
   pub receive_addresses: Vec<Address>,
   pub change_addresses: Vec<Address>,
   pub wallets: BTreeSet<String>,
+  pub watch_only_wallets: BTreeSet<String>,
 }
 
 impl State {
@@ -54,14 +55,27 @@ impl State {
       utxos: BTreeMap::new(),
       version,
       wallets: BTreeSet::new(),
+      watch_only_wallets: BTreeSet::new(),
     }
   }
 
-  pub(crate) fn new_address(&mut self, change: bool) -> Address {
+  pub(crate) fn new_address(
+    &mut self,
+    change: bool,
+    address_type: Option<bitcoincore_rpc::json::AddressType>,
+  ) -> Address {
     let secp256k1 = Secp256k1::new();
     let key_pair = KeyPair::new(&secp256k1, &mut rand::thread_rng());
-    let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
-    let address = Address::p2tr(&secp256k1, public_key, None, self.network);
+    let address = if address_type == Some(bitcoincore_rpc::json::AddressType::Bech32) {
+      Address::p2wpkh(
+        &bitcoin::PublicKey::new(key_pair.public_key()),
+        self.network,
+      )
+      .unwrap()
+    } else {
+      let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
+      Address::p2tr(&secp256k1, public_key, None, self.network)
+    };
     if change {
       &mut self.change_addresses
     } else {
@@ -104,6 +118,12 @@ impl State {
 
   #[track_caller]
   pub(crate) fn mine_block(&mut self, subsidy: u64) -> Block {
+    let script_pubkey = self.new_address(false, None).into();
+    self.mine_block_to(subsidy, script_pubkey)
+  }
+
+  #[track_caller]
+  pub(crate) fn mine_block_to(&mut self, subsidy: u64, script_pubkey: ScriptBuf) -> Block {
     let coinbase = Transaction {
       version: 2,
       lock_time: LockTime::ZERO,
@@ -136,7 +156,7 @@ impl State {
               fee
             })
             .sum::<u64>(),
-        script_pubkey: self.new_address(false).into(),
+        script_pubkey,
       }],
     };
 
@@ -261,6 +281,26 @@ impl State {
     txid
   }
 
+  /// Remove mempool transactions that spend any of the same outputs as
+  /// `tx`, along with their descendants, as replacement by fee would.
+  pub(crate) fn evict_conflicts(&mut self, tx: &Transaction) {
+    let spent = tx
+      .input
+      .iter()
+      .map(|txin| txin.previous_output)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let mut evicted = BTreeSet::new();
+
+    while let Some(position) = self.mempool.iter().position(|tx| {
+      tx.input.iter().any(|txin| {
+        spent.contains(&txin.previous_output) || evicted.contains(&txin.previous_output.txid)
+      })
+    }) {
+      evicted.insert(self.mempool.remove(position).txid());
+    }
+  }
+
   pub(crate) fn mempool(&self) -> &[Transaction] {
     &self.mempool
   }
@@ -30,7 +30,7 @@ pub use {
   artifact::Artifact, cenotaph::Cenotaph, charm::Charm, decimal_sat::DecimalSat, degree::Degree,
   edict::Edict, epoch::Epoch, etching::Etching, flaw::Flaw, height::Height, pile::Pile,
   rarity::Rarity, rune::Rune, rune_id::RuneId, runestone::Runestone, sat::Sat, sat_point::SatPoint,
-  spaced_rune::SpacedRune, terms::Terms,
+  satribute::Satribute, spaced_rune::SpacedRune, terms::Terms,
 };
 
 pub const CYCLE_EPOCHS: u32 = 6;
@@ -56,6 +56,7 @@ mod rune_id;
 mod runestone;
 mod sat;
 mod sat_point;
+mod satribute;
 mod spaced_rune;
 mod terms;
 pub mod varint;
@@ -87,26 +87,27 @@ impl Sat {
   pub fn charms(self) -> u16 {
     let mut charms = 0;
 
-    if self.nineball() {
-      Charm::Nineball.set(&mut charms);
-    }
-
-    if self.coin() {
-      Charm::Coin.set(&mut charms);
-    }
-
-    match self.rarity() {
-      Rarity::Common => {}
-      Rarity::Epic => Charm::Epic.set(&mut charms),
-      Rarity::Legendary => Charm::Legendary.set(&mut charms),
-      Rarity::Mythic => Charm::Mythic.set(&mut charms),
-      Rarity::Rare => Charm::Rare.set(&mut charms),
-      Rarity::Uncommon => Charm::Uncommon.set(&mut charms),
+    for satribute in Satribute::ALL {
+      if let Some(charm) = satribute.charm() {
+        if satribute.contains(self) {
+          charm.set(&mut charms);
+        }
+      }
     }
 
     charms
   }
 
+  /// Satributes of this sat, including those which only exist on mainnet if
+  /// `mainnet` is true.
+  pub fn satributes(self, mainnet: bool) -> Vec<Satribute> {
+    Satribute::ALL
+      .into_iter()
+      .filter(|satribute| mainnet || !satribute.mainnet())
+      .filter(|satribute| satribute.contains(self))
+      .collect()
+  }
+
   fn from_name(s: &str) -> Result<Self, Error> {
     let mut x = 0;
     for c in s.chars() {
@@ -0,0 +1,385 @@
+use super::*;
+
+/// Sat categories collectors trade, each a pure function of the sat number.
+///
+/// Every satribute is listed in `Satribute::ALL`. Satributes which correspond
+/// to a `Charm` feed `Sat::charms`. To add a satribute, add a variant, list it
+/// in `ALL`, and either match it in `contains` and `ranges`, or, if it is a
+/// fixed set of sat ranges, give it a table in `table`. Satributes which
+/// describe sats by their part in mainnet's history must also be matched in
+/// `mainnet`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, DeserializeFromStr, SerializeDisplay)]
+pub enum Satribute {
+  Block78,
+  Coin,
+  Epic,
+  Hal,
+  Legendary,
+  Mythic,
+  Nineball,
+  Omega,
+  Palindrome,
+  Rare,
+  Uncommon,
+  Vintage,
+}
+
+const BLOCK: u64 = 50 * COIN_VALUE;
+
+// Blocks 0 through 999
+const VINTAGE: &[(u64, u64)] = &[(0, 1000 * BLOCK)];
+
+// Block 9
+const NINEBALL: &[(u64, u64)] = &[(9 * BLOCK, 10 * BLOCK)];
+
+// Block 78, the first block mined by Hal Finney
+const BLOCK_78: &[(u64, u64)] = &[(78 * BLOCK, 79 * BLOCK)];
+
+// The first 10 bitcoin of block 9, sent to Hal Finney in the first transaction
+const HAL: &[(u64, u64)] = &[(9 * BLOCK, 9 * BLOCK + 10 * COIN_VALUE)];
+
+impl Satribute {
+  pub const ALL: [Self; 12] = [
+    Self::Coin,
+    Self::Uncommon,
+    Self::Rare,
+    Self::Epic,
+    Self::Legendary,
+    Self::Mythic,
+    Self::Nineball,
+    Self::Omega,
+    Self::Palindrome,
+    Self::Vintage,
+    Self::Block78,
+    Self::Hal,
+  ];
+
+  pub fn charm(self) -> Option<Charm> {
+    match self {
+      Self::Coin => Some(Charm::Coin),
+      Self::Epic => Some(Charm::Epic),
+      Self::Legendary => Some(Charm::Legendary),
+      Self::Mythic => Some(Charm::Mythic),
+      Self::Nineball => Some(Charm::Nineball),
+      Self::Rare => Some(Charm::Rare),
+      Self::Uncommon => Some(Charm::Uncommon),
+      Self::Block78 | Self::Hal | Self::Omega | Self::Palindrome | Self::Vintage => None,
+    }
+  }
+
+  /// Whether this satribute describes sats by their part in mainnet's
+  /// history, and so is meaningless on other chains.
+  pub fn mainnet(self) -> bool {
+    matches!(self, Self::Block78 | Self::Hal | Self::Vintage)
+  }
+
+  fn rarity(self) -> Option<Rarity> {
+    match self {
+      Self::Epic => Some(Rarity::Epic),
+      Self::Legendary => Some(Rarity::Legendary),
+      Self::Mythic => Some(Rarity::Mythic),
+      Self::Rare => Some(Rarity::Rare),
+      Self::Uncommon => Some(Rarity::Uncommon),
+      _ => None,
+    }
+  }
+
+  fn table(self) -> Option<&'static [(u64, u64)]> {
+    match self {
+      Self::Block78 => Some(BLOCK_78),
+      Self::Nineball => Some(NINEBALL),
+      Self::Hal => Some(HAL),
+      Self::Vintage => Some(VINTAGE),
+      _ => None,
+    }
+  }
+
+  pub fn contains(self, sat: Sat) -> bool {
+    if let Some(table) = self.table() {
+      return table
+        .iter()
+        .any(|(start, end)| *start <= sat.n() && sat.n() < *end);
+    }
+
+    if let Some(rarity) = self.rarity() {
+      return !sat.common() && sat.rarity() == rarity;
+    }
+
+    match self {
+      Self::Coin => sat.coin(),
+      Self::Omega => sat.n() % COIN_VALUE == COIN_VALUE - 1,
+      Self::Palindrome => next_palindrome(sat.n()) == Some(sat.n()),
+      _ => unreachable!(),
+    }
+  }
+
+  /// Sat ranges with this satribute within `[start, end)`, without checking
+  /// every sat in between.
+  pub fn ranges(self, start: u64, end: u64) -> Vec<(u64, u64)> {
+    let end = end.min(Sat::SUPPLY);
+
+    if start >= end {
+      return Vec::new();
+    }
+
+    if let Some(table) = self.table() {
+      return table
+        .iter()
+        .map(|(range_start, range_end)| (start.max(*range_start), end.min(*range_end)))
+        .filter(|(start, end)| start < end)
+        .collect();
+    }
+
+    let sats: Vec<u64> = if self.rarity().is_some() {
+      (Sat(start).height().n()..=Sat(end - 1).height().n())
+        .map(|height| Height(height).starting_sat().n())
+        .filter(|sat| (start..end).contains(sat) && self.contains(Sat(*sat)))
+        .collect()
+    } else {
+      match self {
+        Self::Coin => (start.next_multiple_of(COIN_VALUE)..end)
+          .step_by(COIN_VALUE.try_into().unwrap())
+          .collect(),
+        Self::Omega => ((start + 1).next_multiple_of(COIN_VALUE) - 1..end)
+          .step_by(COIN_VALUE.try_into().unwrap())
+          .collect(),
+        Self::Palindrome => {
+          std::iter::successors(next_palindrome(start), |sat| next_palindrome(sat + 1))
+            .take_while(|sat| *sat < end)
+            .collect()
+        }
+        _ => unreachable!(),
+      }
+    };
+
+    sats.into_iter().map(|sat| (sat, sat + 1)).collect()
+  }
+}
+
+/// The smallest decimal palindrome greater than or equal to `n`.
+fn next_palindrome(n: u64) -> Option<u64> {
+  fn mirror(half: &str, len: usize) -> Option<u64> {
+    half
+      .chars()
+      .chain(half[..len / 2].chars().rev())
+      .collect::<String>()
+      .parse()
+      .ok()
+  }
+
+  let digits = n.to_string();
+  let len = digits.len();
+  let half = &digits[..len.div_ceil(2)];
+
+  let candidate = mirror(half, len)?;
+
+  if candidate >= n {
+    return Some(candidate);
+  }
+
+  // mirroring a half of all nines is never less than `n`, so incrementing
+  // the half never changes its length
+  mirror(&(half.parse::<u64>().ok()? + 1).to_string(), len)
+}
+
+impl Display for Satribute {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::Block78 => "block-78",
+        Self::Coin => "coin",
+        Self::Epic => "epic",
+        Self::Hal => "hal",
+        Self::Legendary => "legendary",
+        Self::Mythic => "mythic",
+        Self::Nineball => "nineball",
+        Self::Omega => "omega",
+        Self::Palindrome => "palindrome",
+        Self::Rare => "rare",
+        Self::Uncommon => "uncommon",
+        Self::Vintage => "vintage",
+      }
+    )
+  }
+}
+
+impl FromStr for Satribute {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Ok(match s {
+      "alpha" | "coin" => Self::Coin,
+      "block-78" => Self::Block78,
+      "block-9" | "nineball" => Self::Nineball,
+      "epic" => Self::Epic,
+      "first-transaction" | "hal" => Self::Hal,
+      "legendary" => Self::Legendary,
+      "mythic" => Self::Mythic,
+      "omega" => Self::Omega,
+      "palindrome" => Self::Palindrome,
+      "rare" => Self::Rare,
+      "uncommon" => Self::Uncommon,
+      "vintage" => Self::Vintage,
+      _ => return Err(format!("invalid satribute `{s}`")),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_trip() {
+    for satribute in Satribute::ALL {
+      assert_eq!(
+        satribute.to_string().parse::<Satribute>().unwrap(),
+        satribute
+      );
+    }
+  }
+
+  #[test]
+  fn aliases() {
+    assert_eq!("alpha".parse::<Satribute>().unwrap(), Satribute::Coin);
+    assert_eq!("block-9".parse::<Satribute>().unwrap(), Satribute::Nineball);
+    assert_eq!(
+      "first-transaction".parse::<Satribute>().unwrap(),
+      Satribute::Hal
+    );
+    assert_eq!(
+      "foo".parse::<Satribute>().unwrap_err(),
+      "invalid satribute `foo`"
+    );
+  }
+
+  #[test]
+  fn palindromes() {
+    assert_eq!(next_palindrome(0), Some(0));
+    assert_eq!(next_palindrome(10), Some(11));
+    assert_eq!(next_palindrome(99), Some(99));
+    assert_eq!(next_palindrome(100), Some(101));
+    assert_eq!(next_palindrome(1999), Some(2002));
+    assert_eq!(next_palindrome(12345), Some(12421));
+    assert_eq!(next_palindrome(u64::MAX), None);
+
+    assert!(Satribute::Palindrome.contains(Sat(1234321)));
+    assert!(!Satribute::Palindrome.contains(Sat(1234320)));
+  }
+
+  #[test]
+  fn contains() {
+    assert!(Satribute::Coin.contains(Sat(COIN_VALUE)));
+    assert!(!Satribute::Coin.contains(Sat(COIN_VALUE + 1)));
+    assert!(Satribute::Omega.contains(Sat(COIN_VALUE - 1)));
+    assert!(!Satribute::Omega.contains(Sat(COIN_VALUE)));
+    assert!(Satribute::Vintage.contains(Sat(1000 * BLOCK - 1)));
+    assert!(!Satribute::Vintage.contains(Sat(1000 * BLOCK)));
+    assert!(Satribute::Block78.contains(Sat(78 * BLOCK)));
+    assert!(!Satribute::Block78.contains(Sat(79 * BLOCK)));
+    assert!(Satribute::Hal.contains(Sat(9 * BLOCK + 10 * COIN_VALUE - 1)));
+    assert!(!Satribute::Hal.contains(Sat(9 * BLOCK + 10 * COIN_VALUE)));
+    assert!(Satribute::Hal.contains(Sat(9 * BLOCK)));
+    assert!(!Satribute::Hal.contains(Sat(9 * BLOCK - 1)));
+    assert!(Satribute::Nineball.contains(Sat(9 * BLOCK + 10 * COIN_VALUE)));
+    assert!(Satribute::Nineball.contains(Sat(10 * BLOCK - 1)));
+    assert!(!Satribute::Nineball.contains(Sat(10 * BLOCK)));
+    assert!(!Satribute::Block78.contains(Sat(78 * BLOCK - 1)));
+    assert!(Satribute::Block78.contains(Sat(79 * BLOCK - 1)));
+    assert!(Satribute::Mythic.contains(Sat(0)));
+    assert!(!Satribute::Uncommon.contains(Sat(0)));
+    assert!(Satribute::Uncommon.contains(Sat(BLOCK)));
+    assert!(!Satribute::Uncommon.contains(Sat(BLOCK + 1)));
+  }
+
+  #[test]
+  fn mainnet_satributes() {
+    assert_eq!(
+      Sat(0).satributes(true),
+      [
+        Satribute::Coin,
+        Satribute::Mythic,
+        Satribute::Palindrome,
+        Satribute::Vintage
+      ],
+    );
+
+    assert_eq!(
+      Sat(0).satributes(false),
+      [Satribute::Coin, Satribute::Mythic, Satribute::Palindrome],
+    );
+  }
+
+  #[test]
+  fn ranges_agree_with_contains() {
+    for satribute in Satribute::ALL {
+      for (start, end) in [
+        (0, 2000),
+        (BLOCK - 1000, BLOCK + 1000),
+        (12320, 12330),
+        (9 * BLOCK - 1000, 9 * BLOCK + 1000),
+        (
+          9 * BLOCK + 10 * COIN_VALUE - 1000,
+          9 * BLOCK + 10 * COIN_VALUE + 1000,
+        ),
+        (10 * BLOCK - 1000, 10 * BLOCK + 1000),
+        (78 * BLOCK - 1000, 78 * BLOCK + 1000),
+        (79 * BLOCK - 1000, 79 * BLOCK + 1000),
+        (1000 * BLOCK - 1000, 1000 * BLOCK + 1000),
+      ] {
+        let expected = (start..end)
+          .filter(|sat| satribute.contains(Sat(*sat)))
+          .collect::<Vec<u64>>();
+
+        let actual = satribute
+          .ranges(start, end)
+          .into_iter()
+          .flat_map(|(start, end)| start..end)
+          .collect::<Vec<u64>>();
+
+        assert_eq!(actual, expected, "{satribute}");
+      }
+    }
+  }
+
+  #[test]
+  fn ranges() {
+    assert_eq!(
+      Satribute::Coin.ranges(1, 3 * COIN_VALUE),
+      [
+        (COIN_VALUE, COIN_VALUE + 1),
+        (2 * COIN_VALUE, 2 * COIN_VALUE + 1)
+      ],
+    );
+
+    assert_eq!(
+      Satribute::Uncommon.ranges(BLOCK, 3 * BLOCK + 1),
+      [
+        (BLOCK, BLOCK + 1),
+        (2 * BLOCK, 2 * BLOCK + 1),
+        (3 * BLOCK, 3 * BLOCK + 1)
+      ],
+    );
+
+    assert_eq!(
+      Satribute::Vintage.ranges(999 * BLOCK, 1001 * BLOCK),
+      [(999 * BLOCK, 1000 * BLOCK)],
+    );
+
+    assert_eq!(Satribute::Block78.ranges(0, BLOCK), []);
+    assert_eq!(Satribute::Coin.ranges(5, 5), []);
+  }
+
+  #[test]
+  fn charms() {
+    assert_eq!(
+      Satribute::ALL
+        .into_iter()
+        .filter_map(Satribute::charm)
+        .collect::<Vec<Charm>>(),
+      Charm::ALL[..7],
+    );
+  }
+}
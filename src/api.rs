@@ -3,11 +3,23 @@ use {
   serde_hex::{SerHex, Strict},
 };
 
-pub use crate::templates::{
-  BlocksHtml as Blocks, RuneHtml as Rune, RunesHtml as Runes, StatusHtml as Status,
-  TransactionHtml as Transaction,
+pub use crate::{
+  index::event::{Event, EventKind},
+  templates::{
+    BlocksHtml as Blocks, CollectionVerificationHtml as CollectionVerification, RuneHtml as Rune,
+    RunesHtml as Runes, SatTraceHtml as SatTrace, StatusHtml as Status,
+    TransactionHtml as Transaction,
+  },
 };
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct AddressInfo {
+  pub inscriptions: Vec<InscriptionId>,
+  pub outputs: Vec<OutPoint>,
+  pub runes_balances: Vec<(SpacedRune, Pile)>,
+  pub sat_balance: u64,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Block {
   pub best_height: u32,
@@ -76,6 +88,36 @@ pub struct Children {
   pub page: usize,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ContentHashInscriptions {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionHistory {
+  pub id: InscriptionId,
+  pub more: bool,
+  pub page: usize,
+  pub transfers: Vec<InscriptionTransfer>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct InscriptionSearch {
+  pub ids: Vec<InscriptionId>,
+  pub more: bool,
+  pub page: usize,
+  pub terms: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct InscriptionTransfer {
+  pub height: u32,
+  pub satpoint: SatPoint,
+  pub txid: Txid,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct Inscription {
   pub address: Option<String>,
@@ -162,6 +204,33 @@ impl Output {
   }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneBalanceHistory {
+  pub address: Address<NetworkUnchecked>,
+  pub history: Vec<RuneBalances>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneBalances {
+  pub balances: Vec<(SpacedRune, Pile)>,
+  pub height: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolder {
+  pub address: Option<Address<NetworkUnchecked>>,
+  pub amount: u128,
+  pub outpoint: OutPoint,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RuneHolders {
+  pub height: u32,
+  pub holders: Vec<RuneHolder>,
+  pub id: RuneId,
+  pub rune: SpacedRune,
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Sat {
   pub block: u32,
@@ -178,6 +247,7 @@ pub struct Sat {
   pub period: u32,
   pub rarity: Rarity,
   pub satpoint: Option<SatPoint>,
+  pub satributes: Vec<Satribute>,
   pub timestamp: i64,
 }
 
@@ -192,3 +262,24 @@ pub struct SatInscriptions {
   pub more: bool,
   pub page: u64,
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct SatLocation {
+  pub height: u32,
+  pub satpoint: SatPoint,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScanOutput {
+  pub output: OutPoint,
+  pub sats: Option<Vec<ScanRange>>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScanRange {
+  pub charms: Vec<Charm>,
+  pub offset: u64,
+  pub rarity: Rarity,
+  pub sat: ordinals::Sat,
+  pub size: u64,
+}
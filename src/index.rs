@@ -11,6 +11,7 @@ use {
   },
   super::*,
   crate::{
+    inscriptions::manifest::Collection,
     runes::MintError,
     subcommand::{find::FindRangeOutput, server::query},
     templates::StatusHtml,
@@ -25,12 +26,13 @@ use {
   log::log_enabled,
   redb::{
     Database, DatabaseError, MultimapTable, MultimapTableDefinition, MultimapTableHandle,
-    ReadOnlyTable, ReadableMultimapTable, ReadableTable, ReadableTableMetadata, RepairSession,
-    StorageError, Table, TableDefinition, TableHandle, TableStats, WriteTransaction,
+    MultimapValue, ReadOnlyTable, ReadableMultimapTable, ReadableTable, ReadableTableMetadata,
+    RepairSession, StorageError, Table, TableDefinition, TableHandle, TableStats, WriteTransaction,
   },
   std::{
     collections::HashMap,
     io::{BufWriter, Write},
+    ops::RangeInclusive,
     sync::Once,
   },
 };
@@ -43,24 +45,36 @@ mod fetcher;
 mod lot;
 mod reorg;
 mod rtx;
+mod scan;
+pub(crate) mod search;
 mod updater;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 25;
+const SCHEMA_VERSION: u64 = 34;
 
+define_multimap_table! { CONTENT_HASH_TO_SEQUENCE_NUMBERS, &[u8; 32], u32 }
+define_multimap_table! { RUNE_ID_TO_HOLDERS, RuneIdValue, (u128, &OutPointValue) }
+define_multimap_table! { RUNE_ID_TO_OUTPOINT_HISTORY, RuneIdValue, (u32, &OutPointValue) }
 define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
 define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
+define_multimap_table! { SEARCH_TERM_TO_SEQUENCE_NUMBERS, &str, u32 }
+define_multimap_table! { SCRIPT_PUBKEY_TO_OUTPOINT, &[u8], &OutPointValue }
+define_multimap_table! { SCRIPT_PUBKEY_TO_RUNE_OUTPOINT_HISTORY, &[u8], (u32, &OutPointValue) }
 define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
+define_multimap_table! { SEQUENCE_NUMBER_TO_TRANSFERS, u32, (u32, u32, &SatPointValue, &TxidValue) }
 define_table! { CONTENT_TYPE_TO_COUNT, Option<&[u8]>, u64 }
+define_table! { EVENT_LOG, (u32, u32, u32), &[u8] }
 define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
 define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
 define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
 define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
 define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
 define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_RUNE_CREATION, &OutPointValue, (u32, &[u8]) }
 define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
+define_table! { OUTPOINT_TO_TXOUT, &OutPointValue, &[u8] }
 define_table! { OUTPOINT_TO_VALUE, &OutPointValue, u64}
 define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
 define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
@@ -68,6 +82,7 @@ define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
 define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
 define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
 define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
+define_table! { SPENT_OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, (u32, &TxidValue, &[u8]) }
 define_table! { STATISTIC_TO_COUNT, u64, u64 }
 define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
 define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
@@ -90,6 +105,12 @@ pub(crate) enum Statistic {
   IndexTransactions = 12,
   IndexSpentSats = 13,
   InitialSyncTime = 14,
+  IndexAddresses = 15,
+  IndexEvents = 16,
+  IndexSpentRunes = 17,
+  IndexContentHashes = 18,
+  IndexSearch = 19,
+  IndexTransferHistory = 20,
 }
 
 impl Statistic {
@@ -189,10 +210,16 @@ pub struct Index {
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_addresses: bool,
+  index_content_hashes: bool,
+  index_events: bool,
   index_runes: bool,
   index_sats: bool,
+  index_search: bool,
+  index_spent_runes: bool,
   index_spent_sats: bool,
   index_transactions: bool,
+  index_transfer_history: bool,
   settings: Settings,
   path: PathBuf,
   started: DateTime<Utc>,
@@ -296,16 +323,26 @@ impl Index {
 
         tx.set_durability(durability);
 
+        tx.open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBERS)?;
+        tx.open_multimap_table(RUNE_ID_TO_HOLDERS)?;
+        tx.open_multimap_table(RUNE_ID_TO_OUTPOINT_HISTORY)?;
         tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SEARCH_TERM_TO_SEQUENCE_NUMBERS)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+        tx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT_HISTORY)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+        tx.open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFERS)?;
         tx.open_table(CONTENT_TYPE_TO_COUNT)?;
+        tx.open_table(EVENT_LOG)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
         tx.open_table(HOME_INSCRIPTIONS)?;
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(OUTPOINT_TO_RUNE_CREATION)?;
+        tx.open_table(OUTPOINT_TO_TXOUT)?;
         tx.open_table(OUTPOINT_TO_VALUE)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
@@ -313,6 +350,7 @@ impl Index {
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
         tx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
         tx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+        tx.open_table(SPENT_OUTPOINT_TO_RUNE_BALANCES)?;
         tx.open_table(TRANSACTION_ID_TO_RUNE)?;
         tx.open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?;
 
@@ -324,10 +362,28 @@ impl Index {
             outpoint_to_sat_ranges.insert(&OutPoint::null().store(), [].as_slice())?;
           }
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexAddresses,
+            u64::from(settings.index_addresses()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexContentHashes,
+            u64::from(settings.index_content_hashes()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexEvents,
+            u64::from(settings.index_events()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexRunes,
-            u64::from(settings.index_runes()),
+            u64::from(settings.index_runes() || settings.index_spent_runes()),
           )?;
 
           Self::set_statistic(
@@ -336,6 +392,18 @@ impl Index {
             u64::from(settings.index_sats() || settings.index_spent_sats()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexSearch,
+            u64::from(settings.index_search()),
+          )?;
+
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexSpentRunes,
+            u64::from(settings.index_spent_runes()),
+          )?;
+
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexSpentSats,
@@ -348,10 +416,18 @@ impl Index {
             u64::from(settings.index_transactions()),
           )?;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexTransferHistory,
+            u64::from(settings.index_transfer_history()),
+          )?;
+
           Self::set_statistic(&mut statistics, Statistic::Schema, SCHEMA_VERSION)?;
         }
 
-        if settings.index_runes() && settings.chain() == Chain::Mainnet {
+        if (settings.index_runes() || settings.index_spent_runes())
+          && settings.chain() == Chain::Mainnet
+        {
           let rune = Rune(2055900680524219742);
 
           let id = RuneId { block: 1, tx: 0 };
@@ -402,18 +478,31 @@ impl Index {
       Err(error) => bail!("failed to open index: {error}"),
     };
 
+    let index_addresses;
+    let index_content_hashes;
+    let index_events;
     let index_runes;
     let index_sats;
+    let index_search;
+    let index_spent_runes;
     let index_spent_sats;
     let index_transactions;
+    let index_transfer_history;
 
     {
       let tx = database.begin_read()?;
       let statistics = tx.open_table(STATISTIC_TO_COUNT)?;
+      index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
+      index_content_hashes = Self::is_statistic_set(&statistics, Statistic::IndexContentHashes)?;
+      index_events = Self::is_statistic_set(&statistics, Statistic::IndexEvents)?;
       index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
       index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
+      index_search = Self::is_statistic_set(&statistics, Statistic::IndexSearch)?;
+      index_spent_runes = Self::is_statistic_set(&statistics, Statistic::IndexSpentRunes)?;
       index_spent_sats = Self::is_statistic_set(&statistics, Statistic::IndexSpentSats)?;
       index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
+      index_transfer_history =
+        Self::is_statistic_set(&statistics, Statistic::IndexTransferHistory)?;
     }
 
     let genesis_block_coinbase_transaction =
@@ -428,10 +517,16 @@ impl Index {
       first_inscription_height: settings.first_inscription_height(),
       genesis_block_coinbase_transaction,
       height_limit: settings.height_limit(),
+      index_addresses,
+      index_content_hashes,
+      index_events,
       index_runes,
       index_sats,
+      index_search,
+      index_spent_runes,
       index_spent_sats,
       index_transactions,
+      index_transfer_history,
       settings: settings.clone(),
       path,
       started: Utc::now(),
@@ -455,14 +550,42 @@ impl Index {
     )
   }
 
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
+  pub(crate) fn has_content_hash_index(&self) -> bool {
+    self.index_content_hashes
+  }
+
+  pub(crate) fn has_event_index(&self) -> bool {
+    self.index_events
+  }
+
   pub(crate) fn has_rune_index(&self) -> bool {
     self.index_runes
   }
 
+  pub(crate) fn has_spent_rune_index(&self) -> bool {
+    self.index_spent_runes
+  }
+
+  pub(crate) fn has_spent_sat_index(&self) -> bool {
+    self.index_spent_sats
+  }
+
+  pub(crate) fn has_transfer_history_index(&self) -> bool {
+    self.index_transfer_history
+  }
+
   pub(crate) fn has_sat_index(&self) -> bool {
     self.index_sats
   }
 
+  pub(crate) fn has_search_index(&self) -> bool {
+    self.index_search
+  }
+
   pub(crate) fn status(&self) -> Result<StatusHtml> {
     let rtx = self.database.begin_read()?;
 
@@ -501,6 +624,7 @@ impl Index {
     content_type_counts.sort_by_key(|(_content_type, count)| Reverse(*count));
 
     Ok(StatusHtml {
+      address_index: statistic(Statistic::IndexAddresses)? != 0,
       blessed_inscriptions,
       chain: self.settings.chain(),
       content_type_counts,
@@ -1007,6 +1131,209 @@ impl Index {
     Ok(balances)
   }
 
+  pub(crate) fn get_rune_top_holders(
+    &self,
+    id: RuneId,
+    n: usize,
+  ) -> Result<(u64, Vec<(OutPoint, u128)>)> {
+    let holders = self
+      .database
+      .begin_read()?
+      .open_multimap_table(RUNE_ID_TO_HOLDERS)?
+      .get(id.store())?;
+
+    let count = holders.len();
+
+    let top = holders
+      .rev()
+      .take(n)
+      .map(|result| {
+        result.map(|holder| {
+          let (amount, outpoint) = holder.value();
+          (OutPoint::load(*outpoint), amount)
+        })
+      })
+      .collect::<Result<Vec<(OutPoint, u128)>, StorageError>>()?;
+
+    Ok((count, top))
+  }
+
+  /// Rune balances of `outpoint`, and the height it was spent at, if it was
+  /// spent, read from the spent rune index.
+  fn get_rune_outpoint_history(
+    outpoint_to_balances: &ReadOnlyTable<&OutPointValue, &[u8]>,
+    spent_outpoint_to_balances: &ReadOnlyTable<&OutPointValue, (u32, &TxidValue, &[u8])>,
+    outpoint: &OutPointValue,
+  ) -> Result<(Option<u32>, Vec<(RuneId, u128)>)> {
+    let spent = spent_outpoint_to_balances.get(outpoint)?;
+
+    let unspent;
+
+    let (spent_height, buffer) = match &spent {
+      Some(spent) => {
+        let (spent_height, _txid, buffer) = spent.value();
+        (Some(spent_height), buffer)
+      }
+      None => {
+        unspent = outpoint_to_balances
+          .get(outpoint)?
+          .ok_or_else(|| anyhow!("missing rune balances for {}", OutPoint::load(*outpoint)))?;
+
+        (None, unspent.value())
+      }
+    };
+
+    let mut balances = Vec::new();
+    let mut i = 0;
+    while i < buffer.len() {
+      let (balance, len) = Index::decode_rune_balance(&buffer[i..])?;
+      i += len;
+      balances.push(balance);
+    }
+
+    Ok((spent_height, balances))
+  }
+
+  pub(crate) fn get_rune_holders_at_height(
+    &self,
+    id: RuneId,
+    height: u32,
+  ) -> Result<Vec<(OutPoint, ScriptBuf, u128)>> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let outpoint_to_creation = rtx.open_table(OUTPOINT_TO_RUNE_CREATION)?;
+    let spent_outpoint_to_balances = rtx.open_table(SPENT_OUTPOINT_TO_RUNE_BALANCES)?;
+
+    let mut holders = Vec::new();
+
+    // outpoints are ordered by the height they were created at
+    for result in rtx
+      .open_multimap_table(RUNE_ID_TO_OUTPOINT_HISTORY)?
+      .get(id.store())?
+    {
+      let guard = result?;
+      let (created, outpoint) = guard.value();
+
+      if created > height {
+        break;
+      }
+
+      let (spent, balances) = Self::get_rune_outpoint_history(
+        &outpoint_to_balances,
+        &spent_outpoint_to_balances,
+        outpoint,
+      )?;
+
+      if spent.is_some_and(|spent| spent <= height) {
+        continue;
+      }
+
+      let script_pubkey = outpoint_to_creation
+        .get(outpoint)?
+        .map(|creation| ScriptBuf::from_bytes(creation.value().1.to_vec()))
+        .unwrap_or_default();
+
+      for (rune_id, amount) in balances {
+        if rune_id == id {
+          holders.push((OutPoint::load(*outpoint), script_pubkey.clone(), amount));
+        }
+      }
+    }
+
+    Ok(holders)
+  }
+
+  /// The rune balances held by `script_pubkey` after each block in which
+  /// they changed.
+  pub(crate) fn get_script_pubkey_rune_history(
+    &self,
+    script_pubkey: &Script,
+  ) -> Result<Vec<(u32, Vec<(SpacedRune, Pile)>)>> {
+    let rtx = self.database.begin_read()?;
+
+    let id_to_rune_entries = rtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+    let outpoint_to_balances = rtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let spent_outpoint_to_balances = rtx.open_table(SPENT_OUTPOINT_TO_RUNE_BALANCES)?;
+
+    let mut changes = BTreeMap::<u32, Vec<(RuneId, u128, bool)>>::new();
+
+    for result in rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT_HISTORY)?
+      .get(script_pubkey.as_bytes())?
+    {
+      let guard = result?;
+      let (created, outpoint) = guard.value();
+
+      let (spent, balances) = Self::get_rune_outpoint_history(
+        &outpoint_to_balances,
+        &spent_outpoint_to_balances,
+        outpoint,
+      )?;
+
+      for (rune_id, amount) in balances {
+        changes
+          .entry(created)
+          .or_default()
+          .push((rune_id, amount, true));
+
+        if let Some(spent) = spent {
+          changes
+            .entry(spent)
+            .or_default()
+            .push((rune_id, amount, false));
+        }
+      }
+    }
+
+    let mut balances = BTreeMap::<RuneId, u128>::new();
+    let mut history = Vec::new();
+
+    for (height, changes) in changes {
+      for (rune_id, amount, received) in changes {
+        let balance = balances.entry(rune_id).or_default();
+
+        *balance = if received {
+          balance
+            .checked_add(amount)
+            .ok_or_else(|| anyhow!("rune {rune_id} balance overflow at height {height}"))?
+        } else {
+          balance
+            .checked_sub(amount)
+            .ok_or_else(|| anyhow!("rune {rune_id} balance underflow at height {height}"))?
+        };
+      }
+
+      balances.retain(|_, balance| *balance > 0);
+
+      history.push((
+        height,
+        balances
+          .iter()
+          .map(|(rune_id, balance)| {
+            let entry = RuneEntry::load(
+              id_to_rune_entries
+                .get(rune_id.store())?
+                .ok_or_else(|| anyhow!("rune {rune_id} entry not found"))?
+                .value(),
+            );
+
+            Ok((
+              entry.spaced_rune,
+              Pile {
+                amount: *balance,
+                divisibility: entry.divisibility,
+                symbol: entry.symbol,
+              },
+            ))
+          })
+          .collect::<Result<Vec<(SpacedRune, Pile)>>>()?,
+      ));
+    }
+
+    Ok(history)
+  }
+
   pub(crate) fn get_rune_balance_map(
     &self,
   ) -> Result<BTreeMap<SpacedRune, BTreeMap<OutPoint, Pile>>> {
@@ -1173,6 +1500,51 @@ impl Index {
       .map(|(children, _more)| children)
   }
 
+  pub(crate) fn verify_collection(
+    &self,
+    parent: InscriptionId,
+  ) -> Result<Option<api::CollectionVerification>> {
+    let Some(entry) = self.get_inscription_entry(parent)? else {
+      return Ok(None);
+    };
+
+    // Verify against a single snapshot of the parent's children, so that a
+    // reorg during verification cannot mix children from both chains.
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_entry = rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut collection = Collection::default();
+
+    for result in rtx
+      .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+      .get(entry.sequence_number)?
+    {
+      let child = InscriptionEntry::load(
+        sequence_number_to_entry
+          .get(result?.value())?
+          .unwrap()
+          .value(),
+      )
+      .id;
+
+      collection.add(child, self.get_inscription_by_id(child)?.as_ref());
+    }
+
+    let Some((manifest_id, verification)) = collection.verify() else {
+      return Ok(None);
+    };
+
+    Ok(Some(api::CollectionVerification {
+      manifest: manifest_id,
+      mismatched: verification.mismatched,
+      missing: verification.missing,
+      parent,
+      parent_number: entry.inscription_number,
+      verified: verification.verified,
+    }))
+  }
+
   #[cfg(test)]
   pub(crate) fn get_parents_by_inscription_id(
     &self,
@@ -1251,6 +1623,73 @@ impl Index {
     Ok((children, more))
   }
 
+  pub(crate) fn get_transfers_by_sequence_number_paginated(
+    &self,
+    sequence_number: u32,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<api::InscriptionTransfer>, bool)> {
+    let mut transfers = self
+      .database
+      .begin_read()?
+      .open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFERS)?
+      .get(sequence_number)?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        let transfer = result?;
+        let (height, _index, satpoint, txid) = transfer.value();
+        Ok(api::InscriptionTransfer {
+          height,
+          satpoint: SatPoint::load(*satpoint),
+          txid: Txid::load(*txid),
+        })
+      })
+      .collect::<Result<Vec<api::InscriptionTransfer>>>()?;
+
+    let more = transfers.len() > page_size;
+
+    if more {
+      transfers.pop();
+    }
+
+    Ok((transfers, more))
+  }
+
+  /// Returns the most recent `count` transfers of an inscription, oldest
+  /// first, and whether there are any earlier transfers.
+  pub(crate) fn get_latest_transfers_by_sequence_number(
+    &self,
+    sequence_number: u32,
+    count: usize,
+  ) -> Result<(Vec<api::InscriptionTransfer>, bool)> {
+    let transfers = self
+      .database
+      .begin_read()?
+      .open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFERS)?
+      .get(sequence_number)?;
+
+    let earlier = transfers.len() > u64::try_from(count).unwrap();
+
+    let mut transfers = transfers
+      .rev()
+      .take(count)
+      .map(|result| {
+        let transfer = result?;
+        let (height, _index, satpoint, txid) = transfer.value();
+        Ok(api::InscriptionTransfer {
+          height,
+          satpoint: SatPoint::load(*satpoint),
+          txid: Txid::load(*txid),
+        })
+      })
+      .collect::<Result<Vec<api::InscriptionTransfer>>>()?;
+
+    transfers.reverse();
+
+    Ok((transfers, earlier))
+  }
+
   pub(crate) fn get_parents_by_sequence_number_paginated(
     &self,
     parent_sequence_numbers: Vec<u32>,
@@ -1360,65 +1799,220 @@ impl Index {
     Ok((ids, more))
   }
 
-  pub(crate) fn get_inscription_id_by_sat_indexed(
+  pub(crate) fn get_inscription_ids_by_content_hash_paginated(
     &self,
-    sat: Sat,
-    inscription_index: isize,
-  ) -> Result<Option<InscriptionId>> {
+    content_hash: sha256::Hash,
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
     let sequence_number_to_inscription_entry =
       rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
 
-    let sat_to_sequence_number = rtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+    let mut ids = rtx
+      .open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBERS)?
+      .get(&content_hash.to_byte_array())?
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|result| {
+        result
+          .and_then(|sequence_number| {
+            sequence_number_to_inscription_entry
+              .get(sequence_number.value())
+              .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+          })
+          .map_err(|err| err.into())
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
 
-    if inscription_index < 0 {
-      sat_to_sequence_number
-        .get(&sat.n())?
-        .nth_back((inscription_index + 1).abs_diff(0))
-    } else {
-      sat_to_sequence_number
-        .get(&sat.n())?
-        .nth(inscription_index.abs_diff(0))
+    let more = ids.len() > page_size;
+
+    if more {
+      ids.pop();
     }
-    .map(|result| {
-      result
-        .and_then(|sequence_number| {
-          let sequence_number = sequence_number.value();
-          sequence_number_to_inscription_entry
-            .get(sequence_number)
-            .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
-        })
-        .map_err(|err| anyhow!(err.to_string()))
-    })
-    .transpose()
+
+    Ok((ids, more))
   }
 
-  #[cfg(test)]
-  pub(crate) fn get_inscription_id_by_inscription_number(
+  pub(crate) fn search_inscriptions_paginated(
     &self,
-    inscription_number: i32,
-  ) -> Result<Option<InscriptionId>> {
+    terms: &[String],
+    page_size: usize,
+    page_index: usize,
+  ) -> Result<(Vec<InscriptionId>, bool)> {
     let rtx = self.database.begin_read()?;
 
-    let Some(sequence_number) = rtx
-      .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
-      .get(inscription_number)?
-      .map(|guard| guard.value())
-    else {
-      return Ok(None);
-    };
+    let search_term_to_sequence_numbers =
+      rtx.open_multimap_table(SEARCH_TERM_TO_SEQUENCE_NUMBERS)?;
 
-    let inscription_id = rtx
-      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
-      .get(&sequence_number)?
-      .map(|entry| InscriptionEntry::load(entry.value()).id);
+    let mut postings = terms
+      .iter()
+      .map(|term| Ok(search_term_to_sequence_numbers.get(term.as_str())?))
+      .collect::<Result<Vec<MultimapValue<u32>>>>()?;
 
-    Ok(inscription_id)
-  }
+    postings.sort_by_key(|posting| posting.len());
 
-  pub(crate) fn get_inscription_satpoint_by_id(
-    &self,
+    let mut postings = postings.into_iter().map(Iterator::rev);
+
+    let Some(rarest) = postings.next() else {
+      return Ok((Vec::new(), false));
+    };
+
+    // The other terms' sequence numbers are walked in step with those of the
+    // rarest term, newest first, so only as many sequence numbers are read as
+    // are needed to fill the page.
+    let mut others = postings
+      .map(|mut posting| {
+        let current = posting.next().transpose()?.map(|guard| guard.value());
+        Ok((posting, current))
+      })
+      .collect::<Result<Vec<_>, StorageError>>()?;
+
+    let needed = page_index
+      .saturating_add(1)
+      .saturating_mul(page_size)
+      .saturating_add(1);
+
+    let mut matches = Vec::new();
+
+    'rarest: for result in rarest {
+      let sequence_number = result?.value();
+
+      for (posting, current) in &mut others {
+        while current.is_some_and(|current| current > sequence_number) {
+          *current = posting.next().transpose()?.map(|guard| guard.value());
+        }
+
+        match current {
+          Some(current) if *current == sequence_number => {}
+          Some(_) => continue 'rarest,
+          None => break 'rarest,
+        }
+      }
+
+      matches.push(sequence_number);
+
+      if matches.len() == needed {
+        break;
+      }
+    }
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let mut ids = matches
+      .into_iter()
+      .skip(page_index.saturating_mul(page_size))
+      .take(page_size.saturating_add(1))
+      .map(|sequence_number| {
+        Ok(
+          InscriptionEntry::load(
+            sequence_number_to_inscription_entry
+              .get(sequence_number)?
+              .unwrap()
+              .value(),
+          )
+          .id,
+        )
+      })
+      .collect::<Result<Vec<InscriptionId>>>()?;
+
+    let more = ids.len() > page_size;
+
+    if more {
+      ids.pop();
+    }
+
+    Ok((ids, more))
+  }
+
+  pub(crate) fn get_first_inscription_by_content_hash(
+    &self,
+    content_hash: sha256::Hash,
+  ) -> Result<Option<(InscriptionId, i32)>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBERS)?
+      .get(&content_hash.to_byte_array())?
+      .next()
+      .transpose()?
+      .map(|sequence_number| sequence_number.value())
+    else {
+      return Ok(None);
+    };
+
+    Ok(
+      rtx
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .get(sequence_number)?
+        .map(|entry| {
+          let entry = InscriptionEntry::load(entry.value());
+          (entry.id, entry.inscription_number)
+        }),
+    )
+  }
+
+  pub(crate) fn get_inscription_id_by_sat_indexed(
+    &self,
+    sat: Sat,
+    inscription_index: isize,
+  ) -> Result<Option<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let sequence_number_to_inscription_entry =
+      rtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    let sat_to_sequence_number = rtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+
+    if inscription_index < 0 {
+      sat_to_sequence_number
+        .get(&sat.n())?
+        .nth_back((inscription_index + 1).abs_diff(0))
+    } else {
+      sat_to_sequence_number
+        .get(&sat.n())?
+        .nth(inscription_index.abs_diff(0))
+    }
+    .map(|result| {
+      result
+        .and_then(|sequence_number| {
+          let sequence_number = sequence_number.value();
+          sequence_number_to_inscription_entry
+            .get(sequence_number)
+            .map(|entry| InscriptionEntry::load(entry.unwrap().value()).id)
+        })
+        .map_err(|err| anyhow!(err.to_string()))
+    })
+    .transpose()
+  }
+
+  #[cfg(test)]
+  pub(crate) fn get_inscription_id_by_inscription_number(
+    &self,
+    inscription_number: i32,
+  ) -> Result<Option<InscriptionId>> {
+    let rtx = self.database.begin_read()?;
+
+    let Some(sequence_number) = rtx
+      .open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?
+      .get(inscription_number)?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    let inscription_id = rtx
+      .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+      .get(&sequence_number)?
+      .map(|entry| InscriptionEntry::load(entry.value()).id);
+
+    Ok(inscription_id)
+  }
+
+  pub(crate) fn get_inscription_satpoint_by_id(
+    &self,
     inscription_id: InscriptionId,
   ) -> Result<Option<SatPoint>> {
     let rtx = self.database.begin_read()?;
@@ -1562,6 +2156,185 @@ impl Index {
     Ok(None)
   }
 
+  /// Lists the outputs that have held `sat`, oldest first. The sat's current
+  /// output is found from `SAT_TO_SATPOINT` for rare sats, or, if `scan` is
+  /// true, by scanning the sat ranges of every output otherwise, which reads
+  /// the whole table and is too slow to do on request. The path is then walked
+  /// backwards through the inputs of each transaction, so that transactions
+  /// are only fetched for outputs on the path. Returns `None` if `sat` has not
+  /// been mined, or if its current output is not indexed and `scan` is false.
+  pub(crate) fn trace(&self, sat: Sat, scan: bool) -> Result<Option<api::SatTrace>> {
+    let rtx = self.begin_read()?;
+
+    if rtx.block_count()? <= sat.height().n() {
+      return Ok(None);
+    }
+
+    let outpoint_to_sat_ranges = rtx.0.open_table(OUTPOINT_TO_SAT_RANGES)?;
+
+    let current = rtx
+      .0
+      .open_table(SAT_TO_SATPOINT)?
+      .get(&sat.n())?
+      .map(|satpoint| SatPoint::load(*satpoint.value()))
+      .filter(|satpoint| satpoint.outpoint != OutPoint::null());
+
+    let mut lost = false;
+
+    let holders = match current {
+      Some(satpoint) => vec![satpoint],
+      None if !scan => return Ok(None),
+      None => {
+        let mut holders = Vec::new();
+
+        for result in outpoint_to_sat_ranges.iter()? {
+          let (key, value) = result?;
+          let outpoint = OutPoint::load(*key.value());
+          if let Some(offset) = Self::sat_offset(value.value(), sat) {
+            if outpoint == OutPoint::null() {
+              lost = true;
+            } else {
+              holders.push(SatPoint { outpoint, offset });
+            }
+          }
+        }
+
+        holders
+      }
+    };
+
+    let mut visited = HashSet::new();
+    let mut locations = Vec::<api::SatLocation>::new();
+
+    // Walking back from a holder stops at the first output already on the
+    // path, which, since a sat is only ever in one unspent output, must be the
+    // latest one found so far.
+    for holder in holders {
+      if visited.contains(&holder.outpoint) {
+        continue;
+      }
+
+      let mut segment = Vec::new();
+      let mut next = Some(holder);
+
+      while let Some(satpoint) = next {
+        if visited.contains(&satpoint.outpoint) {
+          ensure!(
+            locations.last().map(|location| location.satpoint) == Some(satpoint),
+            "sat {sat} path forks at {satpoint}",
+          );
+          break;
+        }
+
+        visited.insert(satpoint.outpoint);
+
+        let (height, previous) =
+          self.previous_sat_location(&outpoint_to_sat_ranges, sat, satpoint.outpoint)?;
+
+        segment.push(api::SatLocation { height, satpoint });
+
+        ensure!(
+          previous.is_some() || locations.is_empty(),
+          "sat {sat} path to {satpoint} does not join earlier path",
+        );
+
+        next = previous;
+      }
+
+      locations.extend(segment.into_iter().rev());
+    }
+
+    Ok(Some(api::SatTrace {
+      sat,
+      locations,
+      lost,
+    }))
+  }
+
+  /// Returns the height of the block containing `outpoint`, and the location
+  /// of `sat` in the output spent by that transaction, or `None` if `sat` was
+  /// mined in it. Sats in a coinbase output which were not mined in that
+  /// block were paid as fees, so the inputs of every transaction in the block
+  /// are searched.
+  fn previous_sat_location(
+    &self,
+    outpoint_to_sat_ranges: &ReadOnlyTable<&OutPointValue, &[u8]>,
+    sat: Sat,
+    outpoint: OutPoint,
+  ) -> Result<(u32, Option<SatPoint>)> {
+    if outpoint == self.settings.chain().genesis_coinbase_outpoint() {
+      return Ok((0, None));
+    }
+
+    let txid = outpoint.txid;
+
+    let blockhash = self
+      .client
+      .get_raw_transaction_info(&txid, None)
+      .into_option()?
+      .and_then(|info| info.blockhash)
+      .ok_or_else(|| anyhow!("transaction {txid} not found in a block"))?;
+
+    let height = u32::try_from(
+      self
+        .block_header_info(blockhash)?
+        .ok_or_else(|| anyhow!("block {blockhash} not found"))?
+        .height,
+    )
+    .unwrap();
+
+    let transaction = self
+      .get_transaction(txid)?
+      .ok_or_else(|| anyhow!("transaction {txid} not found"))?;
+
+    let inputs = if transaction.is_coin_base() {
+      if sat.height().n() == height {
+        return Ok((height, None));
+      }
+
+      self
+        .get_block_by_hash(blockhash)?
+        .ok_or_else(|| anyhow!("block {blockhash} not found"))?
+        .txdata
+        .into_iter()
+        .skip(1)
+        .flat_map(|transaction| transaction.input)
+        .collect()
+    } else {
+      transaction.input
+    };
+
+    for input in inputs {
+      if let Some(sat_ranges) = outpoint_to_sat_ranges.get(&input.previous_output.store())? {
+        if let Some(offset) = Self::sat_offset(sat_ranges.value(), sat) {
+          return Ok((
+            height,
+            Some(SatPoint {
+              outpoint: input.previous_output,
+              offset,
+            }),
+          ));
+        }
+      }
+    }
+
+    Err(anyhow!(
+      "sat {sat} in output {outpoint} not found in spent outputs"
+    ))
+  }
+
+  fn sat_offset(sat_ranges: &[u8], sat: Sat) -> Option<u64> {
+    let mut offset = 0;
+    for chunk in sat_ranges.chunks_exact(11) {
+      let (start, end) = SatRange::load(chunk.try_into().unwrap());
+      if start <= sat.n() && sat.n() < end {
+        return Some(offset + sat.n() - start);
+      }
+      offset += end - start;
+    }
+    None
+  }
+
   pub(crate) fn find_range(
     &self,
     range_start: Sat,
@@ -1632,6 +2405,18 @@ impl Index {
     )
   }
 
+  pub(crate) fn scan(&self, outpoints: &[OutPoint]) -> Result<Vec<api::ScanOutput>> {
+    outpoints
+      .iter()
+      .map(|outpoint| {
+        Ok(api::ScanOutput {
+          output: *outpoint,
+          sats: self.list(*outpoint)?.map(|ranges| scan::rare_sats(&ranges)),
+        })
+      })
+      .collect()
+  }
+
   pub(crate) fn is_output_spent(&self, outpoint: OutPoint) -> Result<bool> {
     Ok(
       outpoint != OutPoint::null()
@@ -2186,6 +2971,70 @@ impl Index {
     )
   }
 
+  pub(crate) fn get_address_outputs(&self, address: &Address) -> Result<Vec<(OutPoint, TxOut)>> {
+    let rtx = self.database.begin_read()?;
+
+    let outpoint_to_txout = rtx.open_table(OUTPOINT_TO_TXOUT)?;
+
+    let mut outputs = Vec::new();
+
+    for result in rtx
+      .open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?
+      .get(address.script_pubkey().as_bytes())?
+    {
+      let outpoint = *result?.value();
+
+      let txout = consensus::encode::deserialize(
+        outpoint_to_txout
+          .get(&outpoint)?
+          .ok_or_else(|| anyhow!("missing output for {}", OutPoint::load(outpoint)))?
+          .value(),
+      )?;
+
+      outputs.push((OutPoint::load(outpoint), txout));
+    }
+
+    Ok(outputs)
+  }
+
+  pub(crate) fn for_each_event(
+    &self,
+    heights: RangeInclusive<u32>,
+    mut f: impl FnMut((u32, u32, u32), Event) -> Result,
+  ) -> Result {
+    let rtx = self.database.begin_read()?;
+
+    for result in rtx
+      .open_table(EVENT_LOG)?
+      .range((*heights.start(), 0, 0)..=(*heights.end(), u32::MAX, u32::MAX))?
+    {
+      let (key, value) = result?;
+      f(key.value(), serde_json::from_slice(value.value())?)?;
+    }
+
+    Ok(())
+  }
+
+  /// Up to `limit` logged events, starting with the event at `(height, seq)`.
+  pub(crate) fn get_events_from(
+    &self,
+    (height, seq): (u32, u32),
+    limit: usize,
+  ) -> Result<Vec<((u32, u32), Event)>> {
+    self
+      .database
+      .begin_read()?
+      .open_table(EVENT_LOG)?
+      .range((height, seq, 0)..)?
+      .take(limit)
+      .map(|result| {
+        let (key, value) = result?;
+        let (height, seq, _tx_index) = key.value();
+        Ok(((height, seq), serde_json::from_slice(value.value())?))
+      })
+      .collect()
+  }
+
   pub(crate) fn get_output_info(&self, outpoint: OutPoint) -> Result<Option<(api::Output, TxOut)>> {
     let sat_ranges = self.list(outpoint)?;
 
@@ -4742,33 +5591,106 @@ mod tests {
   }
 
   #[test]
-  fn inscription_with_two_parent_tags_and_parents_has_parent_entries() {
-    for context in Context::configurations() {
-      context.mine_blocks(2);
+  fn collections_are_verified_against_children_inscribed_since_last_verification() {
+    let context = Context::builder().build();
 
-      let parent_txid_a = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
-        ..default()
-      });
-      let parent_txid_b = context.core.broadcast_tx(TransactionTemplate {
-        inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
-        ..default()
-      });
+    context.mine_blocks(1);
 
-      context.mine_blocks(1);
+    let parent_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
 
-      let parent_inscription_id_a = InscriptionId {
-        txid: parent_txid_a,
-        index: 0,
-      };
-      let parent_inscription_id_b = InscriptionId {
-        txid: parent_txid_b,
-        index: 0,
-      };
+    context.mine_blocks(1);
 
-      let multi_parent_inscription = Inscription {
-        content_type: Some("text/plain".into()),
-        body: Some("hello".into()),
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let item = Inscription {
+      content_type: Some("text/plain".into()),
+      body: Some("foo".into()),
+      parents: vec![parent.value()],
+      ..default()
+    };
+
+    let manifest_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        1,
+        0,
+        Manifest {
+          items: vec![Manifest::item(&item).unwrap()],
+        }
+        .inscription(parent, 0)
+        .unwrap()
+        .to_witness(),
+      )],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let manifest = InscriptionId {
+      txid: manifest_txid,
+      index: 0,
+    };
+
+    let verification = context.index.verify_collection(parent).unwrap().unwrap();
+
+    assert_eq!(verification.manifest, manifest);
+    assert_eq!(verification.missing, [0]);
+    assert!(verification.verified.is_empty());
+
+    let item_txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, item.to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let verification = context.index.verify_collection(parent).unwrap().unwrap();
+
+    assert_eq!(verification.manifest, manifest);
+    assert!(verification.missing.is_empty());
+    assert_eq!(
+      verification.verified,
+      [InscriptionId {
+        txid: item_txid,
+        index: 0,
+      }]
+    );
+  }
+
+  #[test]
+  fn inscription_with_two_parent_tags_and_parents_has_parent_entries() {
+    for context in Context::configurations() {
+      context.mine_blocks(2);
+
+      let parent_txid_a = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      });
+      let parent_txid_b = context.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(2, 0, 0, inscription("text/plain", "world").to_witness())],
+        ..default()
+      });
+
+      context.mine_blocks(1);
+
+      let parent_inscription_id_a = InscriptionId {
+        txid: parent_txid_a,
+        index: 0,
+      };
+      let parent_inscription_id_b = InscriptionId {
+        txid: parent_txid_b,
+        index: 0,
+      };
+
+      let multi_parent_inscription = Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some("hello".into()),
         parents: vec![
           parent_inscription_id_a.value(),
           parent_inscription_id_b.value(),
@@ -6607,4 +7529,658 @@ mod tests {
       }
     );
   }
+
+  #[test]
+  fn address_index_tracks_unspent_outputs() {
+    let context = Context::builder().arg("--index-addresses").build();
+
+    context.mine_blocks(1);
+
+    let script_pubkey = ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros());
+    let address = Address::from_script(&script_pubkey, Network::Regtest).unwrap();
+
+    assert_eq!(
+      context.index.get_address_outputs(&address).unwrap(),
+      Vec::new()
+    );
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 2,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut outputs = context.index.get_address_outputs(&address).unwrap();
+    outputs.sort_by_key(|(outpoint, _txout)| *outpoint);
+
+    assert_eq!(
+      outputs,
+      [
+        (
+          OutPoint { txid, vout: 0 },
+          TxOut {
+            value: 25 * COIN_VALUE,
+            script_pubkey: script_pubkey.clone(),
+          }
+        ),
+        (
+          OutPoint { txid, vout: 1 },
+          TxOut {
+            value: 25 * COIN_VALUE,
+            script_pubkey: script_pubkey.clone(),
+          }
+        ),
+      ]
+    );
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.get_address_outputs(&address).unwrap(),
+      [(
+        OutPoint { txid, vout: 1 },
+        TxOut {
+          value: 25 * COIN_VALUE,
+          script_pubkey,
+        }
+      )]
+    );
+  }
+
+  #[test]
+  fn address_index_is_rolled_back_on_reorg() {
+    let mut context = Context::builder().arg("--index-addresses").build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    let script_pubkey = ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros());
+    let address = Address::from_script(&script_pubkey, Network::Regtest).unwrap();
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(6);
+
+    let outputs = vec![(
+      OutPoint { txid, vout: 0 },
+      TxOut {
+        value: 50 * COIN_VALUE,
+        script_pubkey,
+      },
+    )];
+
+    assert_eq!(
+      context.index.get_address_outputs(&address).unwrap(),
+      outputs
+    );
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.get_address_outputs(&address).unwrap(),
+      Vec::new()
+    );
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context.index.get_address_outputs(&address).unwrap(),
+      outputs
+    );
+  }
+
+  #[test]
+  fn event_log_records_events() {
+    let context = Context::builder().arg("--index-events").build();
+
+    context.mine_blocks(1);
+
+    let txid = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut events = Vec::new();
+
+    context
+      .index
+      .for_each_event(0..=u32::MAX, |key, event| {
+        events.push((key, event));
+        Ok(())
+      })
+      .unwrap();
+
+    assert_eq!(events.len(), 1);
+
+    let ((height, seq, tx_index), event) = &events[0];
+
+    assert_eq!((*height, *seq, *tx_index), (2, 0, 1));
+    assert_eq!(event.inscription_ids(), [InscriptionId { txid, index: 0 }]);
+
+    let mut events = Vec::new();
+
+    context
+      .index
+      .for_each_event(3..=u32::MAX, |key, event| {
+        events.push((key, event));
+        Ok(())
+      })
+      .unwrap();
+
+    assert!(events.is_empty());
+  }
+
+  #[test]
+  fn event_log_is_ordered_by_emission() {
+    let context = Context::builder().arg("--index-events").build();
+
+    context.mine_blocks(2);
+
+    let first = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "foo").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let second = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "bar").to_witness())],
+      ..default()
+    });
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let mut events = Vec::new();
+
+    context
+      .index
+      .for_each_event(4..=4, |key, event| {
+        events.push((key, event.kind(), event.inscription_ids()));
+        Ok(())
+      })
+      .unwrap();
+
+    assert_eq!(
+      events,
+      [
+        (
+          (4, 0, 1),
+          event::EventKind::InscriptionCreated,
+          vec![InscriptionId {
+            txid: second,
+            index: 0
+          }],
+        ),
+        (
+          (4, 1, 0),
+          event::EventKind::InscriptionTransferred,
+          vec![InscriptionId {
+            txid: first,
+            index: 0
+          }],
+        ),
+      ],
+    );
+  }
+
+  #[test]
+  fn event_log_is_rolled_back_on_reorg() {
+    let mut context = Context::builder().arg("--index-events").build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    let count_events = |context: &Context| {
+      let mut count = 0;
+      context
+        .index
+        .for_each_event(0..=u32::MAX, |_, _| {
+          count += 1;
+          Ok(())
+        })
+        .unwrap();
+      count
+    };
+
+    context.mine_blocks(5);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(count_events(&context), 1);
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert_eq!(count_events(&context), 0);
+  }
+
+  #[test]
+  fn event_log_is_empty_without_index_events() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(!context.index.has_event_index());
+
+    context
+      .index
+      .for_each_event(0..=u32::MAX, |_, _| panic!("unexpected event"))
+      .unwrap();
+  }
+
+  #[test]
+  fn spent_rune_index_reconstructs_past_holders() {
+    const RUNE: u128 = 99246114928149462;
+
+    let context = Context::builder().arg("--index-spent-runes").build();
+
+    let (etching, id) = context.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          premine: Some(u128::MAX),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+    );
+
+    let etching_height = u32::try_from(id.block).unwrap();
+
+    let transfer = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      outputs: 1,
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let script_pubkey = ScriptBuf::new_v0_p2wpkh(&WPubkeyHash::all_zeros());
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_holders_at_height(id, etching_height - 1)
+        .unwrap(),
+      Vec::new(),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_holders_at_height(id, etching_height)
+        .unwrap(),
+      [(
+        OutPoint {
+          txid: etching,
+          vout: 0
+        },
+        script_pubkey.clone(),
+        u128::MAX
+      )],
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_rune_holders_at_height(id, etching_height + 1)
+        .unwrap(),
+      [(
+        OutPoint {
+          txid: transfer,
+          vout: 0
+        },
+        script_pubkey,
+        u128::MAX
+      )],
+    );
+  }
+
+  #[test]
+  fn index_spent_runes_implies_index_runes() {
+    let context = Context::builder().arg("--index-spent-runes").build();
+    assert!(context.index.has_rune_index());
+    assert!(context.index.has_spent_rune_index());
+
+    let context = Context::builder().arg("--index-runes").build();
+    assert!(context.index.has_rune_index());
+    assert!(!context.index.has_spent_rune_index());
+  }
+
+  #[test]
+  fn transfer_history_records_every_location() {
+    let context = Context::builder().arg("--index-transfer-history").build();
+
+    context.mine_blocks(1);
+
+    let inscription = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    let transfer = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      ..default()
+    });
+
+    let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+    let transfers = vec![
+      api::InscriptionTransfer {
+        height: 2,
+        satpoint: SatPoint {
+          outpoint: OutPoint::new(inscription, 0),
+          offset: 0,
+        },
+        txid: inscription,
+      },
+      api::InscriptionTransfer {
+        height: 3,
+        satpoint: SatPoint {
+          outpoint: OutPoint::new(transfer, 0),
+          offset: 0,
+        },
+        txid: transfer,
+      },
+      api::InscriptionTransfer {
+        height: 4,
+        satpoint: SatPoint {
+          outpoint: OutPoint::new(coinbase, 0),
+          offset: 50 * COIN_VALUE,
+        },
+        txid: coinbase,
+      },
+    ];
+
+    assert_eq!(
+      context
+        .index
+        .get_transfers_by_sequence_number_paginated(0, 100, 0)
+        .unwrap(),
+      (transfers.clone(), false),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_transfers_by_sequence_number_paginated(0, 2, 0)
+        .unwrap(),
+      (transfers[..2].to_vec(), true),
+    );
+
+    assert_eq!(
+      context
+        .index
+        .get_transfers_by_sequence_number_paginated(0, 2, 1)
+        .unwrap(),
+      (transfers[2..].to_vec(), false),
+    );
+  }
+
+  #[test]
+  fn transfer_history_is_not_recorded_without_index() {
+    let context = Context::builder().build();
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert!(!context.index.has_transfer_history_index());
+
+    assert_eq!(
+      context
+        .index
+        .get_transfers_by_sequence_number_paginated(0, 100, 0)
+        .unwrap(),
+      (Vec::new(), false),
+    );
+  }
+
+  #[test]
+  fn transfer_history_is_rolled_back_on_reorg() {
+    let mut context = Context::builder().arg("--index-transfer-history").build();
+
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+
+    let inscription = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    context.mine_blocks(6);
+
+    let genesis = api::InscriptionTransfer {
+      height: 2,
+      satpoint: SatPoint {
+        outpoint: OutPoint::new(inscription, 0),
+        offset: 0,
+      },
+      txid: inscription,
+    };
+
+    let transfer = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context
+        .index
+        .get_transfers_by_sequence_number_paginated(0, 100, 0)
+        .unwrap(),
+      (
+        vec![
+          genesis.clone(),
+          api::InscriptionTransfer {
+            height: 8,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(transfer, 0),
+              offset: 0,
+            },
+            txid: transfer,
+          },
+        ],
+        false
+      ),
+    );
+
+    context.core.invalidate_tip();
+    context.mine_blocks(2);
+
+    assert_eq!(
+      context
+        .index
+        .get_transfers_by_sequence_number_paginated(0, 100, 0)
+        .unwrap(),
+      (vec![genesis], false),
+    );
+  }
+
+  #[test]
+  fn trace_follows_sat_through_transactions_and_fees() {
+    let context = Context::builder().arg("--index-spent-sats").build();
+
+    let sat = Sat(50 * COIN_VALUE);
+
+    assert_eq!(context.index.trace(sat, true).unwrap(), None);
+
+    let first = context.mine_blocks(1)[0].txdata[0].txid();
+
+    let transfer = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    context.mine_blocks(1);
+
+    context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      fee: 50 * COIN_VALUE,
+      ..default()
+    });
+
+    let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+    pretty_assert_eq!(
+      context.index.trace(sat, true).unwrap().unwrap(),
+      api::SatTrace {
+        sat,
+        locations: vec![
+          api::SatLocation {
+            height: 1,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(first, 0),
+              offset: 0,
+            },
+          },
+          api::SatLocation {
+            height: 2,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(transfer, 0),
+              offset: 0,
+            },
+          },
+          api::SatLocation {
+            height: 3,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(coinbase, 0),
+              offset: 50 * COIN_VALUE,
+            },
+          },
+        ],
+        lost: false,
+      },
+    );
+  }
+
+  #[test]
+  fn trace_follows_common_sat_through_transactions_in_same_block() {
+    let context = Context::builder().arg("--index-spent-sats").build();
+
+    let sat = Sat(50 * COIN_VALUE + 1);
+
+    let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+    let first = context.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    let second = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: OutPoint::new(first, 0),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::MAX,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: 50 * COIN_VALUE,
+        script_pubkey: ScriptBuf::new(),
+      }],
+    };
+
+    context.core.state().mempool.push(second.clone());
+
+    context.mine_blocks(1);
+
+    assert_eq!(context.index.trace(sat, false).unwrap(), None);
+
+    pretty_assert_eq!(
+      context.index.trace(sat, true).unwrap().unwrap(),
+      api::SatTrace {
+        sat,
+        locations: vec![
+          api::SatLocation {
+            height: 1,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(coinbase, 0),
+              offset: 1,
+            },
+          },
+          api::SatLocation {
+            height: 2,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(first, 0),
+              offset: 1,
+            },
+          },
+          api::SatLocation {
+            height: 2,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(second.txid(), 0),
+              offset: 1,
+            },
+          },
+        ],
+        lost: false,
+      },
+    );
+  }
 }
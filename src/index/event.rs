@@ -1,6 +1,7 @@
 use super::*;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Event {
   InscriptionCreated {
     block_height: u32,
@@ -42,3 +43,131 @@ pub enum Event {
     txid: Txid,
   },
 }
+
+impl Event {
+  pub fn block_height(&self) -> u32 {
+    match self {
+      Self::InscriptionCreated { block_height, .. }
+      | Self::InscriptionTransferred { block_height, .. }
+      | Self::RuneBurned { block_height, .. }
+      | Self::RuneEtched { block_height, .. }
+      | Self::RuneMinted { block_height, .. }
+      | Self::RuneTransferred { block_height, .. } => *block_height,
+    }
+  }
+
+  pub fn kind(&self) -> EventKind {
+    match self {
+      Self::InscriptionCreated { .. } => EventKind::InscriptionCreated,
+      Self::InscriptionTransferred { .. } => EventKind::InscriptionTransferred,
+      Self::RuneBurned { .. } => EventKind::RuneBurned,
+      Self::RuneEtched { .. } => EventKind::RuneEtched,
+      Self::RuneMinted { .. } => EventKind::RuneMinted,
+      Self::RuneTransferred { .. } => EventKind::RuneTransferred,
+    }
+  }
+
+  pub fn inscription_ids(&self) -> Vec<InscriptionId> {
+    match self {
+      Self::InscriptionCreated {
+        inscription_id,
+        parent_inscription_ids,
+        ..
+      } => std::iter::once(*inscription_id)
+        .chain(parent_inscription_ids.iter().copied())
+        .collect(),
+      Self::InscriptionTransferred { inscription_id, .. } => vec![*inscription_id],
+      _ => Vec::new(),
+    }
+  }
+
+  pub fn rune_id(&self) -> Option<RuneId> {
+    match self {
+      Self::InscriptionCreated { .. } | Self::InscriptionTransferred { .. } => None,
+      Self::RuneBurned { rune_id, .. }
+      | Self::RuneEtched { rune_id, .. }
+      | Self::RuneMinted { rune_id, .. }
+      | Self::RuneTransferred { rune_id, .. } => Some(*rune_id),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, DeserializeFromStr, SerializeDisplay)]
+pub enum EventKind {
+  InscriptionCreated,
+  InscriptionTransferred,
+  RuneBurned,
+  RuneEtched,
+  RuneMinted,
+  RuneTransferred,
+}
+
+impl Display for EventKind {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Self::InscriptionCreated => "inscription_created",
+        Self::InscriptionTransferred => "inscription_transferred",
+        Self::RuneBurned => "rune_burned",
+        Self::RuneEtched => "rune_etched",
+        Self::RuneMinted => "rune_minted",
+        Self::RuneTransferred => "rune_transferred",
+      }
+    )
+  }
+}
+
+impl FromStr for EventKind {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "inscription_created" => Ok(Self::InscriptionCreated),
+      "inscription_transferred" => Ok(Self::InscriptionTransferred),
+      "rune_burned" => Ok(Self::RuneBurned),
+      "rune_etched" => Ok(Self::RuneEtched),
+      "rune_minted" => Ok(Self::RuneMinted),
+      "rune_transferred" => Ok(Self::RuneTransferred),
+      _ => Err(anyhow!("invalid event kind: {s}")),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn event_kind_round_trips() {
+    for kind in [
+      EventKind::InscriptionCreated,
+      EventKind::InscriptionTransferred,
+      EventKind::RuneBurned,
+      EventKind::RuneEtched,
+      EventKind::RuneMinted,
+      EventKind::RuneTransferred,
+    ] {
+      assert_eq!(kind.to_string().parse::<EventKind>().unwrap(), kind);
+    }
+
+    assert!("foo".parse::<EventKind>().is_err());
+  }
+
+  #[test]
+  fn event_json_is_tagged_with_kind() {
+    let event = Event::RuneEtched {
+      block_height: 1,
+      rune_id: RuneId { block: 1, tx: 2 },
+      txid: txid(1),
+    };
+
+    let json = serde_json::to_value(&event).unwrap();
+
+    assert_eq!(json["type"], event.kind().to_string());
+    assert_eq!(json["rune_id"], "1:2");
+
+    assert_eq!(serde_json::from_value::<Event>(json).unwrap(), event);
+  }
+}
@@ -0,0 +1,102 @@
+use super::*;
+
+/// Split an output's sat ranges into runs of sats with the same non-empty
+/// set of charms, without visiting every sat.
+pub(super) fn rare_sats(ranges: &[(u64, u64)]) -> Vec<api::ScanRange> {
+  let mut rare = Vec::new();
+  let mut offset = 0;
+
+  for &(start, end) in ranges {
+    let mut boundaries = vec![start, end];
+
+    for satribute in Satribute::ALL {
+      if satribute.charm().is_some() {
+        for (first, last) in satribute.ranges(start, end) {
+          boundaries.push(first);
+          boundaries.push(last);
+        }
+      }
+    }
+
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    for window in boundaries.windows(2) {
+      let sat = Sat(window[0]);
+      let charms = sat.charms();
+
+      if charms != 0 {
+        rare.push(api::ScanRange {
+          charms: Charm::charms(charms),
+          offset: offset + window[0] - start,
+          rarity: sat.rarity(),
+          sat,
+          size: window[1] - window[0],
+        });
+      }
+    }
+
+    offset += end - start;
+  }
+
+  rare
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn common_sats_are_skipped() {
+    assert_eq!(
+      rare_sats(&[(1, 1000), (COIN_VALUE + 1, 2 * COIN_VALUE)]),
+      []
+    );
+  }
+
+  #[test]
+  fn rare_sats_are_reported_with_offsets() {
+    assert_eq!(
+      rare_sats(&[(10, 20), (50 * COIN_VALUE, 50 * COIN_VALUE + 2)]),
+      [api::ScanRange {
+        charms: vec![Charm::Coin, Charm::Uncommon],
+        offset: 10,
+        rarity: Rarity::Uncommon,
+        sat: Sat(50 * COIN_VALUE),
+        size: 1,
+      }],
+    );
+  }
+
+  #[test]
+  fn charmed_ranges_are_split_at_charm_boundaries() {
+    let block_nine = 9 * 50 * COIN_VALUE;
+
+    assert_eq!(
+      rare_sats(&[(block_nine - 1, block_nine + COIN_VALUE + 1)]),
+      [
+        api::ScanRange {
+          charms: vec![Charm::Coin, Charm::Uncommon, Charm::Nineball],
+          offset: 1,
+          rarity: Rarity::Uncommon,
+          sat: Sat(block_nine),
+          size: 1,
+        },
+        api::ScanRange {
+          charms: vec![Charm::Nineball],
+          offset: 2,
+          rarity: Rarity::Common,
+          sat: Sat(block_nine + 1),
+          size: COIN_VALUE - 1,
+        },
+        api::ScanRange {
+          charms: vec![Charm::Coin, Charm::Nineball],
+          offset: COIN_VALUE + 1,
+          rarity: Rarity::Common,
+          sat: Sat(block_nine + COIN_VALUE),
+          size: 1,
+        },
+      ],
+    );
+  }
+}
@@ -0,0 +1,193 @@
+use super::*;
+
+const INDEXED_CONTENT_TYPES: [&str; 3] = ["application/json", "text/markdown", "text/plain"];
+const MAX_BODY_SIZE: usize = 1 << 20;
+const MAX_QUERY_TERMS: usize = 8;
+const MAX_TERM_LENGTH: usize = 64;
+
+const CONTENT_TYPE_PREFIX: &str = "content_type:";
+const METAPROTOCOL_PREFIX: &str = "metaprotocol:";
+
+/// Terms under which an inscription is indexed: words in text and JSON
+/// bodies, metadata keys and values, and its qualified content type and
+/// metaprotocol.
+pub(crate) fn terms(inscription: &Inscription) -> BTreeSet<String> {
+  let mut terms = BTreeSet::new();
+
+  let essence = inscription.content_type().map(essence);
+
+  if let Some(essence) = &essence {
+    insert_qualified(&mut terms, CONTENT_TYPE_PREFIX, essence);
+  }
+
+  if let Some(metaprotocol) = inscription.metaprotocol() {
+    insert_qualified(&mut terms, METAPROTOCOL_PREFIX, metaprotocol);
+  }
+
+  if essence
+    .as_deref()
+    .is_some_and(|essence| INDEXED_CONTENT_TYPES.contains(&essence))
+  {
+    if let Some(body) = inscription.decoded_body(MAX_BODY_SIZE) {
+      terms.extend(words(&String::from_utf8_lossy(&body)));
+    }
+  }
+
+  if let Some(metadata) = inscription.metadata() {
+    metadata_terms(&mut terms, &metadata);
+  }
+
+  terms
+}
+
+/// Parse a search query into terms, all of which must match. Whitespace
+/// separated words are split like indexed text, except for `content_type:`
+/// and `metaprotocol:` qualified terms, which are matched whole.
+pub(crate) fn query(query: &str) -> Result<Vec<String>> {
+  let mut terms = Vec::new();
+
+  for word in query.split_whitespace() {
+    let lowercase = word.to_lowercase();
+
+    if [CONTENT_TYPE_PREFIX, METAPROTOCOL_PREFIX]
+      .iter()
+      .any(|prefix| lowercase.starts_with(prefix))
+    {
+      terms.push(lowercase);
+    } else {
+      terms.extend(words(word));
+    }
+  }
+
+  terms.sort();
+  terms.dedup();
+
+  ensure!(!terms.is_empty(), "search query has no terms");
+
+  ensure!(
+    terms.len() <= MAX_QUERY_TERMS,
+    "search query has more than {MAX_QUERY_TERMS} terms",
+  );
+
+  Ok(terms)
+}
+
+fn essence(content_type: &str) -> String {
+  content_type
+    .split(';')
+    .next()
+    .unwrap_or_default()
+    .trim()
+    .to_lowercase()
+}
+
+fn insert_qualified(terms: &mut BTreeSet<String>, prefix: &str, value: &str) {
+  let term = format!("{prefix}{}", value.to_lowercase());
+
+  if term.len() <= prefix.len() + MAX_TERM_LENGTH {
+    terms.insert(term);
+  }
+}
+
+fn metadata_terms(terms: &mut BTreeSet<String>, value: &Value) {
+  match value {
+    Value::Array(values) => {
+      for value in values {
+        metadata_terms(terms, value);
+      }
+    }
+    Value::Bool(bool) => {
+      terms.insert(bool.to_string());
+    }
+    Value::Integer(integer) => {
+      terms.insert(i128::from(*integer).to_string());
+    }
+    Value::Map(entries) => {
+      for (key, value) in entries {
+        metadata_terms(terms, key);
+        metadata_terms(terms, value);
+      }
+    }
+    Value::Tag(_, value) => metadata_terms(terms, value),
+    Value::Text(text) => terms.extend(words(text)),
+    _ => {}
+  }
+}
+
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+  text
+    .split(|c: char| !c.is_alphanumeric())
+    .filter(|word| !word.is_empty() && word.len() <= MAX_TERM_LENGTH)
+    .map(|word| word.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn text_terms() {
+    assert_eq!(
+      terms(&inscription(
+        "text/plain;charset=utf-8",
+        "Hello, World! hello"
+      )),
+      ["content_type:text/plain", "hello", "world"]
+        .into_iter()
+        .map(str::to_string)
+        .collect(),
+    );
+  }
+
+  #[test]
+  fn non_text_bodies_are_not_tokenized() {
+    assert_eq!(
+      terms(&inscription("image/png", "hello")),
+      ["content_type:image/png".to_string()].into(),
+    );
+  }
+
+  #[test]
+  fn metadata_and_metaprotocol_terms() {
+    let mut metadata = Vec::new();
+
+    ciborium::into_writer(
+      &Value::Map(vec![
+        (Value::Text("Name".into()), Value::Text("Foo Bar".into())),
+        (Value::Text("rank".into()), Value::Integer(7.into())),
+      ]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    assert_eq!(
+      terms(&Inscription {
+        metadata: Some(metadata),
+        metaprotocol: Some(b"BRC-20".to_vec()),
+        ..default()
+      }),
+      ["7", "bar", "foo", "metaprotocol:brc-20", "name", "rank"]
+        .into_iter()
+        .map(str::to_string)
+        .collect(),
+    );
+  }
+
+  #[test]
+  fn parse_query() {
+    assert_eq!(
+      query("Hello  world-wide content_type:TEXT/plain hello").unwrap(),
+      ["content_type:text/plain", "hello", "wide", "world"],
+    );
+
+    assert_eq!(
+      query(" !! ").unwrap_err().to_string(),
+      "search query has no terms",
+    );
+
+    assert_eq!(
+      query("a b c d e f g h i").unwrap_err().to_string(),
+      "search query has more than 8 terms",
+    );
+  }
+}
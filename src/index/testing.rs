@@ -135,10 +135,12 @@ impl Context {
     pretty_assert_eq!(balances, self.index.get_rune_balances().unwrap());
 
     let mut outstanding: HashMap<RuneId, u128> = HashMap::new();
+    let mut holders: HashMap<RuneId, Vec<(OutPoint, u128)>> = HashMap::new();
 
-    for (_, balances) in balances {
+    for (outpoint, balances) in balances {
       for (id, balance) in balances {
         *outstanding.entry(*id).or_default() += *balance;
+        holders.entry(*id).or_default().push((*outpoint, *balance));
       }
     }
 
@@ -147,6 +149,14 @@ impl Context {
         outstanding.get(id).copied().unwrap_or_default(),
         entry.supply() - entry.burned
       );
+
+      let mut expected = holders.remove(id).unwrap_or_default();
+      expected.sort_by_key(|&(outpoint, amount)| (Reverse(amount), Reverse(outpoint.store())));
+
+      let (count, top) = self.index.get_rune_top_holders(*id, usize::MAX).unwrap();
+
+      pretty_assert_eq!(count, u64::try_from(expected.len()).unwrap());
+      pretty_assert_eq!(top, expected);
     }
   }
 
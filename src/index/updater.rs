@@ -1,11 +1,14 @@
 use {
-  self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
+  self::{
+    event_emitter::EventEmitter, inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater,
+  },
   super::{fetcher::Fetcher, *},
   futures::future::try_join_all,
   std::sync::mpsc,
   tokio::sync::mpsc::{error::TryRecvError, Receiver, Sender},
 };
 
+mod event_emitter;
 mod inscription_updater;
 mod rune_updater;
 
@@ -71,7 +74,11 @@ impl<'index> Updater<'index> {
       Some(progress_bar)
     };
 
-    let rx = Self::fetch_blocks_from(self.index, self.height, self.index.index_sats)?;
+    let rx = Self::fetch_blocks_from(
+      self.index,
+      self.height,
+      self.index.index_sats || self.index.index_addresses,
+    )?;
 
     let (mut outpoint_sender, mut value_receiver) = Self::spawn_fetcher(&self.index.settings)?;
 
@@ -153,7 +160,7 @@ impl<'index> Updater<'index> {
   fn fetch_blocks_from(
     index: &Index,
     mut height: u32,
-    index_sats: bool,
+    full_blocks: bool,
   ) -> Result<mpsc::Receiver<BlockData>> {
     let (tx, rx) = mpsc::sync_channel(32);
 
@@ -170,7 +177,7 @@ impl<'index> Updater<'index> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+      match Self::get_block_with_retries(&client, height, full_blocks, first_inscription_height) {
         Ok(Some(block)) => {
           if let Err(err) = tx.send(block.into()) {
             log::info!("Block receiver disconnected: {err}");
@@ -192,7 +199,7 @@ impl<'index> Updater<'index> {
   fn get_block_with_retries(
     client: &Client,
     height: u32,
-    index_sats: bool,
+    full_blocks: bool,
     first_inscription_height: u32,
   ) -> Result<Option<Block>> {
     let mut errors = 0;
@@ -203,7 +210,7 @@ impl<'index> Updater<'index> {
         .and_then(|option| {
           option
             .map(|hash| {
-              if index_sats || height >= first_inscription_height {
+              if full_blocks || height >= first_inscription_height {
                 Ok(client.get_block(&hash)?)
               } else {
                 Ok(Block {
@@ -370,7 +377,17 @@ impl<'index> Updater<'index> {
       }
     }
 
+    let mut content_hash_to_sequence_numbers = if self.index.index_content_hashes {
+      Some(wtx.open_multimap_table(CONTENT_HASH_TO_SEQUENCE_NUMBERS)?)
+    } else {
+      None
+    };
     let mut content_type_to_count = wtx.open_table(CONTENT_TYPE_TO_COUNT)?;
+    let mut event_log = if self.index.index_events {
+      Some(wtx.open_table(EVENT_LOG)?)
+    } else {
+      None
+    };
     let mut height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
     let mut height_to_last_sequence_number = wtx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
     let mut home_inscriptions = wtx.open_table(HOME_INSCRIPTIONS)?;
@@ -380,10 +397,20 @@ impl<'index> Updater<'index> {
       wtx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
     let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let mut search_term_to_sequence_numbers = if self.index.index_search {
+      Some(wtx.open_multimap_table(SEARCH_TERM_TO_SEQUENCE_NUMBERS)?)
+    } else {
+      None
+    };
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
     let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
+    let mut sequence_number_to_transfers = if self.index.index_transfer_history {
+      Some(wtx.open_multimap_table(SEQUENCE_NUMBER_TO_TRANSFERS)?)
+    } else {
+      None
+    };
     let mut statistic_to_count = wtx.open_table(STATISTIC_TO_COUNT)?;
     let mut transaction_id_to_transaction = wtx.open_table(TRANSACTION_ID_TO_TRANSACTION)?;
 
@@ -419,9 +446,13 @@ impl<'index> Updater<'index> {
     let mut inscription_updater = InscriptionUpdater {
       blessed_inscription_count,
       chain: self.index.settings.chain(),
+      content_hash_to_sequence_numbers: content_hash_to_sequence_numbers.as_mut(),
       content_type_to_count: &mut content_type_to_count,
       cursed_inscription_count,
-      event_sender: self.index.event_sender.as_ref(),
+      event_emitter: EventEmitter {
+        event_log: event_log.as_mut(),
+        event_sender: self.index.event_sender.as_ref(),
+      },
       flotsam: Vec::new(),
       height: self.height,
       home_inscription_count,
@@ -435,10 +466,13 @@ impl<'index> Updater<'index> {
       reward: Height(self.height).subsidy(),
       sat_to_sequence_number: &mut sat_to_sequence_number,
       satpoint_to_sequence_number: &mut satpoint_to_sequence_number,
+      search_term_to_sequence_numbers: search_term_to_sequence_numbers.as_mut(),
       sequence_number_to_children: &mut sequence_number_to_children,
       sequence_number_to_entry: &mut sequence_number_to_inscription_entry,
       sequence_number_to_satpoint: &mut sequence_number_to_satpoint,
+      sequence_number_to_transfers: sequence_number_to_transfers.as_mut(),
       timestamp: block.header.time,
+      transfer_count: 0,
       transaction_buffer: Vec::new(),
       transaction_id_to_transaction: &mut transaction_id_to_transaction,
       unbound_inscriptions,
@@ -492,6 +526,7 @@ impl<'index> Updater<'index> {
         }
 
         self.index_transaction_sats(
+          u32::try_from(tx_offset).unwrap(),
           tx,
           *txid,
           &mut sat_to_satpoint,
@@ -507,6 +542,7 @@ impl<'index> Updater<'index> {
 
       if let Some((tx, txid)) = block.txdata.first() {
         self.index_transaction_sats(
+          0,
           tx,
           *txid,
           &mut sat_to_satpoint,
@@ -544,8 +580,15 @@ impl<'index> Updater<'index> {
         outpoint_to_sat_ranges.insert(&OutPoint::null().store(), lost_sat_ranges.as_slice())?;
       }
     } else if index_inscriptions {
-      for (tx, txid) in block.txdata.iter().skip(1).chain(block.txdata.first()) {
-        inscription_updater.index_inscriptions(tx, *txid, None)?;
+      for (tx_index, (tx, txid)) in block
+        .txdata
+        .iter()
+        .enumerate()
+        .skip(1)
+        .chain(block.txdata.iter().enumerate().take(1))
+      {
+        let tx_index = u32::try_from(tx_index).unwrap();
+        inscription_updater.index_inscriptions(tx_index, tx, *txid, None)?;
       }
     }
 
@@ -578,34 +621,83 @@ impl<'index> Updater<'index> {
       &inscription_updater.unbound_inscriptions,
     )?;
 
+    if self.index.index_addresses {
+      let mut outpoint_to_txout = wtx.open_table(OUTPOINT_TO_TXOUT)?;
+      let mut script_pubkey_to_outpoint = wtx.open_multimap_table(SCRIPT_PUBKEY_TO_OUTPOINT)?;
+
+      for (tx, txid) in &block.txdata {
+        Self::index_transaction_addresses(
+          tx,
+          *txid,
+          &mut outpoint_to_txout,
+          &mut script_pubkey_to_outpoint,
+        )?;
+      }
+    }
+
     if self.index.index_runes && self.height >= self.index.settings.first_rune_height() {
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut rune_id_to_holders = wtx.open_multimap_table(RUNE_ID_TO_HOLDERS)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
       let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
       let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
       let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
 
+      let mut outpoint_to_rune_creation = if self.index.index_spent_runes {
+        Some(wtx.open_table(OUTPOINT_TO_RUNE_CREATION)?)
+      } else {
+        None
+      };
+
+      let mut spent_outpoint_to_rune_balances = if self.index.index_spent_runes {
+        Some(wtx.open_table(SPENT_OUTPOINT_TO_RUNE_BALANCES)?)
+      } else {
+        None
+      };
+
+      let mut rune_id_to_outpoint_history = if self.index.index_spent_runes {
+        Some(wtx.open_multimap_table(RUNE_ID_TO_OUTPOINT_HISTORY)?)
+      } else {
+        None
+      };
+
+      let mut script_pubkey_to_rune_outpoint_history = if self.index.index_spent_runes {
+        Some(wtx.open_multimap_table(SCRIPT_PUBKEY_TO_RUNE_OUTPOINT_HISTORY)?)
+      } else {
+        None
+      };
+
       let runes = statistic_to_count
         .get(&Statistic::Runes.into())?
         .map(|x| x.value())
         .unwrap_or(0);
 
       let mut rune_updater = RuneUpdater {
-        event_sender: self.index.event_sender.as_ref(),
         block_time: block.header.time,
         burned: HashMap::new(),
         client: &self.index.client,
+        event_emitter: EventEmitter {
+          event_log: event_log.as_mut(),
+          event_sender: self.index.event_sender.as_ref(),
+        },
         height: self.height,
+        created_holders: HashMap::new(),
         id_to_entry: &mut rune_id_to_rune_entry,
+        id_to_history: rune_id_to_outpoint_history.as_mut(),
+        id_to_holders: &mut rune_id_to_holders,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
         minimum: Rune::minimum_at_height(
           self.index.settings.chain().network(),
           Height(self.height),
         ),
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        outpoint_to_creation: outpoint_to_rune_creation.as_mut(),
         rune_to_id: &mut rune_to_rune_id,
         runes,
+        script_pubkey_to_history: script_pubkey_to_rune_outpoint_history.as_mut(),
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
+        spent_holders: Vec::new(),
+        spent_outpoint_to_balances: spent_outpoint_to_rune_balances.as_mut(),
         statistic_to_count: &mut statistic_to_count,
         transaction_id_to_rune: &mut transaction_id_to_rune,
       };
@@ -630,8 +722,51 @@ impl<'index> Updater<'index> {
     Ok(())
   }
 
+  fn index_transaction_addresses(
+    tx: &Transaction,
+    txid: Txid,
+    outpoint_to_txout: &mut Table<&OutPointValue, &[u8]>,
+    script_pubkey_to_outpoint: &mut MultimapTable<&[u8], &OutPointValue>,
+  ) -> Result {
+    for input in &tx.input {
+      if input.previous_output.is_null() {
+        continue;
+      }
+
+      let outpoint = input.previous_output.store();
+
+      let Some(txout) = outpoint_to_txout
+        .remove(&outpoint)?
+        .map(|txout| consensus::encode::deserialize::<TxOut>(txout.value()))
+        .transpose()?
+      else {
+        continue;
+      };
+
+      script_pubkey_to_outpoint.remove(txout.script_pubkey.as_bytes(), &outpoint)?;
+    }
+
+    for (vout, txout) in tx.output.iter().enumerate() {
+      if txout.script_pubkey.is_op_return() {
+        continue;
+      }
+
+      let outpoint = OutPoint {
+        vout: vout.try_into().unwrap(),
+        txid,
+      }
+      .store();
+
+      outpoint_to_txout.insert(&outpoint, consensus::encode::serialize(txout).as_slice())?;
+      script_pubkey_to_outpoint.insert(txout.script_pubkey.as_bytes(), &outpoint)?;
+    }
+
+    Ok(())
+  }
+
   fn index_transaction_sats(
     &mut self,
+    tx_index: u32,
     tx: &Transaction,
     txid: Txid,
     sat_to_satpoint: &mut Table<u64, &SatPointValue>,
@@ -642,7 +777,7 @@ impl<'index> Updater<'index> {
     index_inscriptions: bool,
   ) -> Result {
     if index_inscriptions {
-      inscription_updater.index_inscriptions(tx, txid, Some(input_sat_ranges))?;
+      inscription_updater.index_inscriptions(tx_index, tx, txid, Some(input_sat_ranges))?;
     }
 
     for (vout, output) in tx.output.iter().enumerate() {
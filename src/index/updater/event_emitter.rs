@@ -0,0 +1,35 @@
+use super::*;
+
+pub(super) struct EventEmitter<'a, 'tx> {
+  pub(super) event_log: Option<&'a mut Table<'tx, (u32, u32, u32), &'static [u8]>>,
+  pub(super) event_sender: Option<&'a Sender<Event>>,
+}
+
+impl<'a, 'tx> EventEmitter<'a, 'tx> {
+  pub(super) fn emit(&mut self, tx_index: u32, event: Event) -> Result {
+    if let Some(event_log) = self.event_log.as_mut() {
+      let height = event.block_height();
+
+      // events are keyed by the order they were emitted in, not by
+      // transaction index, since inscriptions which land in the coinbase are
+      // only moved after every other transaction in the block
+      let seq = event_log
+        .range((height, 0, 0)..=(height, u32::MAX, u32::MAX))?
+        .next_back()
+        .transpose()?
+        .map(|(key, _)| key.value().1 + 1)
+        .unwrap_or(0);
+
+      event_log.insert(
+        (height, seq, tx_index),
+        serde_json::to_vec(&event)?.as_slice(),
+      )?;
+    }
+
+    if let Some(sender) = self.event_sender {
+      sender.blocking_send(event)?;
+    }
+
+    Ok(())
+  }
+}
@@ -23,12 +23,14 @@ pub(super) struct Flotsam {
 #[derive(Debug, Clone)]
 enum Origin {
   New {
+    content_hash: Option<[u8; 32]>,
     cursed: bool,
     fee: u64,
     hidden: bool,
     parents: Vec<InscriptionId>,
     pointer: Option<u64>,
     reinscription: bool,
+    search_terms: BTreeSet<String>,
     unbound: bool,
     vindicated: bool,
   },
@@ -40,9 +42,11 @@ enum Origin {
 pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) blessed_inscription_count: u64,
   pub(super) chain: Chain,
+  pub(super) content_hash_to_sequence_numbers:
+    Option<&'a mut MultimapTable<'tx, &'static [u8; 32], u32>>,
   pub(super) content_type_to_count: &'a mut Table<'tx, Option<&'static [u8]>, u64>,
   pub(super) cursed_inscription_count: u64,
-  pub(super) event_sender: Option<&'a Sender<Event>>,
+  pub(super) event_emitter: EventEmitter<'a, 'tx>,
   pub(super) flotsam: Vec<Flotsam>,
   pub(super) height: u32,
   pub(super) home_inscription_count: u64,
@@ -58,10 +62,14 @@ pub(super) struct InscriptionUpdater<'a, 'tx> {
   pub(super) transaction_id_to_transaction: &'a mut Table<'tx, &'static TxidValue, &'static [u8]>,
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'tx, u64, u32>,
   pub(super) satpoint_to_sequence_number: &'a mut MultimapTable<'tx, &'static SatPointValue, u32>,
+  pub(super) search_term_to_sequence_numbers: Option<&'a mut MultimapTable<'tx, &'static str, u32>>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'tx, u32, InscriptionEntryValue>,
   pub(super) sequence_number_to_satpoint: &'a mut Table<'tx, u32, &'static SatPointValue>,
+  pub(super) sequence_number_to_transfers:
+    Option<&'a mut MultimapTable<'tx, u32, (u32, u32, &'static SatPointValue, &'static TxidValue)>>,
   pub(super) timestamp: u32,
+  pub(super) transfer_count: u32,
   pub(super) unbound_inscriptions: u64,
   pub(super) value_cache: &'a mut HashMap<OutPoint, u64>,
   pub(super) value_receiver: &'a mut Receiver<u64>,
@@ -70,6 +78,7 @@ pub(super) struct InscriptionUpdater<'a, 'tx> {
 impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
   pub(super) fn index_inscriptions(
     &mut self,
+    tx_index: u32,
     tx: &Transaction,
     txid: Txid,
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
@@ -210,12 +219,23 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           inscription_id,
           offset,
           origin: Origin::New {
+            content_hash: self
+              .content_hash_to_sequence_numbers
+              .is_some()
+              .then(|| inscription.payload.content_hash())
+              .flatten()
+              .map(|hash| hash.to_byte_array()),
             cursed: curse.is_some() && !jubilant,
             fee: 0,
             hidden: inscription.payload.hidden(),
             parents: inscription.payload.parents(),
             pointer: inscription.payload.pointer(),
             reinscription: inscribed_offsets.get(&offset).is_some(),
+            search_terms: if self.search_term_to_sequence_numbers.is_some() {
+              search::terms(&inscription.payload)
+            } else {
+              BTreeSet::new()
+            },
             unbound: current_input_value == 0
               || curse == Some(Curse::UnrecognizedEvenField)
               || inscription.payload.unrecognized_even_field,
@@ -345,7 +365,7 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
         _ => new_satpoint,
       };
 
-      self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+      self.update_inscription_location(tx_index, txid, input_sat_ranges, flotsam, new_satpoint)?;
     }
 
     if is_coinbase {
@@ -354,7 +374,13 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           outpoint: OutPoint::null(),
           offset: self.lost_sats + flotsam.offset - output_value,
         };
-        self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
+        self.update_inscription_location(
+          tx_index,
+          txid,
+          input_sat_ranges,
+          flotsam,
+          new_satpoint,
+        )?;
       }
       self.lost_sats += self.reward - output_value;
       Ok(())
@@ -389,6 +415,8 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
 
   fn update_inscription_location(
     &mut self,
+    tx_index: u32,
+    txid: Txid,
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
     flotsam: Flotsam,
     new_satpoint: SatPoint,
@@ -406,25 +434,28 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           .unwrap()
           .value();
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::InscriptionTransferred {
+        self.event_emitter.emit(
+          tx_index,
+          Event::InscriptionTransferred {
             block_height: self.height,
             inscription_id,
             new_location: new_satpoint,
             old_location: old_satpoint,
             sequence_number,
-          })?;
-        }
+          },
+        )?;
 
         (false, sequence_number)
       }
       Origin::New {
+        content_hash,
         cursed,
         fee,
         hidden,
         parents,
         pointer: _,
         reinscription,
+        search_terms,
         unbound,
         vindicated,
       } => {
@@ -481,6 +512,18 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           self.sat_to_sequence_number.insert(&n, &sequence_number)?;
         }
 
+        if let (Some(content_hash_to_sequence_numbers), Some(content_hash)) =
+          (&mut self.content_hash_to_sequence_numbers, content_hash)
+        {
+          content_hash_to_sequence_numbers.insert(&content_hash, sequence_number)?;
+        }
+
+        if let Some(search_term_to_sequence_numbers) = &mut self.search_term_to_sequence_numbers {
+          for term in &search_terms {
+            search_term_to_sequence_numbers.insert(term.as_str(), sequence_number)?;
+          }
+        }
+
         let parent_sequence_numbers = parents
           .iter()
           .map(|parent| {
@@ -498,16 +541,17 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
           })
           .collect::<Result<Vec<u32>>>()?;
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::InscriptionCreated {
+        self.event_emitter.emit(
+          tx_index,
+          Event::InscriptionCreated {
             block_height: self.height,
             charms,
             inscription_id,
             location: (!unbound).then_some(new_satpoint),
             parent_inscription_ids: parents,
             sequence_number,
-          })?;
-        }
+          },
+        )?;
 
         self.sequence_number_to_entry.insert(
           sequence_number,
@@ -563,6 +607,14 @@ impl<'a, 'tx> InscriptionUpdater<'a, 'tx> {
       .sequence_number_to_satpoint
       .insert(sequence_number, &satpoint)?;
 
+    if let Some(sequence_number_to_transfers) = &mut self.sequence_number_to_transfers {
+      sequence_number_to_transfers.insert(
+        sequence_number,
+        (self.height, self.transfer_count, &satpoint, &txid.store()),
+      )?;
+      self.transfer_count += 1;
+    }
+
     Ok(())
   }
 }
@@ -4,15 +4,26 @@ pub(super) struct RuneUpdater<'a, 'tx, 'client> {
   pub(super) block_time: u32,
   pub(super) burned: HashMap<RuneId, Lot>,
   pub(super) client: &'client Client,
-  pub(super) event_sender: Option<&'a Sender<Event>>,
+  pub(super) event_emitter: EventEmitter<'a, 'tx>,
   pub(super) height: u32,
+  pub(super) created_holders: HashMap<(RuneId, OutPoint), u128>,
   pub(super) id_to_entry: &'a mut Table<'tx, RuneIdValue, RuneEntryValue>,
+  pub(super) id_to_history:
+    Option<&'a mut MultimapTable<'tx, RuneIdValue, (u32, &'static OutPointValue)>>,
+  pub(super) id_to_holders: &'a mut MultimapTable<'tx, RuneIdValue, (u128, &'static OutPointValue)>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'tx, InscriptionIdValue, u32>,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) outpoint_to_creation:
+    Option<&'a mut Table<'tx, &'static OutPointValue, (u32, &'static [u8])>>,
   pub(super) rune_to_id: &'a mut Table<'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
+  pub(super) script_pubkey_to_history:
+    Option<&'a mut MultimapTable<'tx, &'static [u8], (u32, &'static OutPointValue)>>,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'tx, u32, RuneIdValue>,
+  pub(super) spent_holders: Vec<(RuneId, OutPoint, u128)>,
+  pub(super) spent_outpoint_to_balances:
+    Option<&'a mut Table<'tx, &'static OutPointValue, (u32, &'static TxidValue, &'static [u8])>>,
   pub(super) statistic_to_count: &'a mut Table<'tx, u64, u64>,
   pub(super) transaction_id_to_rune: &'a mut Table<'tx, &'static TxidValue, u128>,
 }
@@ -21,7 +32,7 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
   pub(super) fn index_runes(&mut self, tx_index: u32, tx: &Transaction, txid: Txid) -> Result<()> {
     let artifact = Runestone::decipher(tx);
 
-    let mut unallocated = self.unallocated(tx)?;
+    let mut unallocated = self.unallocated(tx, txid)?;
 
     let mut allocated: Vec<HashMap<RuneId, Lot>> = vec![HashMap::new(); tx.output.len()];
 
@@ -30,14 +41,15 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
         if let Some(amount) = self.mint(id)? {
           *unallocated.entry(id).or_default() += amount;
 
-          if let Some(sender) = self.event_sender {
-            sender.blocking_send(Event::RuneMinted {
+          self.event_emitter.emit(
+            tx_index,
+            Event::RuneMinted {
               block_height: self.height,
               txid,
               rune_id: id,
               amount: amount.n(),
-            })?;
-          }
+            },
+          )?;
         }
       }
 
@@ -199,40 +211,74 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
       for (id, balance) in balances {
         Index::encode_rune_balance(id, balance.n(), &mut buffer);
 
-        if let Some(sender) = self.event_sender {
-          sender.blocking_send(Event::RuneTransferred {
+        self.created_holders.insert((id, outpoint), balance.n());
+
+        if let Some(id_to_history) = self.id_to_history.as_mut() {
+          id_to_history.insert(id.store(), (self.height, &outpoint.store()))?;
+        }
+
+        self.event_emitter.emit(
+          tx_index,
+          Event::RuneTransferred {
             outpoint,
             block_height: self.height,
             txid,
             rune_id: id,
             amount: balance.0,
-          })?;
-        }
+          },
+        )?;
       }
 
       self
         .outpoint_to_balances
         .insert(&outpoint.store(), buffer.as_slice())?;
+
+      if let Some(outpoint_to_creation) = self.outpoint_to_creation.as_mut() {
+        outpoint_to_creation.insert(
+          &outpoint.store(),
+          (self.height, tx.output[vout].script_pubkey.as_bytes()),
+        )?;
+      }
+
+      if let Some(script_pubkey_to_history) = self.script_pubkey_to_history.as_mut() {
+        script_pubkey_to_history.insert(
+          tx.output[vout].script_pubkey.as_bytes(),
+          (self.height, &outpoint.store()),
+        )?;
+      }
     }
 
     // increment entries with burned runes
     for (id, amount) in burned {
       *self.burned.entry(id).or_default() += amount;
 
-      if let Some(sender) = self.event_sender {
-        sender.blocking_send(Event::RuneBurned {
+      self.event_emitter.emit(
+        tx_index,
+        Event::RuneBurned {
           block_height: self.height,
           txid,
           rune_id: id,
           amount: amount.n(),
-        })?;
-      }
+        },
+      )?;
     }
 
     Ok(())
   }
 
   pub(super) fn update(self) -> Result {
+    for (id, outpoint, balance) in self.spent_holders {
+      self
+        .id_to_holders
+        .remove(id.store(), (balance, &outpoint.store()))?;
+    }
+
+    for ((id, outpoint), balance) in self.created_holders {
+      self
+        .id_to_holders
+        .insert(id.store(), (balance, &outpoint.store()))?;
+    }
+
     for (rune_id, burned) in self.burned {
       let mut entry = RuneEntry::load(self.id_to_entry.get(&rune_id.store())?.unwrap().value());
       entry.burned = entry.burned.checked_add(burned.n()).unwrap();
@@ -309,13 +355,14 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
 
     self.id_to_entry.insert(id.store(), entry.store())?;
 
-    if let Some(sender) = self.event_sender {
-      sender.blocking_send(Event::RuneEtched {
+    self.event_emitter.emit(
+      id.tx,
+      Event::RuneEtched {
         block_height: self.height,
         txid,
         rune_id: id,
-      })?;
-    }
+      },
+    )?;
 
     let inscription_id = InscriptionId { txid, index: 0 };
 
@@ -467,7 +514,7 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
     Ok(false)
   }
 
-  fn unallocated(&mut self, tx: &Transaction) -> Result<HashMap<RuneId, Lot>> {
+  fn unallocated(&mut self, tx: &Transaction, txid: Txid) -> Result<HashMap<RuneId, Lot>> {
     // map of rune ID to un-allocated balance of that rune
     let mut unallocated: HashMap<RuneId, Lot> = HashMap::new();
 
@@ -478,11 +525,29 @@ impl<'a, 'tx, 'client> RuneUpdater<'a, 'tx, 'client> {
         .remove(&input.previous_output.store())?
       {
         let buffer = guard.value();
+
+        if let Some(spent_outpoint_to_balances) = self.spent_outpoint_to_balances.as_mut() {
+          spent_outpoint_to_balances.insert(
+            &input.previous_output.store(),
+            (self.height, &txid.store(), buffer),
+          )?;
+        }
+
         let mut i = 0;
         while i < buffer.len() {
           let ((id, balance), len) = Index::decode_rune_balance(&buffer[i..]).unwrap();
           i += len;
           *unallocated.entry(id).or_default() += balance;
+
+          if self
+            .created_holders
+            .remove(&(id, input.previous_output))
+            .is_none()
+          {
+            self
+              .spent_holders
+              .push((id, input.previous_output, balance));
+          }
         }
       }
     }
@@ -4,11 +4,14 @@ use tag::Tag;
 
 pub(crate) use self::{envelope::ParsedEnvelope, media::Media};
 
-pub use self::{envelope::Envelope, inscription::Inscription, inscription_id::InscriptionId};
+pub use self::{
+  envelope::Envelope, inscription::Inscription, inscription_id::InscriptionId, manifest::Manifest,
+};
 
 mod envelope;
 mod inscription;
 pub(crate) mod inscription_id;
+pub mod manifest;
 pub(crate) mod media;
 mod tag;
 pub(crate) mod teleburn;
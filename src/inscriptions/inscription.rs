@@ -5,7 +5,7 @@ use {
   brotli::enc::{writer::CompressorWriter, BrotliEncoderParams},
   http::header::HeaderValue,
   io::Write,
-  std::str,
+  std::{borrow::Cow, str},
 };
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Eq, Default)]
@@ -25,6 +25,9 @@ pub struct Inscription {
 }
 
 impl Inscription {
+  pub(crate) const MAX_DECODED_SIZE: usize = 1 << 24;
+  pub(crate) const MAX_DECOMPRESSION_RATIO: usize = 64;
+
   pub fn new(
     chain: Chain,
     compress: bool,
@@ -226,6 +229,52 @@ impl Inscription {
     Some(self.body()?.len())
   }
 
+  /// The body with any brotli content encoding removed. Returns `None` if the
+  /// encoding is unsupported or the decoded body is larger than `limit`.
+  pub(crate) fn decoded_body(&self, limit: usize) -> Option<Cow<'_, [u8]>> {
+    let body = self.body()?;
+
+    match self.content_encoding.as_deref() {
+      None => (body.len() <= limit).then_some(Cow::Borrowed(body)),
+      Some(b"br") => {
+        let mut decoded = Vec::new();
+
+        brotli::Decompressor::new(body, 4096)
+          .take(u64::try_from(limit).unwrap().saturating_add(1))
+          .read_to_end(&mut decoded)
+          .ok()?;
+
+        (decoded.len() <= limit).then_some(Cow::Owned(decoded))
+      }
+      Some(_) => None,
+    }
+  }
+
+  /// The largest decoded body which will be decoded. Brotli encoded bodies
+  /// may not decompress to more than `MAX_DECOMPRESSION_RATIO` times their
+  /// encoded size, and no body may be larger than `MAX_DECODED_SIZE`.
+  pub(crate) fn decoded_size_limit(&self) -> usize {
+    match self.content_encoding.as_deref() {
+      Some(b"br") => Self::MAX_DECODED_SIZE.min(
+        self
+          .body()
+          .unwrap_or_default()
+          .len()
+          .saturating_mul(Self::MAX_DECOMPRESSION_RATIO),
+      ),
+      _ => Self::MAX_DECODED_SIZE,
+    }
+  }
+
+  /// SHA-256 of the decoded body, so that compressed and uncompressed copies
+  /// of the same content hash the same. Bodies which exceed the decoded size
+  /// limit are not hashed.
+  pub(crate) fn content_hash(&self) -> Option<sha256::Hash> {
+    Some(sha256::Hash::hash(
+      &self.decoded_body(self.decoded_size_limit())?,
+    ))
+  }
+
   pub(crate) fn content_type(&self) -> Option<&str> {
     str::from_utf8(self.content_type.as_ref()?).ok()
   }
@@ -907,4 +956,60 @@ mod tests {
     }
     .hidden());
   }
+
+  #[test]
+  fn content_hash() {
+    assert_eq!(Inscription::default().content_hash(), None);
+
+    let plain = Inscription {
+      body: Some(b"hello".to_vec()),
+      ..default()
+    };
+
+    assert_eq!(plain.content_hash(), Some(sha256::Hash::hash(b"hello")));
+
+    let mut compressed = Vec::new();
+
+    CompressorWriter::new(&mut compressed, 4096, 11, 24)
+      .write_all(b"hello")
+      .unwrap();
+
+    assert_eq!(
+      Inscription {
+        body: Some(compressed.clone()),
+        content_encoding: Some(b"br".to_vec()),
+        ..default()
+      }
+      .content_hash(),
+      plain.content_hash(),
+    );
+
+    assert_eq!(
+      Inscription {
+        body: Some(compressed),
+        content_encoding: Some(b"gzip".to_vec()),
+        ..default()
+      }
+      .content_hash(),
+      None,
+    );
+
+    let mut bomb = Vec::new();
+
+    CompressorWriter::new(&mut bomb, 4096, 11, 24)
+      .write_all(&vec![0; 1 << 20])
+      .unwrap();
+
+    assert!(bomb.len() * Inscription::MAX_DECOMPRESSION_RATIO < 1 << 20);
+
+    assert_eq!(
+      Inscription {
+        body: Some(bomb),
+        content_encoding: Some(b"br".to_vec()),
+        ..default()
+      }
+      .content_hash(),
+      None,
+    );
+  }
 }
@@ -0,0 +1,243 @@
+use super::*;
+
+/// A collection manifest is a JSON child inscription of a collection's parent
+/// that commits to the content and traits of each item in the collection.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+  pub items: Vec<Item>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Item {
+  pub sha256: sha256::Hash,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub traits: Option<serde_json::Value>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Verification {
+  pub(crate) mismatched: Vec<InscriptionId>,
+  pub(crate) missing: Vec<usize>,
+  pub(crate) verified: Vec<InscriptionId>,
+}
+
+impl Manifest {
+  pub(crate) const METAPROTOCOL: &'static str = "ord-collection-manifest";
+
+  pub(crate) fn item(inscription: &Inscription) -> Result<Item> {
+    Ok(Item {
+      sha256: sha256::Hash::hash(inscription.body.as_deref().unwrap_or_default()),
+      traits: inscription
+        .metadata
+        .as_ref()
+        .map(|metadata| ciborium::from_reader(Cursor::new(metadata)))
+        .transpose()
+        .context("metadata cannot be represented as JSON traits")?,
+    })
+  }
+
+  pub(crate) fn inscription(&self, parent: InscriptionId, pointer: u64) -> Result<Inscription> {
+    Ok(Inscription {
+      body: Some(serde_json::to_vec(self)?),
+      content_type: Some("application/json".into()),
+      metaprotocol: Some(Self::METAPROTOCOL.into()),
+      parents: vec![parent.value()],
+      pointer: Some(Inscription::pointer_value(pointer)),
+      ..default()
+    })
+  }
+
+  pub(crate) fn from_inscription(inscription: &Inscription) -> Option<Self> {
+    if inscription.metaprotocol() != Some(Self::METAPROTOCOL) {
+      return None;
+    }
+
+    serde_json::from_slice(inscription.body.as_ref()?).ok()
+  }
+
+  /// Match each child to an unclaimed item with the same content hash and
+  /// traits. Children which match no item are mismatched, and items which
+  /// match no child are missing.
+  pub(crate) fn verify(&self, children: &[(InscriptionId, Option<Item>)]) -> Verification {
+    let mut unclaimed = BTreeMap::<sha256::Hash, Vec<usize>>::new();
+
+    for (index, item) in self.items.iter().enumerate() {
+      unclaimed.entry(item.sha256).or_default().push(index);
+    }
+
+    let mut mismatched = Vec::new();
+    let mut verified = Vec::new();
+
+    for (id, child) in children {
+      let claimed = child.as_ref().and_then(|item| {
+        let candidates = unclaimed.get_mut(&item.sha256)?;
+        let position = candidates
+          .iter()
+          .position(|&index| self.items[index] == *item)?;
+        Some(candidates.remove(position))
+      });
+
+      match claimed {
+        Some(_index) => verified.push(*id),
+        None => mismatched.push(*id),
+      }
+    }
+
+    let mut missing = unclaimed.into_values().flatten().collect::<Vec<usize>>();
+
+    missing.sort();
+
+    Verification {
+      mismatched,
+      missing,
+      verified,
+    }
+  }
+}
+
+/// The manifest and items of a collection, accumulated one child of the
+/// collection's parent at a time, so that children's content need not be kept
+/// in memory.
+#[derive(Default)]
+pub(crate) struct Collection {
+  items: Vec<(InscriptionId, Option<Item>)>,
+  manifest: Option<(InscriptionId, Manifest)>,
+}
+
+impl Collection {
+  pub(crate) fn add(&mut self, id: InscriptionId, inscription: Option<&Inscription>) {
+    let Some(inscription) = inscription else {
+      return;
+    };
+
+    // Manifests are not collection items, and later manifests supersede
+    // earlier ones.
+    if inscription.metaprotocol() == Some(Manifest::METAPROTOCOL) {
+      if let Some(manifest) = Manifest::from_inscription(inscription) {
+        self.manifest = Some((id, manifest));
+      }
+    } else {
+      self.items.push((id, Manifest::item(inscription).ok()));
+    }
+  }
+
+  pub(crate) fn verify(&self) -> Option<(InscriptionId, Verification)> {
+    let (id, manifest) = self.manifest.as_ref()?;
+    Some((*id, manifest.verify(&self.items)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn child(body: &str, traits: Option<serde_json::Value>) -> Inscription {
+    Inscription {
+      body: Some(body.into()),
+      metadata: traits.map(|traits| {
+        let mut cbor = Vec::new();
+        ciborium::into_writer(&traits, &mut cbor).unwrap();
+        cbor
+      }),
+      ..default()
+    }
+  }
+
+  #[test]
+  fn inscription_round_trips() {
+    let manifest = Manifest {
+      items: vec![Manifest::item(&child("foo", Some(serde_json::json!({"eyes": "red"})))).unwrap()],
+    };
+
+    let inscription = manifest.inscription(inscription_id(1), 10000).unwrap();
+
+    assert_eq!(inscription.parents(), [inscription_id(1)]);
+    assert_eq!(inscription.pointer(), Some(10000));
+    assert_eq!(inscription.content_type(), Some("application/json"));
+    assert_eq!(Manifest::from_inscription(&inscription), Some(manifest));
+  }
+
+  #[test]
+  fn other_metaprotocols_are_not_manifests() {
+    let mut inscription = Manifest::default()
+      .inscription(inscription_id(1), 0)
+      .unwrap();
+
+    inscription.metaprotocol = Some("foo".into());
+
+    assert_eq!(Manifest::from_inscription(&inscription), None);
+  }
+
+  #[test]
+  fn verify() {
+    let manifest = Manifest {
+      items: vec![
+        Manifest::item(&child("foo", None)).unwrap(),
+        Manifest::item(&child("bar", Some(serde_json::json!({"eyes": "red"})))).unwrap(),
+        Manifest::item(&child("foo", None)).unwrap(),
+        Manifest::item(&child("baz", None)).unwrap(),
+      ],
+    };
+
+    let children = [
+      (inscription_id(1), child("foo", None)),
+      (
+        inscription_id(2),
+        child("bar", Some(serde_json::json!({"eyes": "blue"}))),
+      ),
+      (inscription_id(3), child("foo", None)),
+      (inscription_id(4), child("foo", None)),
+      (inscription_id(5), child("qux", None)),
+    ]
+    .into_iter()
+    .map(|(id, child)| (id, Manifest::item(&child).ok()))
+    .collect::<Vec<(InscriptionId, Option<Item>)>>();
+
+    assert_eq!(
+      manifest.verify(&children),
+      Verification {
+        mismatched: vec![inscription_id(2), inscription_id(4), inscription_id(5)],
+        missing: vec![1, 3],
+        verified: vec![inscription_id(1), inscription_id(3)],
+      }
+    );
+  }
+
+  #[test]
+  fn later_manifests_supersede_earlier_ones() {
+    let mut collection = Collection::default();
+
+    let first = Manifest {
+      items: vec![Manifest::item(&child("foo", None)).unwrap()],
+    };
+
+    let second = Manifest {
+      items: vec![Manifest::item(&child("bar", None)).unwrap()],
+    };
+
+    collection.add(
+      inscription_id(1),
+      Some(&first.inscription(inscription_id(0), 0).unwrap()),
+    );
+    collection.add(inscription_id(2), Some(&child("bar", None)));
+    collection.add(
+      inscription_id(3),
+      Some(&second.inscription(inscription_id(0), 0).unwrap()),
+    );
+    collection.add(inscription_id(4), None);
+
+    assert_eq!(
+      collection.verify(),
+      Some((
+        inscription_id(3),
+        Verification {
+          mismatched: Vec::new(),
+          missing: Vec::new(),
+          verified: vec![inscription_id(2)],
+        }
+      ))
+    );
+  }
+}
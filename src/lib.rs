@@ -39,7 +39,7 @@ use {
     },
     consensus::{self, Decodable, Encodable},
     hash_types::{BlockHash, TxMerkleNode},
-    hashes::Hash,
+    hashes::{sha256, Hash},
     script, Amount, Block, Network, OutPoint, Script, ScriptBuf, Sequence, Transaction, TxIn,
     TxOut, Txid, Witness,
   },
@@ -52,7 +52,7 @@ use {
   lazy_static::lazy_static,
   ordinals::{
     varint, Artifact, Charm, Edict, Epoch, Etching, Height, Pile, Rarity, Rune, RuneId, Runestone,
-    Sat, SatPoint, SpacedRune, Terms,
+    Sat, SatPoint, Satribute, SpacedRune, Terms,
   },
   regex::Regex,
   reqwest::Url,
@@ -85,7 +85,7 @@ pub use self::{
   chain::Chain,
   fee_rate::FeeRate,
   index::{Index, RuneEntry},
-  inscriptions::{Envelope, Inscription, InscriptionId},
+  inscriptions::{manifest, Envelope, Inscription, InscriptionId, Manifest},
   object::Object,
   options::Options,
   wallet::transaction_builder::{Target, TransactionBuilder},
@@ -45,11 +45,17 @@ pub struct Options {
   pub(crate) height_limit: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
+  #[arg(long, help = "Track unspent outputs by address.")]
+  pub(crate) index_addresses: bool,
+  #[arg(long, help = "Store index events in a durable log.")]
+  pub(crate) index_events: bool,
   #[arg(
     long,
     help = "Set index cache size to <INDEX_CACHE_SIZE> bytes. [default: 1/4 available RAM]"
   )]
   pub(crate) index_cache_size: Option<usize>,
+  #[arg(long, help = "Track inscriptions by SHA-256 of their decoded content.")]
+  pub(crate) index_content_hashes: bool,
   #[arg(
     long,
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
@@ -57,10 +63,19 @@ pub struct Options {
   pub(crate) index_runes: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
+  #[arg(
+    long,
+    help = "Track words, metadata, content types, and metaprotocols of inscriptions for search."
+  )]
+  pub(crate) index_search: bool,
+  #[arg(long, help = "Keep rune balances of spent outputs.")]
+  pub(crate) index_spent_runes: bool,
   #[arg(long, help = "Keep sat index entries of spent outputs.")]
   pub(crate) index_spent_sats: bool,
   #[arg(long, help = "Store transactions in index.")]
   pub(crate) index_transactions: bool,
+  #[arg(long, help = "Keep every location of each inscription.")]
+  pub(crate) index_transfer_history: bool,
   #[arg(long, help = "Run in integration test mode.")]
   pub(crate) integration_test: bool,
   #[arg(long, help = "Minify JSON output.")]
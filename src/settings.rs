@@ -17,11 +17,17 @@ pub struct Settings {
   height_limit: Option<u32>,
   hidden: Option<HashSet<InscriptionId>>,
   index: Option<PathBuf>,
+  index_addresses: bool,
   index_cache_size: Option<usize>,
+  index_content_hashes: bool,
+  index_events: bool,
   index_runes: bool,
   index_sats: bool,
+  index_search: bool,
+  index_spent_runes: bool,
   index_spent_sats: bool,
   index_transactions: bool,
+  index_transfer_history: bool,
   integration_test: bool,
   no_index_inscriptions: bool,
   server_password: Option<String>,
@@ -131,11 +137,17 @@ impl Settings {
           .collect(),
       ),
       index: self.index.or(source.index),
+      index_addresses: self.index_addresses || source.index_addresses,
       index_cache_size: self.index_cache_size.or(source.index_cache_size),
+      index_content_hashes: self.index_content_hashes || source.index_content_hashes,
+      index_events: self.index_events || source.index_events,
       index_runes: self.index_runes || source.index_runes,
       index_sats: self.index_sats || source.index_sats,
+      index_search: self.index_search || source.index_search,
+      index_spent_runes: self.index_spent_runes || source.index_spent_runes,
       index_spent_sats: self.index_spent_sats || source.index_spent_sats,
       index_transactions: self.index_transactions || source.index_transactions,
+      index_transfer_history: self.index_transfer_history || source.index_transfer_history,
       integration_test: self.integration_test || source.integration_test,
       no_index_inscriptions: self.no_index_inscriptions || source.no_index_inscriptions,
       server_password: self.server_password.or(source.server_password),
@@ -165,11 +177,17 @@ impl Settings {
       height_limit: options.height_limit,
       hidden: None,
       index: options.index,
+      index_addresses: options.index_addresses,
       index_cache_size: options.index_cache_size,
+      index_content_hashes: options.index_content_hashes,
+      index_events: options.index_events,
       index_runes: options.index_runes,
       index_sats: options.index_sats,
+      index_search: options.index_search,
+      index_spent_runes: options.index_spent_runes,
       index_spent_sats: options.index_spent_sats,
       index_transactions: options.index_transactions,
+      index_transfer_history: options.index_transfer_history,
       integration_test: options.integration_test,
       no_index_inscriptions: options.no_index_inscriptions,
       server_password: options.server_password,
@@ -243,11 +261,17 @@ impl Settings {
       height_limit: get_u32("HEIGHT_LIMIT")?,
       hidden: inscriptions("HIDDEN")?,
       index: get_path("INDEX"),
+      index_addresses: get_bool("INDEX_ADDRESSES"),
       index_cache_size: get_usize("INDEX_CACHE_SIZE")?,
+      index_content_hashes: get_bool("INDEX_CONTENT_HASHES"),
+      index_events: get_bool("INDEX_EVENTS"),
       index_runes: get_bool("INDEX_RUNES"),
       index_sats: get_bool("INDEX_SATS"),
+      index_search: get_bool("INDEX_SEARCH"),
+      index_spent_runes: get_bool("INDEX_SPENT_RUNES"),
       index_spent_sats: get_bool("INDEX_SPENT_SATS"),
       index_transactions: get_bool("INDEX_TRANSACTIONS"),
+      index_transfer_history: get_bool("INDEX_TRANSFER_HISTORY"),
       integration_test: get_bool("INTEGRATION_TEST"),
       no_index_inscriptions: get_bool("NO_INDEX_INSCRIPTIONS"),
       server_password: get_string("SERVER_PASSWORD"),
@@ -272,11 +296,17 @@ impl Settings {
       height_limit: None,
       hidden: None,
       index: None,
+      index_addresses: false,
       index_cache_size: None,
+      index_content_hashes: false,
+      index_events: false,
       index_runes: true,
       index_sats: true,
+      index_search: false,
+      index_spent_runes: false,
       index_spent_sats: false,
       index_transactions: false,
+      index_transfer_history: false,
       integration_test: false,
       no_index_inscriptions: false,
       server_password: None,
@@ -344,6 +374,7 @@ impl Settings {
       height_limit: self.height_limit,
       hidden: self.hidden,
       index: Some(index),
+      index_addresses: self.index_addresses,
       index_cache_size: Some(match self.index_cache_size {
         Some(index_cache_size) => index_cache_size,
         None => {
@@ -352,10 +383,15 @@ impl Settings {
           usize::try_from(sys.total_memory() / 4)?
         }
       }),
+      index_content_hashes: self.index_content_hashes,
+      index_events: self.index_events,
       index_runes: self.index_runes,
       index_sats: self.index_sats,
+      index_search: self.index_search,
+      index_spent_runes: self.index_spent_runes,
       index_spent_sats: self.index_spent_sats,
       index_transactions: self.index_transactions,
+      index_transfer_history: self.index_transfer_history,
       integration_test: self.integration_test,
       no_index_inscriptions: self.no_index_inscriptions,
       server_password: self.server_password,
@@ -506,6 +542,18 @@ impl Settings {
     self.index.as_ref().unwrap()
   }
 
+  pub(crate) fn index_addresses(&self) -> bool {
+    self.index_addresses
+  }
+
+  pub(crate) fn index_content_hashes(&self) -> bool {
+    self.index_content_hashes
+  }
+
+  pub(crate) fn index_events(&self) -> bool {
+    self.index_events
+  }
+
   pub(crate) fn index_inscriptions(&self) -> bool {
     !self.no_index_inscriptions
   }
@@ -522,6 +570,14 @@ impl Settings {
     self.index_sats
   }
 
+  pub(crate) fn index_search(&self) -> bool {
+    self.index_search
+  }
+
+  pub(crate) fn index_spent_runes(&self) -> bool {
+    self.index_spent_runes
+  }
+
   pub(crate) fn index_spent_sats(&self) -> bool {
     self.index_spent_sats
   }
@@ -530,6 +586,10 @@ impl Settings {
     self.index_transactions
   }
 
+  pub(crate) fn index_transfer_history(&self) -> bool {
+    self.index_transfer_history
+  }
+
   pub(crate) fn integration_test(&self) -> bool {
     self.integration_test
   }
@@ -991,11 +1051,17 @@ mod tests {
       ("HEIGHT_LIMIT", "3"),
       ("HIDDEN", "6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0 703e5f7c49d82aab99e605af306b9a30e991e57d42f982908a962a81ac439832i0"),
       ("INDEX", "index"),
+      ("INDEX_ADDRESSES", "1"),
       ("INDEX_CACHE_SIZE", "4"),
+      ("INDEX_CONTENT_HASHES", "1"),
+      ("INDEX_EVENTS", "1"),
       ("INDEX_RUNES", "1"),
       ("INDEX_SATS", "1"),
+      ("INDEX_SEARCH", "1"),
+      ("INDEX_SPENT_RUNES", "1"),
       ("INDEX_SPENT_SATS", "1"),
       ("INDEX_TRANSACTIONS", "1"),
+      ("INDEX_TRANSFER_HISTORY", "1"),
       ("INTEGRATION_TEST", "1"),
       ("NO_INDEX_INSCRIPTIONS", "1"),
       ("SERVER_PASSWORD", "server password"),
@@ -1034,11 +1100,17 @@ mod tests {
           .collect()
         ),
         index: Some("index".into()),
+        index_addresses: true,
         index_cache_size: Some(4),
+        index_content_hashes: true,
+        index_events: true,
         index_runes: true,
         index_sats: true,
+        index_search: true,
+        index_spent_runes: true,
         index_spent_sats: true,
         index_transactions: true,
+        index_transfer_history: true,
         integration_test: true,
         no_index_inscriptions: true,
         server_password: Some("server password".into()),
@@ -1066,11 +1138,17 @@ mod tests {
           "--datadir=/data/dir",
           "--first-inscription-height=2",
           "--height-limit=3",
+          "--index-addresses",
           "--index-cache-size=4",
+          "--index-content-hashes",
+          "--index-events",
           "--index-runes",
           "--index-sats",
+          "--index-search",
+          "--index-spent-runes",
           "--index-spent-sats",
           "--index-transactions",
+          "--index-transfer-history",
           "--index=index",
           "--integration-test",
           "--no-index-inscriptions",
@@ -1094,11 +1172,17 @@ mod tests {
         height_limit: Some(3),
         hidden: None,
         index: Some("index".into()),
+        index_addresses: true,
         index_cache_size: Some(4),
+        index_content_hashes: true,
+        index_events: true,
         index_runes: true,
         index_sats: true,
+        index_search: true,
+        index_spent_runes: true,
         index_spent_sats: true,
         index_transactions: true,
+        index_transfer_history: true,
         integration_test: true,
         no_index_inscriptions: true,
         server_password: Some("server password".into()),
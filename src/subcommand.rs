@@ -9,12 +9,15 @@ pub mod index;
 pub mod list;
 pub mod parse;
 pub mod runes;
+pub mod scan;
 pub(crate) mod server;
 mod settings;
 pub mod subsidy;
 pub mod supply;
 pub mod teleburn;
+pub mod trace;
 pub mod traits;
+pub mod verify_collection;
 pub mod wallet;
 
 #[derive(Debug, Parser)]
@@ -37,6 +40,8 @@ pub(crate) enum Subcommand {
   Parse(parse::Parse),
   #[command(about = "List all runes")]
   Runes,
+  #[command(about = "Find rare sats in outputs")]
+  Scan(scan::Scan),
   #[command(about = "Run the explorer server")]
   Server(server::Server),
   #[command(about = "Display settings")]
@@ -47,8 +52,12 @@ pub(crate) enum Subcommand {
   Supply,
   #[command(about = "Generate teleburn addresses")]
   Teleburn(teleburn::Teleburn),
+  #[command(about = "Trace every output a satoshi has been in")]
+  Trace(trace::Trace),
   #[command(about = "Display satoshi traits")]
   Traits(traits::Traits),
+  #[command(about = "Verify a collection against its manifest")]
+  VerifyCollection(verify_collection::VerifyCollection),
   #[command(about = "Wallet commands")]
   Wallet(wallet::WalletCommand),
 }
@@ -65,17 +74,24 @@ impl Subcommand {
       Self::List(list) => list.run(settings),
       Self::Parse(parse) => parse.run(),
       Self::Runes => runes::run(settings),
+      Self::Scan(scan) => scan.run(settings),
       Self::Server(server) => {
-        let index = Arc::new(Index::open(&settings)?);
+        let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+        let index = Arc::new(Index::open_with_event_sender(
+          &settings,
+          Some(event_sender),
+        )?);
         let handle = axum_server::Handle::new();
         LISTENERS.lock().unwrap().push(handle.clone());
-        server.run(settings, index, handle)
+        server.run(settings, index, handle, Some(event_receiver))
       }
       Self::Settings => settings::run(settings),
       Self::Subsidy(subsidy) => subsidy.run(),
       Self::Supply => supply::run(),
       Self::Teleburn(teleburn) => teleburn.run(),
-      Self::Traits(traits) => traits.run(),
+      Self::Trace(trace) => trace.run(settings),
+      Self::Traits(traits) => traits.run(settings),
+      Self::VerifyCollection(verify_collection) => verify_collection.run(settings),
       Self::Wallet(wallet) => wallet.run(settings),
     }
   }
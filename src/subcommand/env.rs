@@ -78,6 +78,7 @@ rpcport={bitcoind_port}
 
     let yaml = serde_yaml::to_string(&batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune: "FOO".parse::<SpacedRune>().unwrap(),
         supply: "2000".parse().unwrap(),
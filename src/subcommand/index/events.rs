@@ -0,0 +1,51 @@
+use {super::*, crate::index::event::Event, std::io::Write};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Events {
+  #[arg(long, help = "Dump events starting at block <FROM_HEIGHT>.")]
+  from_height: u32,
+  #[arg(long, help = "Dump events up to and including block <TO_HEIGHT>.")]
+  to_height: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct EventLine {
+  pub height: u32,
+  pub seq: u32,
+  pub tx_index: u32,
+  pub event: Event,
+}
+
+impl Events {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    ensure!(
+      index.has_event_index(),
+      "`ord index events` requires index created with `--index-events` flag",
+    );
+
+    index.update()?;
+
+    let mut stdout = io::stdout().lock();
+
+    index.for_each_event(
+      self.from_height..=self.to_height.unwrap_or(u32::MAX),
+      |(height, seq, tx_index), event| {
+        serde_json::to_writer(
+          &mut stdout,
+          &EventLine {
+            height,
+            seq,
+            tx_index,
+            event,
+          },
+        )?;
+        writeln!(stdout)?;
+        Ok(())
+      },
+    )?;
+
+    Ok(None)
+  }
+}
@@ -0,0 +1,95 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Scan {
+  #[arg(
+    help = "Scan <TARGET> for rare sats. <TARGET> may be an outpoint, an address, or a file \
+    with one outpoint per line."
+  )]
+  target: String,
+}
+
+impl Scan {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    if !index.has_sat_index() {
+      bail!("scan requires index created with `--index-sats` flag");
+    }
+
+    index.update()?;
+
+    let outpoints = if let Ok(outpoint) = self.target.parse::<OutPoint>() {
+      vec![outpoint]
+    } else if let Ok(address) = self.target.parse::<Address<NetworkUnchecked>>() {
+      ensure!(
+        index.has_address_index(),
+        "scanning an address requires index created with `--index-addresses` flag"
+      );
+
+      let address = address.require_network(settings.chain().network())?;
+
+      let mut outpoints = index
+        .get_address_outputs(&address)?
+        .into_iter()
+        .map(|(outpoint, _txout)| outpoint)
+        .collect::<Vec<OutPoint>>();
+
+      outpoints.sort();
+
+      outpoints
+    } else {
+      let outpoints = fs::read_to_string(&self.target)
+        .with_context(|| format!("I/O error reading `{}`", self.target))?;
+
+      Self::outpoints(&outpoints)?
+    };
+
+    Ok(Some(Box::new(index.scan(&outpoints)?)))
+  }
+
+  fn outpoints(file: &str) -> Result<Vec<OutPoint>> {
+    file
+      .lines()
+      .enumerate()
+      .map(|(i, line)| (i, line.trim()))
+      .filter(|(_i, line)| !line.starts_with('#') && !line.is_empty())
+      .map(|(i, line)| {
+        line.parse::<OutPoint>().map_err(|err| {
+          anyhow!(
+            "failed to parse outpoint from string \"{line}\" on line {}: {err}",
+            i + 1,
+          )
+        })
+      })
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn outpoints_skip_comments_and_empty_lines() {
+    assert_eq!(
+      Scan::outpoints(&format!(
+        "# deposits\n\n{}\n  {}  \n",
+        outpoint(1),
+        outpoint(2)
+      ))
+      .unwrap(),
+      [outpoint(1), outpoint(2)],
+    );
+  }
+
+  #[test]
+  fn outpoint_parse_error_reports_line() {
+    assert_eq!(
+      Scan::outpoints(&format!("{}\nfoo\n", outpoint(1)))
+        .unwrap_err()
+        .to_string(),
+      "failed to parse outpoint from string \"foo\" on line 2: OutPoint not in <txid>:<vout> format",
+    );
+  }
+}
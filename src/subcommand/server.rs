@@ -3,20 +3,30 @@ use {
     accept_encoding::AcceptEncoding,
     accept_json::AcceptJson,
     error::{OptionExt, ServerError, ServerResult},
+    event_stream::{EventFilter, EventStream, EventsQuery},
+    thumbnail::Thumbnails,
   },
   super::*,
+  crate::index::event::Event,
   crate::templates::{
-    BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, HomeHtml, InputHtml,
-    InscriptionHtml, InscriptionsBlockHtml, InscriptionsHtml, OutputHtml, PageContent, PageHtml,
-    ParentsHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml,
-    PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml,
-    PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RunesHtml, SatHtml, TransactionHtml,
+    AddressHtml, BlockHtml, BlocksHtml, ChildrenHtml, ClockSvg, CollectionsHtml, ContentHashHtml,
+    HomeHtml, InputHtml, InscriptionHtml, InscriptionSearchHtml, InscriptionsBlockHtml,
+    InscriptionsHtml, OutputHtml, PageContent, PageHtml, ParentsHtml, PreviewAudioHtml,
+    PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml,
+    PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml, RangeHtml, RareTxt,
+    RuneHtml, RunesHtml, SatHtml, TransactionHtml,
   },
   axum::{
     body,
-    extract::{Extension, Json, Path, Query},
+    extract::{
+      ws::{Message, WebSocketUpgrade},
+      Extension, Json, Path, Query,
+    },
     http::{header, HeaderValue, StatusCode, Uri},
-    response::{IntoResponse, Redirect, Response},
+    response::{
+      sse::{self, KeepAlive, Sse},
+      IntoResponse, Redirect, Response,
+    },
     routing::{get, post},
     Router,
   },
@@ -30,9 +40,12 @@ use {
     AcmeConfig,
   },
   std::{cmp::Ordering, str, sync::Arc},
-  tokio_stream::StreamExt,
+  tokio_stream::{Stream, StreamExt},
   tower_http::{
-    compression::CompressionLayer,
+    compression::{
+      predicate::{DefaultPredicate, NotForContentType, Predicate},
+      CompressionLayer,
+    },
     cors::{Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
     validate_request::ValidateRequestHeaderLayer,
@@ -44,8 +57,12 @@ pub(crate) use server_config::ServerConfig;
 mod accept_encoding;
 mod accept_json;
 mod error;
+mod event_stream;
 pub mod query;
 mod server_config;
+mod thumbnail;
+
+const RUNE_TOP_HOLDERS: usize = 10;
 
 enum SpawnConfig {
   Https(AxumAcceptor),
@@ -58,6 +75,11 @@ struct Search {
   query: String,
 }
 
+#[derive(Deserialize)]
+struct RuneHoldersQuery {
+  height: Option<u32>,
+}
+
 #[derive(RustEmbed)]
 #[folder = "static"]
 struct StaticAssets;
@@ -137,11 +159,31 @@ pub struct Server {
     help = "Poll Bitcoin Core every <POLLING_INTERVAL>."
   )]
   pub(crate) polling_interval: humantime::Duration,
+  #[arg(
+    long,
+    help = "Validate image, PDF, and model inscription content at `/thumbnail/INSCRIPTION_ID`, and serve PNG thumbnails of raster images."
+  )]
+  pub(crate) thumbnails: bool,
 }
 
 impl Server {
-  pub fn run(self, settings: Settings, index: Arc<Index>, handle: Handle) -> SubcommandResult {
+  pub fn run(
+    self,
+    settings: Settings,
+    index: Arc<Index>,
+    handle: Handle,
+    event_receiver: Option<tokio::sync::mpsc::Receiver<Event>>,
+  ) -> SubcommandResult {
     Runtime::new()?.block_on(async {
+      let event_stream = Arc::new(EventStream::new(
+        index.block_count()?,
+        index.has_event_index().then(|| index.clone()),
+      ));
+
+      if let Some(event_receiver) = event_receiver {
+        tokio::spawn(event_stream.clone().relay(event_receiver));
+      }
+
       let index_clone = index.clone();
       let integration_test = settings.integration_test();
 
@@ -176,10 +218,16 @@ impl Server {
         domain: acme_domains.first().cloned(),
         index_sats: index.has_sat_index(),
         json_api_enabled: !self.disable_json_api,
+        thumbnails: self.thumbnails,
       });
 
       let router = Router::new()
         .route("/", get(Self::home))
+        .route("/address/:address", get(Self::address))
+        .route(
+          "/address/:address/rune-history",
+          get(Self::address_rune_history),
+        )
         .route("/block/:query", get(Self::block))
         .route("/blockcount", get(Self::block_count))
         .route("/blockhash", get(Self::block_hash))
@@ -197,11 +245,26 @@ impl Server {
         .route("/collections", get(Self::collections))
         .route("/collections/:page", get(Self::collections_paginated))
         .route("/content/:inscription_id", get(Self::content))
+        .route("/content-hash/:sha256", get(Self::content_hash))
+        .route(
+          "/content-hash/:sha256/:page",
+          get(Self::content_hash_paginated),
+        )
         .route("/faq", get(Self::faq))
         .route("/favicon.ico", get(Self::favicon))
+        .route("/events", get(Self::events))
+        .route("/events/ws", get(Self::events_ws))
         .route("/feed.xml", get(Self::feed))
         .route("/input/:block/:transaction/:input", get(Self::input))
         .route("/inscription/:inscription_query", get(Self::inscription))
+        .route(
+          "/inscription/:inscription_query/history",
+          get(Self::inscription_history),
+        )
+        .route(
+          "/inscription/:inscription_query/history/:page",
+          get(Self::inscription_history_paginated),
+        )
         .route("/inscriptions", get(Self::inscriptions))
         .route("/inscriptions", post(Self::inscriptions_json))
         .route("/inscriptions/:page", get(Self::inscriptions_paginated))
@@ -213,6 +276,11 @@ impl Server {
           "/inscriptions/block/:height/:page",
           get(Self::inscriptions_in_block_paginated),
         )
+        .route("/inscriptions/search", get(Self::inscription_search))
+        .route(
+          "/inscriptions/search/:page",
+          get(Self::inscription_search_paginated),
+        )
         .route("/install.sh", get(Self::install_script))
         .route("/ordinal/:sat", get(Self::ordinal))
         .route("/output/:output", get(Self::output))
@@ -235,11 +303,24 @@ impl Server {
           "/r/inscription/:inscription_id",
           get(Self::inscription_recursive),
         )
+        .route(
+          "/r/inscription/:inscription_id/history",
+          get(Self::inscription_history),
+        )
+        .route(
+          "/r/inscription/:inscription_id/history/:page",
+          get(Self::inscription_history_paginated),
+        )
         .route("/r/children/:inscription_id", get(Self::children_recursive))
         .route(
           "/r/children/:inscription_id/:page",
           get(Self::children_recursive_paginated),
         )
+        .route("/r/content-hash/:sha256", get(Self::content_hash_recursive))
+        .route(
+          "/r/content-hash/:sha256/:page",
+          get(Self::content_hash_recursive_paginated),
+        )
         .route("/r/metadata/:inscription_id", get(Self::metadata))
         .route("/r/sat/:sat_number", get(Self::sat_inscriptions))
         .route(
@@ -253,17 +334,28 @@ impl Server {
         .route("/range/:start/:end", get(Self::range))
         .route("/rare.txt", get(Self::rare_txt))
         .route("/rune/:rune", get(Self::rune))
+        .route("/rune/:rune/holders", get(Self::rune_holders))
         .route("/runes", get(Self::runes))
         .route("/runes/:page", get(Self::runes_paginated))
         .route("/sat/:sat", get(Self::sat))
+        .route("/sat/:sat/trace", get(Self::sat_trace))
+        .route("/scan", post(Self::scan_outputs))
+        .route("/scan/:target", get(Self::scan))
         .route("/search", get(Self::search_by_query))
         .route("/search/*query", get(Self::search_by_path))
         .route("/static/*path", get(Self::static_asset))
         .route("/status", get(Self::status))
+        .route("/thumbnail/:inscription_id", get(Self::thumbnail))
         .route("/tx/:txid", get(Self::transaction))
         .route("/update", get(Self::update))
+        .route(
+          "/verify-collection/:inscription_id",
+          get(Self::verify_collection),
+        )
         .fallback(Self::fallback)
         .layer(Extension(index))
+        .layer(Extension(event_stream))
+        .layer(Extension(Arc::new(Thumbnails::default())))
         .layer(Extension(server_config.clone()))
         .layer(Extension(settings.clone()))
         .layer(SetResponseHeaderLayer::if_not_present(
@@ -279,7 +371,9 @@ impl Server {
             .allow_methods([http::Method::GET])
             .allow_origin(Any),
         )
-        .layer(CompressionLayer::new())
+        .layer(CompressionLayer::new().compress_when(
+          DefaultPredicate::new().and(NotForContentType::const_new("text/event-stream")),
+        ))
         .with_state(server_config);
 
       let router = if let Some((username, password)) = settings.credentials() {
@@ -477,6 +571,18 @@ impl Server {
     index.block_height()?.ok_or_not_found(|| "genesis block")
   }
 
+  fn resolve_rune(index: &Index, query: query::Rune) -> ServerResult<Rune> {
+    Ok(match query {
+      query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
+      query::Rune::Id(rune_id) => index
+        .get_rune_by_id(rune_id)?
+        .ok_or_not_found(|| format!("rune {rune_id}"))?,
+      query::Rune::Number(number) => index
+        .get_rune_by_number(usize::try_from(number).unwrap())?
+        .ok_or_not_found(|| format!("rune number {number}"))?,
+    })
+  }
+
   async fn clock(Extension(index): Extension<Arc<Index>>) -> ServerResult {
     task::block_in_place(|| {
       Ok(
@@ -551,6 +657,7 @@ impl Server {
           rarity: sat.rarity(),
           percentile: sat.percentile(),
           satpoint,
+          satributes: sat.satributes(server_config.chain == Chain::Mainnet),
           timestamp: blocktime.timestamp().timestamp(),
           inscriptions,
           charms: Charm::charms(charms),
@@ -558,6 +665,7 @@ impl Server {
         .into_response()
       } else {
         SatHtml {
+          chain: server_config.chain,
           sat,
           satpoint,
           blocktime,
@@ -569,6 +677,94 @@ impl Server {
     })
   }
 
+  async fn sat_trace(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(sat)): Path<DeserializeFromStr<Sat>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_spent_sat_index() {
+        return Err(ServerError::NotFound(
+          "this server has no spent sat index".to_string(),
+        ));
+      }
+
+      // finding the current output of a sat whose location is not indexed
+      // requires scanning every output, so is left to `ord trace`
+      let Some(trace) = index.trace(sat, false)? else {
+        return Err(ServerError::NotFound(format!(
+          "sat {sat} not found, only mined sats with indexed locations can be traced, use `ord trace` for others"
+        )));
+      };
+
+      Ok(if accept_json {
+        Json(trace).into_response()
+      } else {
+        trace.page(server_config).into_response()
+      })
+    })
+  }
+
+  async fn scan(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(target): Path<String>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_sat_index() {
+        return Err(ServerError::NotFound(
+          "this server has no sat index".to_string(),
+        ));
+      }
+
+      let outpoints = if let Ok(outpoint) = target.parse::<OutPoint>() {
+        vec![outpoint]
+      } else if let Ok(address) = target.parse::<Address<NetworkUnchecked>>() {
+        if !index.has_address_index() {
+          return Err(ServerError::NotFound(
+            "this server has no address index".to_string(),
+          ));
+        }
+
+        let address = address
+          .require_network(server_config.chain.network())
+          .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+        let mut outpoints = index
+          .get_address_outputs(&address)?
+          .into_iter()
+          .map(|(outpoint, _txout)| outpoint)
+          .collect::<Vec<OutPoint>>();
+
+        outpoints.sort();
+
+        outpoints
+      } else {
+        return Err(ServerError::BadRequest(format!(
+          "invalid scan target `{target}`, expected outpoint or address"
+        )));
+      };
+
+      Ok(Json(index.scan(&outpoints)?).into_response())
+    })
+  }
+
+  async fn scan_outputs(
+    Extension(index): Extension<Arc<Index>>,
+    Json(outputs): Json<Vec<OutPoint>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_sat_index() {
+        return Err(ServerError::NotFound(
+          "this server has no sat index".to_string(),
+        ));
+      }
+
+      Ok(Json(index.scan(&outputs)?).into_response())
+    })
+  }
+
   async fn ordinal(Path(sat): Path<String>) -> Redirect {
     Redirect::to(&format!("/sat/{sat}"))
   }
@@ -620,6 +816,72 @@ impl Server {
     })
   }
 
+  async fn address(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_address_index() {
+        return Err(ServerError::NotFound(
+          "this server has no address index".to_string(),
+        ));
+      }
+
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let mut outputs = index.get_address_outputs(&address)?;
+
+      outputs.sort_by_key(|(outpoint, _txout)| *outpoint);
+
+      let sat_balance = outputs.iter().map(|(_outpoint, txout)| txout.value).sum();
+
+      let mut inscriptions = Vec::new();
+      let mut runes_balances = BTreeMap::<SpacedRune, Pile>::new();
+
+      for (outpoint, _txout) in &outputs {
+        inscriptions.extend(index.get_inscriptions_on_output(*outpoint)?);
+
+        for (spaced_rune, pile) in index.get_rune_balances_for_outpoint(*outpoint)? {
+          runes_balances
+            .entry(spaced_rune)
+            .and_modify(|balance| balance.amount += pile.amount)
+            .or_insert(pile);
+        }
+      }
+
+      let outputs = outputs
+        .into_iter()
+        .map(|(outpoint, _txout)| outpoint)
+        .collect();
+
+      let runes_balances = runes_balances.into_iter().collect();
+
+      Ok(if accept_json {
+        Json(api::AddressInfo {
+          inscriptions,
+          outputs,
+          runes_balances,
+          sat_balance,
+        })
+        .into_response()
+      } else {
+        AddressHtml {
+          address,
+          inscriptions,
+          outputs,
+          runes_balances,
+          sat_balance,
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn range(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Path((DeserializeFromStr(start), DeserializeFromStr(end))): Path<(
@@ -653,15 +915,7 @@ impl Server {
         ));
       }
 
-      let rune = match rune_query {
-        query::Rune::Spaced(spaced_rune) => spaced_rune.rune,
-        query::Rune::Id(rune_id) => index
-          .get_rune_by_id(rune_id)?
-          .ok_or_not_found(|| format!("rune {rune_id}"))?,
-        query::Rune::Number(number) => index
-          .get_rune_by_number(usize::try_from(number).unwrap())?
-          .ok_or_not_found(|| format!("rune number {number}"))?,
-      };
+      let rune = Self::resolve_rune(&index, rune_query)?;
 
       let (id, entry, parent) = index
         .rune(rune)?
@@ -671,20 +925,30 @@ impl Server {
 
       let mintable = entry.mintable((block_height.n() + 1).into()).is_ok();
 
+      let (holders, top_holders) = index.get_rune_top_holders(id, RUNE_TOP_HOLDERS)?;
+
+      let circulating = entry.supply() - entry.burned;
+
       Ok(if accept_json {
         Json(api::Rune {
+          circulating,
           entry,
+          holders,
           id,
           mintable,
           parent,
+          top_holders,
         })
         .into_response()
       } else {
         RuneHtml {
+          circulating,
           entry,
+          holders,
           id,
           mintable,
           parent,
+          top_holders,
         }
         .page(server_config)
         .into_response()
@@ -692,6 +956,95 @@ impl Server {
     })
   }
 
+  async fn address_rune_history(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(address): Path<Address<NetworkUnchecked>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_spent_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no spent rune index".to_string(),
+        ));
+      }
+
+      let address = address
+        .require_network(server_config.chain.network())
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let history = index
+        .get_script_pubkey_rune_history(&address.script_pubkey())?
+        .into_iter()
+        .map(|(height, balances)| api::RuneBalances { balances, height })
+        .collect();
+
+      Ok(
+        Json(api::RuneBalanceHistory {
+          address: uncheck(&address),
+          history,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  async fn rune_holders(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(DeserializeFromStr(rune_query)): Path<DeserializeFromStr<query::Rune>>,
+    Query(query): Query<RuneHoldersQuery>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_spent_rune_index() {
+        return Err(ServerError::NotFound(
+          "this server has no spent rune index".to_string(),
+        ));
+      }
+
+      let rune = Self::resolve_rune(&index, rune_query)?;
+
+      let (id, entry, _parent) = index
+        .rune(rune)?
+        .ok_or_not_found(|| format!("rune {rune}"))?;
+
+      let block_height = index.block_height()?.unwrap_or(Height(0)).n();
+
+      let height = query.height.unwrap_or(block_height);
+
+      if height > block_height {
+        return Err(ServerError::BadRequest(format!(
+          "height {height} is greater than current block height {block_height}"
+        )));
+      }
+
+      let mut holders = index
+        .get_rune_holders_at_height(id, height)?
+        .into_iter()
+        .map(|(outpoint, script_pubkey, amount)| api::RuneHolder {
+          address: server_config
+            .chain
+            .address_from_script(&script_pubkey)
+            .ok()
+            .map(|address| uncheck(&address)),
+          amount,
+          outpoint,
+        })
+        .collect::<Vec<api::RuneHolder>>();
+
+      holders.sort_by_key(|holder| (Reverse(holder.amount), holder.outpoint));
+
+      Ok(
+        Json(api::RuneHolders {
+          height,
+          holders,
+          id,
+          rune: entry.spaced_rune,
+        })
+        .into_response(),
+      )
+    })
+  }
+
   async fn runes(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -996,6 +1349,10 @@ impl Server {
     task::block_in_place(|| {
       let query = query.trim();
 
+      // With a search index, words which aren't known runes or numeric sats go
+      // to inscription search, so sat names must be prefixed with `sat:`.
+      let search = index.has_search_index();
+
       if re::HASH.is_match(query) {
         if index.block_header(query.parse().unwrap())?.is_some() {
           Ok(Redirect::to(&format!("/block/{query}")))
@@ -1006,7 +1363,16 @@ impl Server {
         Ok(Redirect::to(&format!("/output/{query}")))
       } else if re::INSCRIPTION_ID.is_match(query) || re::INSCRIPTION_NUMBER.is_match(query) {
         Ok(Redirect::to(&format!("/inscription/{query}")))
-      } else if re::SPACED_RUNE.is_match(query) {
+      } else if re::SPACED_RUNE.is_match(query)
+        && (!search
+          || query
+            .parse::<SpacedRune>()
+            .ok()
+            .map(|spaced_rune| index.rune(spaced_rune.rune))
+            .transpose()?
+            .flatten()
+            .is_some())
+      {
         Ok(Redirect::to(&format!("/rune/{query}")))
       } else if re::RUNE_ID.is_match(query) {
         let id = query
@@ -1016,6 +1382,17 @@ impl Server {
         let rune = index.get_rune_by_id(id)?.ok_or_not_found(|| "rune ID")?;
 
         Ok(Redirect::to(&format!("/rune/{rune}")))
+      } else if query.parse::<Address<NetworkUnchecked>>().is_ok() {
+        Ok(Redirect::to(&format!("/address/{query}")))
+      } else if let Some(sat) = query.strip_prefix("sat:") {
+        Ok(Redirect::to(&format!("/sat/{}", sat.trim())))
+      } else if search
+        && (query.parse::<Sat>().is_err() || query.chars().any(|c| c.is_ascii_lowercase()))
+      {
+        Ok(Redirect::to(&format!(
+          "/inscriptions/search?query={}",
+          urlencoding::encode(query)
+        )))
       } else {
         Ok(Redirect::to(&format!("/sat/{query}")))
       }
@@ -1030,6 +1407,46 @@ impl Server {
     )
   }
 
+  async fn events(
+    Extension(event_stream): Extension<Arc<EventStream>>,
+    Query(query): Query<EventsQuery>,
+    headers: HeaderMap,
+  ) -> ServerResult<Sse<impl Stream<Item = Result<sse::Event, serde_json::Error>>>> {
+    let events = event_stream.subscribe(EventFilter::new(query, headers.get("last-event-id"))?)?;
+
+    Ok(
+      Sse::new(events.map(|((height, seq), event)| {
+        sse::Event::default()
+          .event(event.kind().to_string())
+          .id(format!("{height}:{seq}"))
+          .json_data(event)
+      }))
+      .keep_alive(KeepAlive::default()),
+    )
+  }
+
+  async fn events_ws(
+    Extension(event_stream): Extension<Arc<EventStream>>,
+    Query(query): Query<EventsQuery>,
+    websocket: WebSocketUpgrade,
+  ) -> ServerResult {
+    let events = event_stream.subscribe(EventFilter::new(query, None)?)?;
+
+    Ok(websocket.on_upgrade(|mut socket| async move {
+      let mut events = Box::pin(events);
+
+      while let Some((_, event)) = events.next().await {
+        let Ok(json) = serde_json::to_string(&event) else {
+          break;
+        };
+
+        if socket.send(Message::Text(json)).await.is_err() {
+          break;
+        }
+      }
+    }))
+  }
+
   async fn feed(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1395,6 +1812,64 @@ impl Server {
     Ok(Some((headers, body)))
   }
 
+  async fn thumbnail(
+    Extension(index): Extension<Arc<Index>>,
+    Extension(settings): Extension<Arc<Settings>>,
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(thumbnails): Extension<Arc<Thumbnails>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !server_config.thumbnails {
+        return Err(ServerError::NotFound(
+          "this server does not generate thumbnails".to_string(),
+        ));
+      }
+
+      if settings.is_hidden(inscription_id) {
+        return Err(ServerError::NotFound(format!(
+          "inscription {inscription_id} thumbnail not found"
+        )));
+      }
+
+      let mut inscription = index
+        .get_inscription_by_id(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?;
+
+      if let Some(delegate) = inscription.delegate() {
+        inscription = index
+          .get_inscription_by_id(delegate)?
+          .ok_or_not_found(|| format!("delegate {inscription_id}"))?
+      }
+
+      if !thumbnail::supported(&inscription) {
+        return Err(ServerError::NotFound(format!(
+          "inscription {inscription_id} thumbnail not found"
+        )));
+      }
+
+      match &*thumbnails.get(inscription_id, &inscription) {
+        Ok(None) => Ok(StatusCode::NO_CONTENT.into_response()),
+        Ok(Some(png)) => Ok(
+          (
+            [
+              (header::CONTENT_TYPE, HeaderValue::from_static("image/png")),
+              (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static("public, max-age=1209600, immutable"),
+              ),
+            ],
+            png.clone(),
+          )
+            .into_response(),
+        ),
+        Err(reason) => Err(ServerError::BadRequest(format!(
+          "inscription {inscription_id} content is invalid: {reason}"
+        ))),
+      }
+    })
+  }
+
   async fn preview(
     Extension(index): Extension<Arc<Index>>,
     Extension(settings): Extension<Arc<Settings>>,
@@ -1501,6 +1976,29 @@ impl Server {
       Ok(if accept_json {
         Json(info).into_response()
       } else {
+        let content_hash = index
+          .has_content_hash_index()
+          .then(|| inscription.content_hash())
+          .flatten();
+
+        let first_inscribed = match content_hash {
+          Some(content_hash) => index
+            .get_first_inscription_by_content_hash(content_hash)?
+            .filter(|(id, _number)| *id != info.id),
+          None => None,
+        };
+
+        let transfers = if index.has_transfer_history_index() {
+          let sequence_number = index
+            .get_inscription_entry(info.id)?
+            .ok_or_not_found(|| format!("inscription {}", info.id))?
+            .sequence_number;
+
+          Some(index.get_latest_transfers_by_sequence_number(sequence_number, 100)?)
+        } else {
+          None
+        };
+
         InscriptionHtml {
           chain: server_config.chain,
           charms: Charm::Vindicated.unset(info.charms.iter().fold(0, |mut acc, charm| {
@@ -1508,7 +2006,9 @@ impl Server {
             acc
           })),
           children: info.children,
+          content_hash,
           fee: info.fee,
+          first_inscribed,
           height: info.height,
           inscription,
           id: info.id,
@@ -1521,6 +2021,7 @@ impl Server {
           sat: info.sat,
           satpoint: info.satpoint,
           timestamp: Utc.timestamp_opt(info.timestamp, 0).unwrap(),
+          transfers,
         }
         .page(server_config)
         .into_response()
@@ -1528,24 +2029,62 @@ impl Server {
     })
   }
 
-  async fn inscriptions_json(
+  async fn inscription_history(
     Extension(index): Extension<Arc<Index>>,
-    _: AcceptJson,
-    Json(inscriptions): Json<Vec<InscriptionId>>,
+    Path(inscription_id): Path<InscriptionId>,
   ) -> ServerResult {
-    task::block_in_place(|| {
-      let mut response = Vec::new();
-      for inscription in inscriptions {
-        let query = query::Inscription::Id(inscription);
-        let (info, _, _) = index
-          .inscription_info(query)?
-          .ok_or_not_found(|| format!("inscription {query}"))?;
-
-        response.push(info);
-      }
-
-      Ok(Json(response).into_response())
-    })
+    Self::inscription_history_paginated(Extension(index), Path((inscription_id, 0))).await
+  }
+
+  async fn inscription_history_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((inscription_id, page)): Path<(InscriptionId, usize)>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_transfer_history_index() {
+        return Err(ServerError::NotFound(
+          "this server has no transfer history index".to_string(),
+        ));
+      }
+
+      let sequence_number = index
+        .get_inscription_entry(inscription_id)?
+        .ok_or_not_found(|| format!("inscription {inscription_id}"))?
+        .sequence_number;
+
+      let (transfers, more) =
+        index.get_transfers_by_sequence_number_paginated(sequence_number, 100, page)?;
+
+      Ok(
+        Json(api::InscriptionHistory {
+          id: inscription_id,
+          more,
+          page,
+          transfers,
+        })
+        .into_response(),
+      )
+    })
+  }
+
+  async fn inscriptions_json(
+    Extension(index): Extension<Arc<Index>>,
+    _: AcceptJson,
+    Json(inscriptions): Json<Vec<InscriptionId>>,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      let mut response = Vec::new();
+      for inscription in inscriptions {
+        let query = query::Inscription::Id(inscription);
+        let (info, _, _) = index
+          .inscription_info(query)?
+          .ok_or_not_found(|| format!("inscription {query}"))?;
+
+        response.push(info);
+      }
+
+      Ok(Json(response).into_response())
+    })
   }
 
   async fn collections(
@@ -1624,6 +2163,28 @@ impl Server {
       )
     })
   }
+  async fn verify_collection(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(parent): Path<InscriptionId>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      index
+        .get_inscription_entry(parent)?
+        .ok_or_not_found(|| format!("inscription {parent}"))?;
+
+      let verification = index
+        .verify_collection(parent)?
+        .ok_or_not_found(|| format!("collection manifest for inscription {parent}"))?;
+
+      Ok(if accept_json {
+        Json(verification).into_response()
+      } else {
+        verification.page(server_config).into_response()
+      })
+    })
+  }
 
   async fn children_recursive(
     Extension(index): Extension<Arc<Index>>,
@@ -1649,6 +2210,82 @@ impl Server {
     })
   }
 
+  async fn content_hash(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(content_hash): Path<DeserializeFromStr<sha256::Hash>>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::content_hash_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path((content_hash, 0)),
+      accept_json,
+    )
+    .await
+  }
+
+  async fn content_hash_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(content_hash), page)): Path<(DeserializeFromStr<sha256::Hash>, usize)>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_content_hash_index() {
+        return Err(ServerError::NotFound(
+          "this server has no content hash index".to_string(),
+        ));
+      }
+
+      let (inscriptions, more) =
+        index.get_inscription_ids_by_content_hash_paginated(content_hash, 100, page)?;
+
+      Ok(if accept_json {
+        Json(api::ContentHashInscriptions {
+          ids: inscriptions,
+          more,
+          page,
+        })
+        .into_response()
+      } else {
+        ContentHashHtml {
+          content_hash,
+          inscriptions,
+          prev_page: page.checked_sub(1),
+          next_page: more.then_some(page + 1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
+  async fn content_hash_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Path(content_hash): Path<DeserializeFromStr<sha256::Hash>>,
+  ) -> ServerResult<Json<api::ContentHashInscriptions>> {
+    Self::content_hash_recursive_paginated(Extension(index), Path((content_hash, 0))).await
+  }
+
+  async fn content_hash_recursive_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((DeserializeFromStr(content_hash), page)): Path<(DeserializeFromStr<sha256::Hash>, usize)>,
+  ) -> ServerResult<Json<api::ContentHashInscriptions>> {
+    task::block_in_place(|| {
+      if !index.has_content_hash_index() {
+        return Err(ServerError::NotFound(
+          "this server has no content hash index".to_string(),
+        ));
+      }
+
+      let (ids, more) =
+        index.get_inscription_ids_by_content_hash_paginated(content_hash, 100, page)?;
+
+      Ok(Json(api::ContentHashInscriptions { ids, more, page }))
+    })
+  }
+
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1695,6 +2332,62 @@ impl Server {
     })
   }
 
+  async fn inscription_search(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    search: Query<Search>,
+    accept_json: AcceptJson,
+  ) -> ServerResult {
+    Self::inscription_search_paginated(
+      Extension(server_config),
+      Extension(index),
+      Path(0),
+      search,
+      accept_json,
+    )
+    .await
+  }
+
+  async fn inscription_search_paginated(
+    Extension(server_config): Extension<Arc<ServerConfig>>,
+    Extension(index): Extension<Arc<Index>>,
+    Path(page): Path<usize>,
+    Query(search): Query<Search>,
+    AcceptJson(accept_json): AcceptJson,
+  ) -> ServerResult {
+    task::block_in_place(|| {
+      if !index.has_search_index() {
+        return Err(ServerError::NotFound(
+          "this server has no search index".to_string(),
+        ));
+      }
+
+      let terms = crate::index::search::query(&search.query)
+        .map_err(|err| ServerError::BadRequest(err.to_string()))?;
+
+      let (inscriptions, more) = index.search_inscriptions_paginated(&terms, 100, page)?;
+
+      Ok(if accept_json {
+        Json(api::InscriptionSearch {
+          ids: inscriptions,
+          more,
+          page,
+          terms,
+        })
+        .into_response()
+      } else {
+        InscriptionSearchHtml {
+          query: search.query,
+          inscriptions,
+          prev_page: page.checked_sub(1),
+          next_page: more.then_some(page + 1),
+        }
+        .page(server_config)
+        .into_response()
+      })
+    })
+  }
+
   async fn inscriptions_in_block(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1855,7 +2548,8 @@ impl Server {
 #[cfg(test)]
 mod tests {
   use {
-    super::*, reqwest::Url, serde::de::DeserializeOwned, std::net::TcpListener, tempfile::TempDir,
+    super::*, reqwest::Url, serde::de::DeserializeOwned, std::io::BufRead, std::net::TcpListener,
+    tempfile::TempDir,
   };
 
   const RUNE: u128 = 99246114928149462;
@@ -1987,13 +2681,18 @@ mod tests {
         .or_defaults()
         .unwrap();
 
-      let index = Arc::new(Index::open(&settings).unwrap());
+      let (event_sender, event_receiver) = tokio::sync::mpsc::channel(1024);
+      let index = Arc::new(Index::open_with_event_sender(&settings, Some(event_sender)).unwrap());
       let ord_server_handle = Handle::new();
 
       {
         let index = index.clone();
         let ord_server_handle = ord_server_handle.clone();
-        thread::spawn(|| server.run(settings, index, ord_server_handle).unwrap());
+        thread::spawn(|| {
+          server
+            .run(settings, index, ord_server_handle, Some(event_receiver))
+            .unwrap()
+        });
       }
 
       while index.statistic(crate::index::Statistic::Commits) == 0 {
@@ -2031,6 +2730,14 @@ mod tests {
       self.server_flag("--https")
     }
 
+    fn index_addresses(self) -> Self {
+      self.ord_flag("--index-addresses")
+    }
+
+    fn index_content_hashes(self) -> Self {
+      self.ord_flag("--index-content-hashes")
+    }
+
     fn index_runes(self) -> Self {
       self.ord_flag("--index-runes")
     }
@@ -2039,6 +2746,22 @@ mod tests {
       self.ord_flag("--index-sats")
     }
 
+    fn index_search(self) -> Self {
+      self.ord_flag("--index-search")
+    }
+
+    fn index_spent_runes(self) -> Self {
+      self.ord_flag("--index-spent-runes")
+    }
+
+    fn index_spent_sats(self) -> Self {
+      self.ord_flag("--index-spent-sats")
+    }
+
+    fn index_transfer_history(self) -> Self {
+      self.ord_flag("--index-transfer-history")
+    }
+
     fn redirect_http_to_https(self) -> Self {
       self.server_flag("--redirect-http-to-https")
     }
@@ -2462,6 +3185,52 @@ mod tests {
     TestServer::new().assert_redirect("/search/abc", "/sat/abc");
   }
 
+  #[test]
+  fn search_with_search_index_falls_back_to_inscription_search() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .index_search()
+      .build();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          rune: Some(rune),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    server.mine_blocks(1);
+
+    server.assert_redirect("/search?query=hello", "/inscriptions/search?query=hello");
+    server.assert_redirect("/search?query=HELLO", "/inscriptions/search?query=HELLO");
+    server.assert_redirect(&format!("/search?query={rune}"), &format!("/rune/{rune}"));
+    server.assert_redirect("/search?query=sat:hello", "/sat/hello");
+    server.assert_redirect("/search?query=100.0", "/sat/100.0");
+  }
+
+  #[test]
+  fn search_for_address_returns_address() {
+    TestServer::new().assert_redirect(
+      "/search/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+      "/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+    );
+  }
+
   #[test]
   fn search_for_blockhash_returns_block() {
     TestServer::new().assert_redirect(
@@ -2835,6 +3604,16 @@ mod tests {
   <dd>340282366920938463463374607431768211455\u{A0}%</dd>
   <dt>burned</dt>
   <dd>0\u{A0}%</dd>
+  <dt>circulating</dt>
+  <dd>340282366920938463463374607431768211455\u{A0}%</dd>
+  <dt>holders</dt>
+  <dd>1</dd>
+  <dt>top holders</dt>
+  <dd>
+    <ul class=monospace>
+      <li><a href=/output/{txid}:0>{txid}:0</a> 340282366920938463463374607431768211455\u{A0}%</li>
+    </ul>
+  </dd>
   <dt>divisibility</dt>
   <dd>0</dd>
   <dt>symbol</dt>
@@ -3271,6 +4050,8 @@ mod tests {
   <dd>.*</dd>
   <dt>unrecoverably reorged</dt>
   <dd>false</dd>
+  <dt>address index</dt>
+  <dd>false</dd>
   <dt>rune index</dt>
   <dd>false</dd>
   <dt>sat index</dt>
@@ -3474,38 +4255,196 @@ mod tests {
   }
 
   #[test]
-  fn output_with_sat_index() {
-    let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+  fn address_without_address_index() {
     TestServer::builder()
-      .index_sats()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        "/address/bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202",
+        StatusCode::NOT_FOUND,
+        "this server has no address index",
+      );
+  }
+
+  #[test]
+  fn address_on_wrong_network() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
       .build()
       .assert_response_regex(
-        format!("/output/{txid}:0"),
-        StatusCode::OK,
-        format!(
-          ".*<title>Output {txid}:0</title>.*<h1>Output <span class=monospace>{txid}:0</span></h1>
-<dl>
-  <dt>value</dt><dd>5000000000</dd>
-  <dt>script pubkey</dt><dd class=monospace>OP_PUSHBYTES_65 [[:xdigit:]]{{130}} OP_CHECKSIG</dd>
-  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
-  <dt>spent</dt><dd>false</dd>
-</dl>
-<h2>1 Sat Range</h2>
-<ul class=monospace>
-  <li><a href=/range/0/5000000000 class=mythic>0–5000000000</a></li>
-</ul>.*"
-        ),
+        "/address/bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        StatusCode::BAD_REQUEST,
+        ".*",
       );
   }
 
   #[test]
-  fn output_without_sat_index() {
-    let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
-    TestServer::new().assert_response_regex(
-      format!("/output/{txid}:0"),
+  fn address() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
+      .build();
+
+    server.mine_blocks(1);
+
+    let address = "bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202";
+
+    pretty_assert_eq!(
+      server.get_json::<api::AddressInfo>(format!("/address/{address}")),
+      api::AddressInfo {
+        inscriptions: Vec::new(),
+        outputs: Vec::new(),
+        runes_balances: Vec::new(),
+        sat_balance: 0,
+      }
+    );
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      outputs: 2,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = InscriptionId { txid, index: 0 };
+
+    pretty_assert_eq!(
+      server.get_json::<api::AddressInfo>(format!("/address/{address}")),
+      api::AddressInfo {
+        inscriptions: vec![id],
+        outputs: vec![OutPoint { txid, vout: 0 }, OutPoint { txid, vout: 1 }],
+        runes_balances: Vec::new(),
+        sat_balance: 50 * COIN_VALUE,
+      }
+    );
+
+    server.assert_response_regex(
+      format!("/address/{address}"),
       StatusCode::OK,
       format!(
-        ".*<title>Output {txid}:0</title>.*<h1>Output <span class=monospace>{txid}:0</span></h1>
+        ".*<title>Address {address}</title>.*<h1>Address <span class=monospace>{address}</span></h1>
+<dl>
+  <dt>sat balance</dt>
+  <dd>5000000000</dd>
+  <dt>inscriptions</dt>
+  <dd class=thumbnails>
+    <a href=/inscription/{id}>.*</a>
+  </dd>
+  <dt>outputs</dt>
+  <dd>
+    <ul class=monospace>
+      <li><a href=/output/{txid}:0>{txid}:0</a></li>
+      <li><a href=/output/{txid}:1>{txid}:1</a></li>
+    </ul>
+  </dd>
+</dl>.*"
+      ),
+    );
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 1, Default::default())],
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::AddressInfo>(format!("/address/{address}")),
+      api::AddressInfo {
+        inscriptions: vec![id],
+        outputs: vec![OutPoint { txid, vout: 0 }],
+        runes_balances: Vec::new(),
+        sat_balance: 25 * COIN_VALUE,
+      }
+    );
+  }
+
+  #[test]
+  fn address_rune_balances() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let (txid, _id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          premine: Some(u128::MAX),
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let address = "bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202";
+
+    let response = server.get_json::<api::AddressInfo>(format!("/address/{address}"));
+
+    assert!(response.outputs.contains(&OutPoint { txid, vout: 0 }));
+
+    pretty_assert_eq!(
+      response.runes_balances,
+      vec![(
+        SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        Pile {
+          amount: u128::MAX,
+          divisibility: 0,
+          symbol: None,
+        },
+      )]
+    );
+  }
+
+  #[test]
+  fn output_with_sat_index() {
+    let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+    TestServer::builder()
+      .index_sats()
+      .build()
+      .assert_response_regex(
+        format!("/output/{txid}:0"),
+        StatusCode::OK,
+        format!(
+          ".*<title>Output {txid}:0</title>.*<h1>Output <span class=monospace>{txid}:0</span></h1>
+<dl>
+  <dt>value</dt><dd>5000000000</dd>
+  <dt>script pubkey</dt><dd class=monospace>OP_PUSHBYTES_65 [[:xdigit:]]{{130}} OP_CHECKSIG</dd>
+  <dt>transaction</dt><dd><a class=monospace href=/tx/{txid}>{txid}</a></dd>
+  <dt>spent</dt><dd>false</dd>
+</dl>
+<h2>1 Sat Range</h2>
+<ul class=monospace>
+  <li><a href=/range/0/5000000000 class=mythic>0–5000000000</a></li>
+</ul>.*"
+        ),
+      );
+  }
+
+  #[test]
+  fn output_without_sat_index() {
+    let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+    TestServer::new().assert_response_regex(
+      format!("/output/{txid}:0"),
+      StatusCode::OK,
+      format!(
+        ".*<title>Output {txid}:0</title>.*<h1>Output <span class=monospace>{txid}:0</span></h1>
 <dl>
   <dt>value</dt><dd>5000000000</dd>
   <dt>script pubkey</dt><dd class=monospace>OP_PUSHBYTES_65 [[:xdigit:]]{{130}} OP_CHECKSIG</dd>
@@ -6201,4 +7140,1104 @@ next
     let server = TestServer::builder().build();
     server.assert_response("/update", StatusCode::NOT_FOUND, "");
   }
+
+  #[test]
+  fn events() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let response = server.get(format!("/events?from_height=0&kind=rune_etched&rune={id}"));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "text/event-stream"
+    );
+
+    let mut lines = io::BufReader::new(response).lines();
+
+    assert_eq!(lines.next().unwrap().unwrap(), "event:rune_etched");
+    assert!(lines
+      .next()
+      .unwrap()
+      .unwrap()
+      .starts_with(&format!("id:{}:", id.block)));
+
+    let data = lines.next().unwrap().unwrap();
+
+    pretty_assert_eq!(
+      serde_json::from_str::<api::Event>(data.strip_prefix("data:").unwrap()).unwrap(),
+      api::Event::RuneEtched {
+        block_height: u32::try_from(id.block).unwrap(),
+        rune_id: id,
+        txid,
+      }
+    );
+  }
+
+  #[test]
+  fn events_are_replayed_from_event_log_after_last_event_id() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .ord_flag("--index-events")
+      .build();
+
+    server.mine_blocks(1);
+
+    let (_, a) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let (txid, b) = server.etch(
+      Runestone {
+        etching: Some(Etching {
+          rune: Some(Rune(RUNE + 1)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let mut lines =
+      io::BufReader::new(server.get("/events?from_height=0&kind=rune_etched")).lines();
+
+    assert_eq!(lines.next().unwrap().unwrap(), "event:rune_etched");
+
+    let last_event_id = lines
+      .next()
+      .unwrap()
+      .unwrap()
+      .strip_prefix("id:")
+      .unwrap()
+      .to_string();
+
+    assert!(last_event_id.starts_with(&format!("{}:", a.block)));
+
+    let response = reqwest::blocking::Client::new()
+      .get(server.join_url("/events?kind=rune_etched"))
+      .header("last-event-id", last_event_id)
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut lines = io::BufReader::new(response).lines();
+
+    assert_eq!(lines.next().unwrap().unwrap(), "event:rune_etched");
+    assert!(lines
+      .next()
+      .unwrap()
+      .unwrap()
+      .starts_with(&format!("id:{}:", b.block)));
+
+    pretty_assert_eq!(
+      serde_json::from_str::<api::Event>(
+        lines
+          .next()
+          .unwrap()
+          .unwrap()
+          .strip_prefix("data:")
+          .unwrap()
+      )
+      .unwrap(),
+      api::Event::RuneEtched {
+        block_height: u32::try_from(b.block).unwrap(),
+        rune_id: b,
+        txid,
+      }
+    );
+  }
+
+  #[test]
+  fn events_with_invalid_filter() {
+    let server = TestServer::builder().build();
+
+    server.assert_response(
+      "/events?kind=foo",
+      StatusCode::BAD_REQUEST,
+      "invalid event kind `foo`: invalid event kind: foo",
+    );
+  }
+
+  #[test]
+  fn rune_holders() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_spent_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let (txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          premine: Some(u128::MAX),
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    let height = u32::try_from(id.block).unwrap();
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneHolders>(format!("/rune/{id}/holders?height={height}")),
+      api::RuneHolders {
+        height,
+        holders: vec![api::RuneHolder {
+          address: Some(
+            "bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202"
+              .parse()
+              .unwrap()
+          ),
+          amount: u128::MAX,
+          outpoint: OutPoint { txid, vout: 0 },
+        }],
+        id,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+      }
+    );
+
+    pretty_assert_eq!(
+      server
+        .get_json::<api::RuneHolders>(format!("/rune/{id}/holders?height={}", height - 1))
+        .holders,
+      Vec::new(),
+    );
+
+    server.assert_response(
+      format!("/rune/{id}/holders?height={}", height + 1),
+      StatusCode::BAD_REQUEST,
+      &format!(
+        "height {} is greater than current block height {height}",
+        height + 1
+      ),
+    );
+  }
+
+  #[test]
+  fn rune_holders_without_spent_rune_index() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.assert_response(
+      "/rune/1:1/holders",
+      StatusCode::NOT_FOUND,
+      "this server has no spent rune index",
+    );
+  }
+
+  #[test]
+  fn address_rune_history() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_spent_runes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let (_txid, id) = server.etch(
+      Runestone {
+        edicts: vec![Edict {
+          id: RuneId::default(),
+          amount: u128::MAX,
+          output: 0,
+        }],
+        etching: Some(Etching {
+          premine: Some(u128::MAX),
+          rune: Some(Rune(RUNE)),
+          ..default()
+        }),
+        ..default()
+      },
+      1,
+      None,
+    );
+
+    server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(id.block.try_into().unwrap(), 1, 0, Witness::new())],
+      outputs: 1,
+      p2tr: true,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let address = "bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202";
+
+    pretty_assert_eq!(
+      server.get_json::<api::RuneBalanceHistory>(format!("/address/{address}/rune-history")),
+      api::RuneBalanceHistory {
+        address: address.parse().unwrap(),
+        history: vec![
+          api::RuneBalances {
+            balances: vec![(
+              SpacedRune {
+                rune: Rune(RUNE),
+                spacers: 0,
+              },
+              Pile {
+                amount: u128::MAX,
+                divisibility: 0,
+                symbol: None,
+              },
+            )],
+            height: u32::try_from(id.block).unwrap(),
+          },
+          api::RuneBalances {
+            balances: Vec::new(),
+            height: u32::try_from(id.block).unwrap() + 1,
+          },
+        ],
+      }
+    );
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_runes()
+      .build();
+
+    server.assert_response(
+      format!("/address/{address}/rune-history"),
+      StatusCode::NOT_FOUND,
+      "this server has no spent rune index",
+    );
+  }
+
+  #[test]
+  fn content_hash() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_content_hashes()
+      .build();
+
+    server.mine_blocks(1);
+
+    let first = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let mut compressed = Vec::new();
+
+    io::Write::write_all(
+      &mut brotli::CompressorWriter::new(&mut compressed, 4096, 11, 24),
+      b"hello",
+    )
+    .unwrap();
+
+    let copy = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          0,
+          0,
+          Inscription {
+            body: Some(compressed),
+            content_encoding: Some("br".into()),
+            content_type: Some("text/plain".into()),
+            ..default()
+          }
+          .to_witness(),
+        )],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let content_hash = sha256::Hash::hash(b"hello");
+
+    pretty_assert_eq!(
+      server.get_json::<api::ContentHashInscriptions>(format!("/r/content-hash/{content_hash}")),
+      api::ContentHashInscriptions {
+        ids: vec![first, copy],
+        more: false,
+        page: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::ContentHashInscriptions>(format!("/content-hash/{content_hash}")),
+      api::ContentHashInscriptions {
+        ids: vec![first, copy],
+        more: false,
+        page: 0,
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::ContentHashInscriptions>(format!("/r/content-hash/{content_hash}/1")),
+      api::ContentHashInscriptions {
+        ids: Vec::new(),
+        more: false,
+        page: 1,
+      }
+    );
+
+    server.assert_response_regex(
+      format!("/content-hash/{content_hash}"),
+      StatusCode::OK,
+      format!(
+        ".*<h1>Content Hash</h1>.*<a href=/inscription/{first}>.*<a href=/inscription/{copy}>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{copy}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>content hash</dt>
+  <dd><a class=monospace href=/content-hash/{content_hash}>{content_hash}</a></dd>
+  <dt>first inscribed as</dt>
+  <dd><a href=/inscription/{first}>#0</a></dd>.*"
+      ),
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{first}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>content hash</dt>
+  <dd><a class=monospace href=/content-hash/{content_hash}>{content_hash}</a></dd>
+  <dt>timestamp</dt>.*"
+      ),
+    );
+  }
+
+  #[test]
+  fn content_hash_requires_index() {
+    let server = TestServer::new();
+
+    let content_hash = sha256::Hash::hash(b"hello");
+
+    server.assert_response(
+      format!("/r/content-hash/{content_hash}"),
+      StatusCode::NOT_FOUND,
+      "this server has no content hash index",
+    );
+
+    server.assert_response(
+      format!("/content-hash/{content_hash}"),
+      StatusCode::NOT_FOUND,
+      "this server has no content hash index",
+    );
+  }
+
+  #[test]
+  fn inscription_search() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_search()
+      .build();
+
+    server.mine_blocks(1);
+
+    let greeting = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          1,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", "Hello, world!").to_witness(),
+        )],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    let mut metadata = Vec::new();
+
+    ciborium::into_writer(
+      &Value::Map(vec![(
+        Value::Text("greeting".into()),
+        Value::Text("hello".into()),
+      )]),
+      &mut metadata,
+    )
+    .unwrap();
+
+    let token = InscriptionId {
+      txid: server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          2,
+          0,
+          0,
+          Inscription {
+            body: Some(r#"{"p":"brc-20","tick":"ordi"}"#.into()),
+            content_type: Some("application/json".into()),
+            metadata: Some(metadata),
+            metaprotocol: Some("brc-20".into()),
+            ..default()
+          }
+          .to_witness(),
+        )],
+        ..default()
+      }),
+      index: 0,
+    };
+
+    server.mine_blocks(1);
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionSearch>("/inscriptions/search?query=HELLO"),
+      api::InscriptionSearch {
+        ids: vec![token, greeting],
+        more: false,
+        page: 0,
+        terms: vec!["hello".into()],
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionSearch>(
+        "/inscriptions/search?query=hello%20content_type:text/plain"
+      ),
+      api::InscriptionSearch {
+        ids: vec![greeting],
+        more: false,
+        page: 0,
+        terms: vec!["content_type:text/plain".into(), "hello".into()],
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionSearch>(
+        "/inscriptions/search?query=ordi%20metaprotocol:brc-20"
+      ),
+      api::InscriptionSearch {
+        ids: vec![token],
+        more: false,
+        page: 0,
+        terms: vec!["metaprotocol:brc-20".into(), "ordi".into()],
+      }
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionSearch>("/inscriptions/search/1?query=hello"),
+      api::InscriptionSearch {
+        ids: Vec::new(),
+        more: false,
+        page: 1,
+        terms: vec!["hello".into()],
+      }
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/search?query=greeting",
+      StatusCode::OK,
+      format!(".*<title>Inscription Search: greeting</title>.*<a href=/inscription/{token}>.*"),
+    );
+
+    server.assert_response_regex(
+      "/inscriptions/search?query=goodbye",
+      StatusCode::OK,
+      ".*<h3>No inscriptions</h3>.*",
+    );
+
+    server.assert_response(
+      "/inscriptions/search?query=%21",
+      StatusCode::BAD_REQUEST,
+      "search query has no terms",
+    );
+
+    server.assert_redirect(
+      "/search?query=hello+world",
+      "/inscriptions/search?query=hello%20world",
+    );
+
+    server.assert_redirect("/search/sat:abc", "/sat/abc");
+  }
+
+  #[test]
+  fn inscription_search_requires_index() {
+    TestServer::new().assert_response(
+      "/inscriptions/search?query=hello",
+      StatusCode::NOT_FOUND,
+      "this server has no search index",
+    );
+  }
+
+  #[test]
+  fn inscription_history() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_transfer_history()
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let history = api::InscriptionHistory {
+      id,
+      more: false,
+      page: 0,
+      transfers: vec![
+        api::InscriptionTransfer {
+          height: 2,
+          satpoint: SatPoint {
+            outpoint: OutPoint { txid, vout: 0 },
+            offset: 0,
+          },
+          txid,
+        },
+        api::InscriptionTransfer {
+          height: 3,
+          satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid: transfer,
+              vout: 0,
+            },
+            offset: 0,
+          },
+          txid: transfer,
+        },
+      ],
+    };
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/inscription/{id}/history")),
+      history,
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/r/inscription/{id}/history")),
+      history,
+    );
+
+    pretty_assert_eq!(
+      server.get_json::<api::InscriptionHistory>(format!("/r/inscription/{id}/history/1")),
+      api::InscriptionHistory {
+        id,
+        more: false,
+        page: 1,
+        transfers: Vec::new(),
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>transfers</dt>
+  <dd>
+    <ol class=monospace>
+      <li><a href=/block/2>2</a> <a href=/tx/{txid}>{txid}:0:0</a></li>
+      <li><a href=/block/3>3</a> <a href=/tx/{transfer}>{transfer}:0:0</a></li>
+    </ol>
+  </dd>.*"
+      ),
+    );
+
+    server.assert_response(
+      format!("/inscription/{}/history", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {} not found", inscription_id(1)),
+    );
+  }
+
+  #[test]
+  fn inscription_page_shows_latest_transfers() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_transfer_history()
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    let mut transfer = txid;
+
+    for height in 3..103 {
+      transfer = server.core.broadcast_tx(TransactionTemplate {
+        inputs: &[(height - 1, 1, 0, Default::default())],
+        ..default()
+      });
+
+      server.mine_blocks(1);
+    }
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      format!(
+        ".*<dt>transfers</dt>
+  <dd>
+    <ol class=monospace>
+      <li><a href=/block/3>3</a> .*
+      <li><a href=/block/102>102</a> <a href=/tx/{transfer}>{transfer}:0:0</a></li>
+    </ol>
+    <div class=center>
+      <a href=/inscription/{id}/history>all transfers</a>
+    </div>
+  </dd>.*"
+      ),
+    );
+
+    assert!(
+      server
+        .get_json::<api::InscriptionHistory>(format!("/inscription/{id}/history"))
+        .more
+    );
+  }
+
+  #[test]
+  fn inscription_history_requires_index() {
+    let server = TestServer::builder().chain(Chain::Regtest).build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    let id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response(
+      format!("/r/inscription/{id}/history"),
+      StatusCode::NOT_FOUND,
+      "this server has no transfer history index",
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{id}"),
+      StatusCode::OK,
+      "(?s).*<dt>offset</dt>\n  <dd>0</dd>\n  <dt>ethereum teleburn address</dt>.*",
+    );
+  }
+
+  #[test]
+  fn sat_trace() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_spent_sats()
+      .build();
+
+    let coinbase = server.mine_blocks(1)[0].txdata[0].txid();
+
+    let transfer = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let sat = Sat(50 * COIN_VALUE);
+
+    pretty_assert_eq!(
+      server.get_json::<api::SatTrace>(format!("/sat/{sat}/trace")),
+      api::SatTrace {
+        sat,
+        locations: vec![
+          api::SatLocation {
+            height: 1,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(coinbase, 0),
+              offset: 0,
+            },
+          },
+          api::SatLocation {
+            height: 2,
+            satpoint: SatPoint {
+              outpoint: OutPoint::new(transfer, 0),
+              offset: 0,
+            },
+          },
+        ],
+        lost: false,
+      },
+    );
+
+    server.assert_response_regex(
+      format!("/sat/{}/trace", sat.name()),
+      StatusCode::OK,
+      format!(
+        ".*<title>Sat {sat} Trace</title>.*
+<ol class=monospace>
+  <li><a href=/block/1>1</a> <a href=/output/{coinbase}:0>{coinbase}:0:0</a></li>
+  <li><a href=/block/2>2</a> <a href=/output/{transfer}:0>{transfer}:0:0</a></li>
+</ol>.*"
+      ),
+    );
+
+    server.assert_response(
+      format!("/sat/{}/trace", 1000 * COIN_VALUE),
+      StatusCode::NOT_FOUND,
+      &format!(
+        "sat {} not found, only mined sats with indexed locations can be traced, use `ord trace` for others",
+        1000 * COIN_VALUE
+      ),
+    );
+
+    server.assert_response(
+      format!("/sat/{}/trace", sat.n() + 1),
+      StatusCode::NOT_FOUND,
+      &format!(
+        "sat {} not found, only mined sats with indexed locations can be traced, use `ord trace` for others",
+        sat.n() + 1
+      ),
+    );
+  }
+
+  #[test]
+  fn sat_trace_requires_spent_sat_index() {
+    TestServer::builder().index_sats().build().assert_response(
+      "/sat/0/trace",
+      StatusCode::NOT_FOUND,
+      "this server has no spent sat index",
+    );
+  }
+
+  #[test]
+  fn scan_outpoint() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let coinbase = server.mine_blocks(1)[0].txdata[0].txid();
+
+    let scan = server.get_json::<Vec<api::ScanOutput>>(format!("/scan/{coinbase}:0"));
+
+    assert_eq!(scan.len(), 1);
+    assert_eq!(scan[0].output, OutPoint::new(coinbase, 0));
+
+    let sats = scan[0].sats.as_ref().unwrap();
+
+    assert_eq!(sats.len(), 50);
+
+    pretty_assert_eq!(
+      sats[0],
+      api::ScanRange {
+        charms: vec![Charm::Coin, Charm::Uncommon],
+        offset: 0,
+        rarity: Rarity::Uncommon,
+        sat: Sat(50 * COIN_VALUE),
+        size: 1,
+      },
+    );
+
+    pretty_assert_eq!(
+      sats[49],
+      api::ScanRange {
+        charms: vec![Charm::Coin],
+        offset: 49 * COIN_VALUE,
+        rarity: Rarity::Common,
+        sat: Sat(99 * COIN_VALUE),
+        size: 1,
+      },
+    );
+  }
+
+  #[test]
+  fn scan_address() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_addresses()
+      .index_sats()
+      .build();
+
+    server.mine_blocks(1);
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      outputs: 2,
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let scan =
+      server.get_json::<Vec<api::ScanOutput>>("/scan/bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202");
+
+    assert_eq!(
+      scan
+        .iter()
+        .map(|output| (output.output, output.sats.as_ref().unwrap().len()))
+        .collect::<Vec<(OutPoint, usize)>>(),
+      [(OutPoint::new(txid, 0), 25), (OutPoint::new(txid, 1), 25)],
+    );
+  }
+
+  #[test]
+  fn scan_outputs() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    let coinbase = server.mine_blocks(1)[0].txdata[0].txid();
+
+    let response = reqwest::blocking::Client::new()
+      .post(server.join_url("/scan"))
+      .json(&vec![OutPoint::new(coinbase, 0), outpoint(1)])
+      .send()
+      .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let scan = response.json::<Vec<api::ScanOutput>>().unwrap();
+
+    assert_eq!(scan[0].sats.as_ref().unwrap().len(), 50);
+
+    pretty_assert_eq!(
+      scan[1],
+      api::ScanOutput {
+        output: outpoint(1),
+        sats: None,
+      },
+    );
+  }
+
+  #[test]
+  fn scan_errors() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        format!("/scan/{}", OutPoint::null()),
+        StatusCode::NOT_FOUND,
+        "this server has no sat index",
+      );
+
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .index_sats()
+      .build();
+
+    server.assert_response(
+      "/scan/bcrt1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqdku202",
+      StatusCode::NOT_FOUND,
+      "this server has no address index",
+    );
+
+    server.assert_response(
+      "/scan/foo",
+      StatusCode::BAD_REQUEST,
+      "invalid scan target `foo`, expected outpoint or address",
+    );
+  }
+
+  #[test]
+  fn thumbnail() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--thumbnails")
+      .build();
+
+    server.mine_blocks(1);
+
+    let mut png = Vec::new();
+
+    image::DynamicImage::ImageRgba8(image::RgbaImage::new(512, 512))
+      .write_to(
+        &mut std::io::Cursor::new(&mut png),
+        image::ImageOutputFormat::Png,
+      )
+      .unwrap();
+
+    let txid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", png).to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!("/thumbnail/{}", InscriptionId { txid, index: 0 }));
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+      response.headers().get(header::CONTENT_TYPE).unwrap(),
+      "image/png"
+    );
+
+    let thumbnail = image::load_from_memory(&response.bytes().unwrap()).unwrap();
+
+    assert_eq!((thumbnail.width(), thumbnail.height()), (256, 256));
+  }
+
+  #[test]
+  fn thumbnail_of_invalid_content() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--thumbnails")
+      .build();
+
+    server.mine_blocks(2);
+
+    let image = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+      ..default()
+    });
+
+    let text = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let image = InscriptionId {
+      txid: image,
+      index: 0,
+    };
+
+    server.assert_response(
+      format!("/thumbnail/{image}"),
+      StatusCode::BAD_REQUEST,
+      &format!("inscription {image} content is invalid: unrecognized image header"),
+    );
+
+    let text = InscriptionId {
+      txid: text,
+      index: 0,
+    };
+
+    server.assert_response(
+      format!("/thumbnail/{text}"),
+      StatusCode::NOT_FOUND,
+      &format!("inscription {text} thumbnail not found"),
+    );
+  }
+
+  #[test]
+  fn svg_thumbnail() {
+    let server = TestServer::builder()
+      .chain(Chain::Regtest)
+      .server_flag("--thumbnails")
+      .build();
+
+    server.mine_blocks(2);
+
+    let valid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("image/svg+xml", "<svg><rect/></svg>").to_witness(),
+      )],
+      ..default()
+    });
+
+    let invalid = server.core.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        inscription("image/svg+xml", "<html></html>").to_witness(),
+      )],
+      ..default()
+    });
+
+    server.mine_blocks(1);
+
+    let response = server.get(format!(
+      "/thumbnail/{}",
+      InscriptionId {
+        txid: valid,
+        index: 0
+      }
+    ));
+
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    assert!(response.headers().get(header::CACHE_CONTROL).is_none());
+    assert!(response.bytes().unwrap().is_empty());
+
+    let invalid = InscriptionId {
+      txid: invalid,
+      index: 0,
+    };
+
+    server.assert_response(
+      format!("/thumbnail/{invalid}"),
+      StatusCode::BAD_REQUEST,
+      &format!("inscription {invalid} content is invalid: SVG root element is not `svg`"),
+    );
+  }
+
+  #[test]
+  fn thumbnails_disabled() {
+    TestServer::builder()
+      .chain(Chain::Regtest)
+      .build()
+      .assert_response(
+        format!("/thumbnail/{}", inscription_id(1)),
+        StatusCode::NOT_FOUND,
+        "this server does not generate thumbnails",
+      );
+  }
 }
@@ -0,0 +1,465 @@
+use {
+  super::*,
+  crate::index::event::{Event, EventKind},
+  futures::stream::BoxStream,
+  tokio::sync::{broadcast, mpsc},
+  tokio_stream::Stream,
+};
+
+const BUFFER_SIZE: usize = 10_000;
+const LOG_PAGE_SIZE: usize = 1_000;
+const LOG_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Events are identified by their block height and their sequence number
+/// within that block, which matches their key in the event log.
+pub(crate) type EventId = (u32, u32);
+
+fn next_id((height, seq): EventId) -> EventId {
+  match seq.checked_add(1) {
+    Some(seq) => (height, seq),
+    None => (height + 1, 0),
+  }
+}
+
+pub(crate) struct EventStream {
+  index: Option<Arc<Index>>,
+  sender: broadcast::Sender<Event>,
+  state: Mutex<State>,
+}
+
+struct State {
+  buffer: VecDeque<(EventId, Event)>,
+  first_id: EventId,
+  last_id: Option<EventId>,
+}
+
+impl EventStream {
+  /// If `index` has an event log, subscriptions are served from the log,
+  /// otherwise from an in-memory buffer of events relayed since `first_height`.
+  pub(crate) fn new(first_height: u32, index: Option<Arc<Index>>) -> Self {
+    Self {
+      index,
+      sender: broadcast::channel(BUFFER_SIZE).0,
+      state: Mutex::new(State {
+        buffer: VecDeque::new(),
+        first_id: (first_height, 0),
+        last_id: None,
+      }),
+    }
+  }
+
+  pub(crate) async fn relay(self: Arc<Self>, mut receiver: mpsc::Receiver<Event>) {
+    while let Some(event) = receiver.recv().await {
+      self.publish(event);
+    }
+  }
+
+  fn publish(&self, event: Event) {
+    let mut state = self.state.lock().unwrap();
+
+    let id = match state.last_id {
+      Some(last_id) if last_id.0 == event.block_height() => next_id(last_id),
+      _ => (event.block_height(), 0),
+    };
+
+    state.last_id = Some(id);
+
+    if state.buffer.len() == BUFFER_SIZE {
+      let (evicted, _) = state.buffer.pop_front().unwrap();
+      state.first_id = state.first_id.max(next_id(evicted));
+    }
+
+    state.buffer.push_back((id, event.clone()));
+
+    self.sender.send(event).ok();
+  }
+
+  pub(crate) fn subscribe(
+    &self,
+    filter: EventFilter,
+  ) -> ServerResult<impl Stream<Item = (EventId, Event)> + Send + 'static> {
+    let events: BoxStream<'static, (EventId, Event)> = match &self.index {
+      Some(index) => {
+        let start = match filter.start {
+          Some(start) => start,
+          None => (index.block_count()?, 0),
+        };
+
+        Box::pin(Self::tail(index.clone(), start, self.sender.subscribe()))
+      }
+      None => Box::pin(self.subscribe_to_buffer(filter.start)?),
+    };
+
+    Ok(events.filter(move |(_, event)| filter.matches(event)))
+  }
+
+  fn subscribe_to_buffer(
+    &self,
+    start: Option<EventId>,
+  ) -> ServerResult<impl Stream<Item = (EventId, Event)> + Send + 'static> {
+    let state = self.state.lock().unwrap();
+
+    let replay = match start {
+      Some(start) => {
+        if start < state.first_id {
+          return Err(ServerError::BadRequest(format!(
+            "events before {}:{} are not available",
+            state.first_id.0, state.first_id.1,
+          )));
+        }
+
+        state
+          .buffer
+          .iter()
+          .filter(|(id, _)| *id >= start)
+          .cloned()
+          .collect()
+      }
+      None => Vec::new(),
+    };
+
+    let live = futures::stream::unfold(
+      (self.sender.subscribe(), state.last_id),
+      |(mut receiver, last_id)| async move {
+        match receiver.recv().await {
+          Ok(event) => {
+            let id = match last_id {
+              Some(last_id) if last_id.0 == event.block_height() => next_id(last_id),
+              _ => (event.block_height(), 0),
+            };
+            Some(((id, event), (receiver, Some(id))))
+          }
+          Err(broadcast::error::RecvError::Lagged(skipped)) => {
+            log::warn!("closing event stream that lagged by {skipped} events");
+            None
+          }
+          Err(broadcast::error::RecvError::Closed) => None,
+        }
+      },
+    );
+
+    Ok(tokio_stream::iter(replay).chain(live))
+  }
+
+  /// Read events from the event log, starting at `start`. Events are only
+  /// visible in the log once their block has been committed, so relayed
+  /// events are only used as a signal to check the log again.
+  fn tail(
+    index: Arc<Index>,
+    start: EventId,
+    receiver: broadcast::Receiver<Event>,
+  ) -> impl Stream<Item = (EventId, Event)> + Send + 'static {
+    futures::stream::unfold(
+      (index, start, receiver, VecDeque::new()),
+      |(index, mut next, mut receiver, mut pending)| async move {
+        loop {
+          if let Some((id, event)) = pending.pop_front() {
+            return Some(((id, event), (index, next, receiver, pending)));
+          }
+
+          let events = {
+            let index = index.clone();
+            task::spawn_blocking(move || index.get_events_from(next, LOG_PAGE_SIZE))
+              .await
+              .map_err(Error::from)
+              .and_then(|result| result)
+          };
+
+          match events {
+            Ok(events) => {
+              if let Some((id, _)) = events.last() {
+                next = next_id(*id);
+                pending.extend(events);
+                continue;
+              }
+            }
+            Err(err) => {
+              log::error!("closing event stream that failed to read event log: {err}");
+              return None;
+            }
+          }
+
+          if let Ok(Err(broadcast::error::RecvError::Closed)) =
+            tokio::time::timeout(LOG_POLL_INTERVAL, receiver.recv()).await
+          {
+            return None;
+          }
+        }
+      },
+    )
+  }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub(crate) struct EventsQuery {
+  from_height: Option<u32>,
+  inscription: Option<String>,
+  kind: Option<String>,
+  rune: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct EventFilter {
+  start: Option<EventId>,
+  inscriptions: HashSet<InscriptionId>,
+  kinds: HashSet<EventKind>,
+  runes: HashSet<RuneId>,
+}
+
+impl EventFilter {
+  pub(crate) fn new(query: EventsQuery, last_event_id: Option<&HeaderValue>) -> ServerResult<Self> {
+    fn parse_list<T: FromStr + Eq + std::hash::Hash>(
+      list: Option<String>,
+      name: &str,
+    ) -> ServerResult<HashSet<T>>
+    where
+      T::Err: Display,
+    {
+      list
+        .iter()
+        .flat_map(|list| list.split(','))
+        .filter(|item| !item.is_empty())
+        .map(|item| {
+          item
+            .parse()
+            .map_err(|err| ServerError::BadRequest(format!("invalid {name} `{item}`: {err}")))
+        })
+        .collect()
+    }
+
+    // resume strictly after the last event the client received
+    let start = match (query.from_height, last_event_id) {
+      (Some(from_height), _) => Some((from_height, 0)),
+      (None, Some(last_event_id)) => Some(next_id(
+        last_event_id
+          .to_str()
+          .ok()
+          .and_then(|last_event_id| last_event_id.split_once(':'))
+          .and_then(|(height, seq)| Some((height.parse().ok()?, seq.parse().ok()?)))
+          .ok_or_else(|| ServerError::BadRequest("invalid `Last-Event-ID` header".into()))?,
+      )),
+      (None, None) => None,
+    };
+
+    Ok(Self {
+      start,
+      inscriptions: parse_list(query.inscription, "inscription id")?,
+      kinds: parse_list(query.kind, "event kind")?,
+      runes: parse_list(query.rune, "rune id")?,
+    })
+  }
+
+  fn matches(&self, event: &Event) -> bool {
+    if !self.kinds.is_empty() && !self.kinds.contains(&event.kind()) {
+      return false;
+    }
+
+    if self.inscriptions.is_empty() && self.runes.is_empty() {
+      return true;
+    }
+
+    event
+      .rune_id()
+      .map(|rune_id| self.runes.contains(&rune_id))
+      .unwrap_or_default()
+      || event
+        .inscription_ids()
+        .iter()
+        .any(|inscription_id| self.inscriptions.contains(inscription_id))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rune_etched(block_height: u32, block: u64) -> Event {
+    Event::RuneEtched {
+      block_height,
+      rune_id: RuneId { block, tx: 1 },
+      txid: txid(1),
+    }
+  }
+
+  fn filter(query: &str) -> ServerResult<EventFilter> {
+    EventFilter::new(
+      Query::try_from_uri(&format!("/events?{query}").parse().unwrap())
+        .unwrap()
+        .0,
+      None,
+    )
+  }
+
+  #[test]
+  fn filter_parses_comma_separated_lists() {
+    assert_eq!(
+      filter("kind=rune_etched,rune_minted&rune=1:1&from_height=5").unwrap(),
+      EventFilter {
+        start: Some((5, 0)),
+        inscriptions: HashSet::new(),
+        kinds: [EventKind::RuneEtched, EventKind::RuneMinted].into(),
+        runes: [RuneId { block: 1, tx: 1 }].into(),
+      }
+    );
+
+    assert!(matches!(
+      filter("kind=foo"),
+      Err(ServerError::BadRequest(_))
+    ));
+    assert!(matches!(
+      filter("rune=foo"),
+      Err(ServerError::BadRequest(_))
+    ));
+    assert!(matches!(
+      filter("inscription=foo"),
+      Err(ServerError::BadRequest(_))
+    ));
+  }
+
+  #[test]
+  fn filter_resumes_after_last_event_id() {
+    assert_eq!(
+      EventFilter::new(
+        EventsQuery::default(),
+        Some(&HeaderValue::from_static("7:2"))
+      )
+      .unwrap()
+      .start,
+      Some((7, 3)),
+    );
+
+    assert_eq!(
+      EventFilter::new(
+        EventsQuery::default(),
+        Some(&HeaderValue::from_static("7:4294967295"))
+      )
+      .unwrap()
+      .start,
+      Some((8, 0)),
+    );
+
+    for last_event_id in ["x", "7", "7:x"] {
+      assert!(matches!(
+        EventFilter::new(
+          EventsQuery::default(),
+          Some(&HeaderValue::from_static(last_event_id))
+        ),
+        Err(ServerError::BadRequest(_))
+      ));
+    }
+  }
+
+  #[test]
+  fn filter_matches_ids() {
+    let parent = inscription_id(2);
+    let inscription_id = inscription_id(1);
+
+    let created = Event::InscriptionCreated {
+      block_height: 1,
+      charms: 0,
+      inscription_id,
+      location: None,
+      parent_inscription_ids: vec![parent],
+      sequence_number: 0,
+    };
+
+    assert!(filter("").unwrap().matches(&created));
+    assert!(filter(&format!("inscription={inscription_id}"))
+      .unwrap()
+      .matches(&created));
+    assert!(filter(&format!("inscription={parent}"))
+      .unwrap()
+      .matches(&created));
+    assert!(!filter("rune=1:1").unwrap().matches(&created));
+    assert!(!filter("kind=rune_etched").unwrap().matches(&created));
+
+    assert!(filter("rune=1:1").unwrap().matches(&rune_etched(1, 1)));
+    assert!(!filter("rune=2:1").unwrap().matches(&rune_etched(1, 1)));
+  }
+
+  #[tokio::test]
+  async fn subscribe_replays_buffered_events() {
+    let stream = EventStream::new(0, None);
+
+    stream.publish(rune_etched(1, 1));
+    stream.publish(rune_etched(2, 2));
+
+    let events = stream
+      .subscribe(filter("from_height=2").unwrap())
+      .unwrap()
+      .take(1)
+      .collect::<Vec<(EventId, Event)>>()
+      .await;
+
+    assert_eq!(events, [((2, 0), rune_etched(2, 2))]);
+  }
+
+  #[tokio::test]
+  async fn subscribe_resumes_after_last_event_id() {
+    let stream = EventStream::new(0, None);
+
+    stream.publish(rune_etched(1, 1));
+    stream.publish(rune_etched(1, 2));
+    stream.publish(rune_etched(2, 3));
+
+    let events = stream
+      .subscribe(
+        EventFilter::new(
+          EventsQuery::default(),
+          Some(&HeaderValue::from_static("1:0")),
+        )
+        .unwrap(),
+      )
+      .unwrap()
+      .take(2)
+      .collect::<Vec<(EventId, Event)>>()
+      .await;
+
+    assert_eq!(
+      events,
+      [((1, 1), rune_etched(1, 2)), ((2, 0), rune_etched(2, 3))]
+    );
+  }
+
+  #[tokio::test]
+  async fn subscribe_receives_live_events() {
+    let stream = EventStream::new(0, None);
+
+    stream.publish(rune_etched(1, 1));
+
+    let subscription = stream.subscribe(filter("rune=2:1").unwrap()).unwrap();
+
+    stream.publish(rune_etched(1, 1));
+    stream.publish(rune_etched(1, 2));
+
+    assert_eq!(
+      subscription
+        .take(1)
+        .collect::<Vec<(EventId, Event)>>()
+        .await,
+      [((1, 2), rune_etched(1, 2))]
+    );
+  }
+
+  #[test]
+  fn subscribe_before_first_height_is_an_error() {
+    let stream = EventStream::new(10, None);
+
+    assert!(stream.subscribe(filter("from_height=10").unwrap()).is_ok());
+    assert!(matches!(
+      stream.subscribe(filter("from_height=9").unwrap()),
+      Err(ServerError::BadRequest(_))
+    ));
+  }
+
+  #[test]
+  fn evicting_events_advances_first_id() {
+    let stream = EventStream::new(0, None);
+
+    for i in 0..=BUFFER_SIZE {
+      stream.publish(rune_etched(u32::try_from(i / 2).unwrap(), 1));
+    }
+
+    assert_eq!(stream.state.lock().unwrap().first_id, (0, 1));
+  }
+}
@@ -0,0 +1,481 @@
+use {
+  super::*,
+  image::{
+    imageops::FilterType,
+    io::{Limits, Reader},
+    DynamicImage, ImageFormat, ImageOutputFormat,
+  },
+  quick_xml::events::Event,
+  std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    io::Cursor,
+    sync::Mutex,
+  },
+};
+
+const CACHE_CAPACITY: usize = 512;
+const MAX_ALLOCATION: u64 = 1 << 28;
+const MAX_DIMENSION: u32 = 8192;
+const MAX_SVG_SIZE: usize = 1 << 20;
+const SIZE: u32 = 256;
+
+/// The result of analyzing an inscription's content: either a PNG thumbnail,
+/// `None` if the content is valid but the server cannot render it, or the
+/// reason the content was rejected.
+pub(super) type Thumbnail = Arc<Result<Option<Vec<u8>>, String>>;
+
+/// Thumbnails of recently requested inscriptions. Inscription content never
+/// changes, so both thumbnails and rejections are cached, and the oldest entry
+/// is evicted once the cache is full.
+#[derive(Default)]
+pub(crate) struct Thumbnails {
+  cache: Mutex<(HashMap<InscriptionId, Thumbnail>, VecDeque<InscriptionId>)>,
+}
+
+impl Thumbnails {
+  pub(super) fn get(&self, inscription_id: InscriptionId, inscription: &Inscription) -> Thumbnail {
+    if let Some(thumbnail) = self.cache.lock().unwrap().0.get(&inscription_id) {
+      return thumbnail.clone();
+    }
+
+    let thumbnail = Arc::new(generate(inscription));
+
+    let (entries, order) = &mut *self.cache.lock().unwrap();
+
+    if entries.insert(inscription_id, thumbnail.clone()).is_none() {
+      order.push_back(inscription_id);
+    }
+
+    while order.len() > CACHE_CAPACITY {
+      if let Some(evicted) = order.pop_front() {
+        entries.remove(&evicted);
+      }
+    }
+
+    thumbnail
+  }
+}
+
+/// Whether the inscription's content is analyzed, which is the case for
+/// images, including SVGs, PDFs, and models.
+pub(super) fn supported(inscription: &Inscription) -> bool {
+  match inscription.media() {
+    Media::Image(_) | Media::Model | Media::Pdf => true,
+    Media::Iframe => content_type(inscription) == "image/svg+xml",
+    _ => false,
+  }
+}
+
+fn content_type(inscription: &Inscription) -> &str {
+  inscription
+    .content_type()
+    .unwrap_or_default()
+    .split(';')
+    .next()
+    .unwrap_or_default()
+    .trim()
+}
+
+/// Validate the inscription's content, and if it is a raster image, return a
+/// PNG thumbnail of it. SVGs, PDFs, and models cannot be rendered by the
+/// server, so they are only validated.
+pub(super) fn generate(inscription: &Inscription) -> Result<Option<Vec<u8>>, String> {
+  let body = decode(inscription)?;
+
+  let content_type = content_type(inscription);
+
+  let image = match inscription.media() {
+    Media::Image(rendering) => image(content_type, &body, rendering)?,
+    Media::Model => {
+      model(content_type, &body)?;
+      return Ok(None);
+    }
+    Media::Pdf => {
+      if !body.starts_with(b"%PDF-") {
+        return Err("invalid PDF header".into());
+      }
+      return Ok(None);
+    }
+    Media::Iframe if content_type == "image/svg+xml" => {
+      svg(&body)?;
+      return Ok(None);
+    }
+    _ => return Err(format!("no thumbnail for content type `{content_type}`")),
+  };
+
+  let mut png = Vec::new();
+
+  image
+    .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+    .map_err(|err| format!("failed to encode thumbnail: {err}"))?;
+
+  Ok(Some(png))
+}
+
+/// Strip brotli content encoding, refusing bodies which exceed the
+/// inscription's decoded size limit.
+fn decode(inscription: &Inscription) -> Result<Cow<'_, [u8]>, String> {
+  inscription.body().ok_or("inscription has no content")?;
+
+  if let Some(encoding) = inscription.content_encoding.as_deref() {
+    if encoding != b"br" {
+      return Err(format!(
+        "unsupported content encoding `{}`",
+        String::from_utf8_lossy(encoding)
+      ));
+    }
+  }
+
+  let limit = inscription.decoded_size_limit();
+
+  inscription.decoded_body(limit).ok_or_else(|| {
+    if inscription.content_encoding.is_some() {
+      format!(
+        "content decompresses to more than {limit} bytes, exceeding the maximum ratio of {} \
+        or size of {}",
+        Inscription::MAX_DECOMPRESSION_RATIO,
+        Inscription::MAX_DECODED_SIZE,
+      )
+    } else {
+      format!(
+        "content is larger than {} bytes",
+        Inscription::MAX_DECODED_SIZE
+      )
+    }
+  })
+}
+
+fn image(
+  content_type: &str,
+  body: &[u8],
+  rendering: ImageRendering,
+) -> Result<DynamicImage, String> {
+  let expected = match content_type {
+    "image/apng" | "image/png" => ImageFormat::Png,
+    "image/gif" => ImageFormat::Gif,
+    "image/jpeg" => ImageFormat::Jpeg,
+    "image/webp" => ImageFormat::WebP,
+    _ => return Err(format!("unsupported image type `{content_type}`")),
+  };
+
+  let format = image::guess_format(body).map_err(|_| "unrecognized image header".to_string())?;
+
+  if format != expected {
+    return Err(format!(
+      "image header is {format:?}, but content type is `{content_type}`"
+    ));
+  }
+
+  let mut limits = Limits::default();
+  limits.max_alloc = Some(MAX_ALLOCATION);
+  limits.max_image_height = Some(MAX_DIMENSION);
+  limits.max_image_width = Some(MAX_DIMENSION);
+
+  let mut reader = Reader::with_format(Cursor::new(body), format);
+  reader.limits(limits);
+
+  let image = reader
+    .decode()
+    .map_err(|err| format!("invalid image: {err}"))?;
+
+  if image.width() <= SIZE && image.height() <= SIZE {
+    return Ok(image);
+  }
+
+  Ok(image.resize(
+    SIZE,
+    SIZE,
+    match rendering {
+      ImageRendering::Auto => FilterType::Triangle,
+      ImageRendering::Pixelated => FilterType::Nearest,
+    },
+  ))
+}
+
+fn model(content_type: &str, body: &[u8]) -> Result<(), String> {
+  match content_type {
+    "model/gltf-binary" => {
+      if body.len() < 12 || !body.starts_with(b"glTF") || body[4..8] != 2u32.to_le_bytes() {
+        return Err("invalid glTF binary header".into());
+      }
+    }
+    _ => {
+      let gltf = serde_json::from_slice::<serde_json::Value>(body)
+        .map_err(|err| format!("invalid glTF JSON: {err}"))?;
+
+      if gltf.get("asset").is_none() {
+        return Err("glTF JSON has no `asset` property".into());
+      }
+    }
+  }
+
+  Ok(())
+}
+
+/// SVGs must be well-formed XML with an `svg` root element, and are capped in
+/// size, since browsers render them whenever they are displayed.
+fn svg(body: &[u8]) -> Result<(), String> {
+  if body.len() > MAX_SVG_SIZE {
+    return Err(format!("SVG is larger than {MAX_SVG_SIZE} bytes"));
+  }
+
+  let mut reader = quick_xml::Reader::from_reader(body);
+  reader.check_end_names(true);
+
+  let mut root = None;
+  let mut depth = 0usize;
+
+  loop {
+    match reader
+      .read_event()
+      .map_err(|err| format!("invalid SVG: {err}"))?
+    {
+      Event::Start(element) => {
+        if depth == 0 {
+          root.get_or_insert(element.local_name().as_ref().to_vec());
+        }
+        depth += 1;
+      }
+      Event::Empty(element) if depth == 0 => {
+        root.get_or_insert(element.local_name().as_ref().to_vec());
+      }
+      Event::End(_) => depth = depth.saturating_sub(1),
+      Event::Eof => break,
+      _ => {}
+    }
+  }
+
+  if depth > 0 {
+    return Err("invalid SVG: unclosed element".into());
+  }
+
+  if root.as_deref() != Some(b"svg".as_slice()) {
+    return Err("SVG root element is not `svg`".into());
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, image::RgbaImage};
+
+  fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut png = Vec::new();
+    DynamicImage::ImageRgba8(RgbaImage::new(width, height))
+      .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+      .unwrap();
+    png
+  }
+
+  fn dimensions(thumbnail: &Option<Vec<u8>>) -> (u32, u32) {
+    let image =
+      image::load_from_memory_with_format(thumbnail.as_ref().unwrap(), ImageFormat::Png).unwrap();
+    (image.width(), image.height())
+  }
+
+  #[test]
+  fn small_images_are_not_resized() {
+    assert_eq!(
+      dimensions(&generate(&inscription("image/png", png(10, 20))).unwrap()),
+      (10, 20),
+    );
+  }
+
+  #[test]
+  fn large_images_are_resized_preserving_aspect_ratio() {
+    assert_eq!(
+      dimensions(&generate(&inscription("image/png", png(1024, 512))).unwrap()),
+      (256, 128),
+    );
+  }
+
+  #[test]
+  fn mismatched_image_header_is_rejected() {
+    assert_eq!(
+      generate(&inscription("image/jpeg", png(1, 1))).unwrap_err(),
+      "image header is Png, but content type is `image/jpeg`",
+    );
+
+    assert_eq!(
+      generate(&inscription("image/png", "hello")).unwrap_err(),
+      "unrecognized image header",
+    );
+  }
+
+  #[test]
+  fn corrupt_image_is_rejected() {
+    let mut png = png(16, 16);
+    png.truncate(40);
+
+    assert!(generate(&inscription("image/png", png))
+      .unwrap_err()
+      .starts_with("invalid image: "));
+  }
+
+  #[test]
+  fn oversized_image_is_rejected() {
+    assert!(
+      generate(&inscription("image/png", png(MAX_DIMENSION + 1, 1)))
+        .unwrap_err()
+        .starts_with("invalid image: ")
+    );
+  }
+
+  #[test]
+  fn decompression_ratio_is_capped() {
+    let body = vec![0; 1 << 16];
+
+    let mut compressed = Vec::new();
+    brotli::BrotliCompress(
+      &mut body.as_slice(),
+      &mut compressed,
+      &brotli::enc::BrotliEncoderParams::default(),
+    )
+    .unwrap();
+
+    assert!(compressed.len() * Inscription::MAX_DECOMPRESSION_RATIO < body.len());
+
+    assert!(generate(&Inscription {
+      content_encoding: Some("br".into()),
+      ..inscription("image/png", compressed)
+    })
+    .unwrap_err()
+    .starts_with("content decompresses to more than"));
+  }
+
+  #[test]
+  fn compressed_images_are_decoded() {
+    let png = png(300, 300);
+
+    let mut compressed = Vec::new();
+    brotli::BrotliCompress(
+      &mut png.as_slice(),
+      &mut compressed,
+      &brotli::enc::BrotliEncoderParams::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+      dimensions(
+        &generate(&Inscription {
+          content_encoding: Some("br".into()),
+          ..inscription("image/png", compressed)
+        })
+        .unwrap()
+      ),
+      (256, 256),
+    );
+  }
+
+  #[test]
+  fn pdfs_and_models_are_validated_but_not_rendered() {
+    assert_eq!(
+      generate(&inscription("application/pdf", "%PDF-1.7")),
+      Ok(None)
+    );
+
+    assert_eq!(
+      generate(&inscription("application/pdf", "hello")).unwrap_err(),
+      "invalid PDF header",
+    );
+
+    assert_eq!(
+      generate(&inscription(
+        "model/gltf-binary",
+        [b"glTF".as_slice(), &2u32.to_le_bytes(), &[0; 4]].concat()
+      )),
+      Ok(None)
+    );
+
+    assert_eq!(
+      generate(&inscription("model/gltf-binary", "glTF")).unwrap_err(),
+      "invalid glTF binary header",
+    );
+
+    assert_eq!(
+      generate(&inscription(
+        "model/gltf+json",
+        r#"{"asset":{"version":"2.0"}}"#
+      )),
+      Ok(None)
+    );
+
+    assert_eq!(
+      generate(&inscription("model/gltf+json", "{}")).unwrap_err(),
+      "glTF JSON has no `asset` property",
+    );
+  }
+
+  #[test]
+  fn svgs_are_validated() {
+    assert_eq!(
+      generate(&inscription(
+        "image/svg+xml",
+        r#"<?xml version="1.0"?><svg xmlns="http://www.w3.org/2000/svg"><rect/></svg>"#
+      )),
+      Ok(None),
+    );
+
+    assert_eq!(
+      generate(&inscription("image/svg+xml", "<html></html>")).unwrap_err(),
+      "SVG root element is not `svg`",
+    );
+
+    assert_eq!(
+      generate(&inscription("image/svg+xml", "<svg><g></svg>"))
+        .unwrap_err()
+        .split(':')
+        .next()
+        .unwrap(),
+      "invalid SVG",
+    );
+
+    assert_eq!(
+      generate(&inscription("image/svg+xml", "<svg><g>")).unwrap_err(),
+      "invalid SVG: unclosed element",
+    );
+
+    assert_eq!(
+      generate(&inscription(
+        "image/svg+xml",
+        format!("<svg>{}</svg>", " ".repeat(MAX_SVG_SIZE))
+      ))
+      .unwrap_err(),
+      format!("SVG is larger than {MAX_SVG_SIZE} bytes"),
+    );
+  }
+
+  #[test]
+  fn other_media_has_no_thumbnail() {
+    assert_eq!(
+      generate(&inscription("text/plain", "hello")).unwrap_err(),
+      "no thumbnail for content type `text/plain`",
+    );
+  }
+
+  #[test]
+  fn cache_is_bounded() {
+    let thumbnails = Thumbnails::default();
+
+    for i in 0..CACHE_CAPACITY + 10 {
+      thumbnails.get(
+        InscriptionId {
+          txid: txid(1),
+          index: i.try_into().unwrap(),
+        },
+        &inscription("text/plain", "hello"),
+      );
+    }
+
+    let (entries, order) = &*thumbnails.cache.lock().unwrap();
+
+    assert_eq!(entries.len(), CACHE_CAPACITY);
+    assert_eq!(order.len(), CACHE_CAPACITY);
+    assert!(!entries.contains_key(&InscriptionId {
+      txid: txid(1),
+      index: 0,
+    }));
+  }
+}
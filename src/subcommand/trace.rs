@@ -0,0 +1,24 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Trace {
+  #[arg(help = "List every output that has held <SAT>, oldest first.")]
+  sat: Sat,
+}
+
+impl Trace {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    if !index.has_spent_sat_index() {
+      bail!("trace requires index created with `--index-spent-sats` flag");
+    }
+
+    index.update()?;
+
+    match index.trace(self.sat, true)? {
+      Some(trace) => Ok(Some(Box::new(trace))),
+      None => Err(anyhow!("sat has not been mined as of index height")),
+    }
+  }
+}
@@ -0,0 +1,27 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct VerifyCollection {
+  #[arg(help = "Verify children of <PARENT> against its collection manifest.")]
+  parent: InscriptionId,
+}
+
+impl VerifyCollection {
+  pub(crate) fn run(self, settings: Settings) -> SubcommandResult {
+    let index = Index::open(&settings)?;
+
+    index.update()?;
+
+    ensure!(
+      index.get_inscription_entry(self.parent)?.is_some(),
+      "inscription {} not found",
+      self.parent
+    );
+
+    let verification = index
+      .verify_collection(self.parent)?
+      .ok_or_else(|| anyhow!("inscription {} has no collection manifest", self.parent))?;
+
+    Ok(Some(Box::new(verification)))
+  }
+}
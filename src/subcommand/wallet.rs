@@ -1,19 +1,28 @@
 use {
   super::*,
-  crate::wallet::{batch, wallet_constructor::WalletConstructor, Wallet},
+  crate::{
+    outgoing::Outgoing,
+    wallet::{batch, wallet_constructor::WalletConstructor, Wallet},
+  },
   bitcoincore_rpc::bitcoincore_rpc_json::ListDescriptorsResult,
   shared_args::SharedArgs,
 };
 
 pub mod balance;
 mod batch_command;
+pub mod bump_fee;
+pub mod burn;
+pub mod cancel;
 pub mod cardinals;
+pub mod consolidate;
 pub mod create;
 pub mod dump;
+pub mod finalize_psbt;
 pub mod inscribe;
 pub mod inscriptions;
 mod label;
 pub mod mint;
+pub mod offer;
 pub mod outputs;
 pub mod receive;
 pub mod restore;
@@ -21,6 +30,8 @@ pub mod resume;
 pub mod sats;
 pub mod send;
 mod shared_args;
+pub mod sign_psbt;
+pub mod split;
 pub mod transactions;
 
 #[derive(Debug, Parser)]
@@ -45,12 +56,22 @@ pub(crate) enum Subcommand {
   Balance,
   #[command(about = "Create inscriptions and runes")]
   Batch(batch_command::Batch),
+  #[command(about = "Bump fee of unconfirmed transaction")]
+  BumpFee(bump_fee::BumpFee),
+  #[command(about = "Burn inscription or runes")]
+  Burn(burn::Burn),
+  #[command(about = "Sweep inscription or etching commit outputs back to wallet")]
+  Cancel(cancel::Cancel),
   #[command(about = "List unspent cardinal outputs in wallet")]
   Cardinals,
+  #[command(about = "Merge cardinal outputs into a single output")]
+  Consolidate(consolidate::Consolidate),
   #[command(about = "Create new wallet")]
   Create(create::Create),
   #[command(about = "Dump wallet descriptors")]
   Dump,
+  #[command(about = "Finalize and broadcast signed PSBTs")]
+  FinalizePsbt(finalize_psbt::FinalizePsbt),
   #[command(about = "Create inscription")]
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
@@ -59,6 +80,8 @@ pub(crate) enum Subcommand {
   Label,
   #[command(about = "Mint a rune")]
   Mint(mint::Mint),
+  #[command(subcommand, about = "Create and accept offers")]
+  Offer(offer::OfferSubcommand),
   #[command(about = "List all unspent outputs in wallet")]
   Outputs,
   #[command(about = "Generate receive address")]
@@ -71,6 +94,10 @@ pub(crate) enum Subcommand {
   Sats(sats::Sats),
   #[command(about = "Send sat or inscription")]
   Send(send::Send),
+  #[command(about = "Sign PSBT with wallet keys")]
+  SignPsbt(sign_psbt::SignPsbt),
+  #[command(about = "Carve sats into their own outputs")]
+  Split(split::Split),
   #[command(about = "See wallet transactions")]
   Transactions(transactions::Transactions),
 }
@@ -97,22 +124,50 @@ impl WalletCommand {
         .context("invalid server URL")?,
     )?;
 
+    let bitcoind_only = match self.subcommand {
+      Subcommand::FinalizePsbt(_) => Some("finalize-psbt"),
+      Subcommand::Resume => Some("resume"),
+      Subcommand::SignPsbt(_) => Some("sign-psbt"),
+      Subcommand::Transactions(_) => Some("transactions"),
+      _ => None,
+    };
+
+    if let Some(subcommand) = bitcoind_only {
+      wallet.check_native_support(&format!("`ord wallet {subcommand}`"))?;
+    }
+
     match self.subcommand {
       Subcommand::Balance => balance::run(wallet),
       Subcommand::Batch(batch) => batch.run(wallet),
+      Subcommand::BumpFee(bump_fee) => bump_fee.run(wallet),
+      Subcommand::Burn(burn) => burn.run(wallet),
+      Subcommand::Cancel(cancel) => cancel.run(wallet),
       Subcommand::Cardinals => cardinals::run(wallet),
+      Subcommand::Consolidate(consolidate) => consolidate.run(wallet),
       Subcommand::Create(_) | Subcommand::Restore(_) => unreachable!(),
       Subcommand::Dump => dump::run(wallet),
+      Subcommand::FinalizePsbt(finalize_psbt) => finalize_psbt.run(wallet),
       Subcommand::Inscribe(inscribe) => inscribe.run(wallet),
       Subcommand::Inscriptions => inscriptions::run(wallet),
       Subcommand::Label => label::run(wallet),
       Subcommand::Mint(mint) => mint.run(wallet),
+      Subcommand::Offer(offer) => offer.run(wallet),
       Subcommand::Outputs => outputs::run(wallet),
       Subcommand::Receive(receive) => receive.run(wallet),
       Subcommand::Resume => resume::run(wallet),
       Subcommand::Sats(sats) => sats.run(wallet),
       Subcommand::Send(send) => send.run(wallet),
+      Subcommand::SignPsbt(sign_psbt) => sign_psbt.run(wallet),
+      Subcommand::Split(split) => split.run(wallet),
       Subcommand::Transactions(transactions) => transactions.run(wallet),
     }
   }
 }
+
+/// Parse runes in the `<DECIMAL:RUNE>` form accepted by `ord wallet send`.
+fn parse_rune(s: &str) -> Result<(Decimal, SpacedRune)> {
+  match s.parse::<Outgoing>()? {
+    Outgoing::Rune { decimal, rune } => Ok((decimal, rune)),
+    _ => bail!("expected runes of the form <DECIMAL:RUNE>: {s}"),
+  }
+}
@@ -34,8 +34,8 @@ impl Batch {
         .map(|(satpoint, txout)| (satpoint.outpoint, txout.clone())),
     );
 
-    if let Some(etching) = batchfile.etching {
-      Self::check_etching(&wallet, &etching)?;
+    if let Some(etching) = &batchfile.etching {
+      Self::check_etching(&wallet, etching)?;
     }
 
     batch::Plan {
@@ -49,9 +49,11 @@ impl Batch {
       no_limit: self.shared.no_limit,
       parent_info,
       postages,
+      psbt: self.shared.psbt,
       reinscribe: batchfile.reinscribe,
       reveal_fee_rate: self.shared.fee_rate,
       reveal_satpoints,
+      satisfaction_weight: wallet.satisfaction_weight(),
       satpoint: if let Some(sat) = batchfile.sat {
         Some(wallet.find_sat_in_outputs(sat)?)
       } else {
@@ -116,6 +118,35 @@ impl Batch {
 
     ensure!(supply > 0, "`supply` must be greater than zero");
 
+    let mut allocated = 0u128;
+
+    for allocation in &etching.allocations {
+      let address = allocation
+        .address
+        .clone()
+        .require_network(wallet.chain().network())?;
+
+      let amount = allocation.amount.to_integer(etching.divisibility)?;
+
+      ensure!(
+        amount > 0,
+        "premine allocation to {address} must be greater than zero",
+      );
+
+      let dust = address.script_pubkey().dust_value().to_sat();
+
+      ensure!(
+        allocation.postage.unwrap_or(TARGET_POSTAGE.to_sat()) >= dust,
+        "premine allocation to {address} has postage below dust limit of {dust}sat",
+      );
+
+      allocated = allocated
+        .checked_add(amount)
+        .ok_or_else(|| anyhow!("premine allocations over maximum"))?;
+    }
+
+    ensure!(allocated <= premine, "premine allocations exceed `premine`");
+
     let bitcoin_client = wallet.bitcoin_client();
 
     let current_height = u32::try_from(bitcoin_client.get_block_count()?).unwrap();
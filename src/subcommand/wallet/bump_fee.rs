@@ -0,0 +1,566 @@
+use {
+  super::*,
+  crate::wallet::entry::EtchingEntry,
+  bitcoin::{
+    key::{KeyPair, XOnlyPublicKey},
+    secp256k1::{Message, Scalar, Secp256k1, SecretKey},
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    taproot::{ControlBlock, LeafVersion, Signature, TapLeafHash, TapNodeHash, TapTweakHash},
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct BumpFee {
+  #[arg(
+    long,
+    help = "Bump fee by spending one of <TXID>'s outputs in a child transaction, instead of replacing it."
+  )]
+  cpfp: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(help = "Bump fee of unconfirmed transaction <TXID>.")]
+  txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub descendants: Vec<Txid>,
+  pub fee: u64,
+  pub original: Txid,
+  pub txid: Txid,
+}
+
+impl BumpFee {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    wallet.check_can_sign(false)?;
+
+    let bitcoin_client = wallet.bitcoin_client();
+
+    let info = bitcoin_client
+      .get_raw_transaction_info(&self.txid, None)
+      .with_context(|| format!("transaction {} not found", self.txid))?;
+
+    ensure!(
+      info.confirmations.is_none(),
+      "transaction {} is already confirmed",
+      self.txid
+    );
+
+    let transaction = bitcoin_client.get_raw_transaction(&self.txid, None)?;
+
+    let descendants = bitcoin_client
+      .call::<Vec<Txid>>("getmempooldescendants", &[self.txid.to_string().into()])?
+      .into_iter()
+      .map(|txid| Ok(bitcoin_client.get_raw_transaction(&txid, None)?))
+      .collect::<Result<Vec<Transaction>>>()?;
+
+    let prevouts = wallet.get_prevouts(&transaction)?;
+
+    let fee = Wallet::fee(&transaction, &prevouts);
+
+    ensure!(
+      self.fee_rate.fee(transaction.vsize()).to_sat() > fee,
+      "fee rate of {} sats/vB does not exceed transaction {}'s current fee rate of {:.2} sats/vB",
+      self.fee_rate.n(),
+      self.txid,
+      fee as f64 / transaction.vsize() as f64,
+    );
+
+    if self.cpfp {
+      self.child(&wallet, transaction, descendants)
+    } else {
+      self.replace(&wallet, transaction, descendants, prevouts, fee)
+    }
+  }
+
+  /// Replace the transaction with one paying a higher fee. Its inputs and
+  /// outputs are kept as they are, so that inscriptions, runestones, and sat
+  /// offsets are unchanged, and cardinal inputs and a change output are
+  /// appended to pay the difference. Descendants, which are evicted by the
+  /// replacement, are re-signed and rebroadcast.
+  fn replace(
+    &self,
+    wallet: &Wallet,
+    transaction: Transaction,
+    descendants: Vec<Transaction>,
+    mut prevouts: BTreeMap<OutPoint, TxOut>,
+    fee: u64,
+  ) -> SubcommandResult {
+    if let Some(Artifact::Runestone(runestone)) = Runestone::decipher(&transaction) {
+      ensure!(
+        runestone
+          .edicts
+          .iter()
+          .all(|edict| usize::try_from(edict.output).unwrap() != transaction.output.len()),
+        "cannot replace transaction {} whose runestone splits runes between all outputs, use `--cpfp`",
+        self.txid,
+      );
+    }
+
+    for descendant in &descendants {
+      ensure!(
+        descendant
+          .input
+          .iter()
+          .all(|txin| txin.previous_output.txid == self.txid
+            || !descendants
+              .iter()
+              .any(|tx| tx.txid() == txin.previous_output.txid)),
+        "cannot replace transaction {} with descendants more than one transaction deep, use `--cpfp`",
+        self.txid,
+      );
+    }
+
+    let evicted_fees = fee
+      + descendants
+        .iter()
+        .map(|descendant| wallet.get_fee(descendant))
+        .sum::<Result<u64>>()?;
+
+    let etchings = wallet
+      .pending_etchings()?
+      .into_iter()
+      .filter(|(_rune, entry)| entry.commit.txid() == self.txid)
+      .collect::<Vec<(Rune, EtchingEntry)>>();
+
+    let recovery_keys = if std::iter::once(&transaction)
+      .chain(&descendants)
+      .chain(etchings.iter().map(|(_rune, entry)| &entry.reveal))
+      .flat_map(|tx| &tx.input)
+      .any(|txin| Self::script_path(&txin.witness).is_some())
+    {
+      wallet.recovery_keys()?
+    } else {
+      Vec::new()
+    };
+
+    let excluded = self.excluded(&transaction, &descendants);
+
+    let mut replacement = transaction.clone();
+
+    let fee = wallet.fund_transaction(&mut replacement, &mut prevouts, &excluded, None, |tx| {
+      let vsize = Self::estimate_vsize(wallet, tx);
+      self
+        .fee_rate
+        .fee(vsize)
+        .to_sat()
+        .max(evicted_fees + u64::try_from(vsize).unwrap())
+    })?;
+
+    let replacement = Self::sign(wallet, replacement, &prevouts, &recovery_keys)?;
+
+    let replacement_txid = replacement.txid();
+
+    let rebuild = |child: &Transaction| -> Result<Transaction> {
+      let mut child = child.clone();
+
+      for txin in &mut child.input {
+        if txin.previous_output.txid == self.txid {
+          txin.previous_output.txid = replacement_txid;
+        }
+      }
+
+      let mut prevouts = BTreeMap::new();
+
+      for txin in &child.input {
+        let outpoint = txin.previous_output;
+        if outpoint.txid == replacement_txid {
+          prevouts.insert(
+            outpoint,
+            replacement.output[usize::try_from(outpoint.vout).unwrap()].clone(),
+          );
+        } else {
+          prevouts.insert(outpoint, wallet.get_prevout(outpoint)?);
+        }
+      }
+
+      Self::sign(wallet, child, &prevouts, &recovery_keys)
+    };
+
+    let descendants = descendants
+      .iter()
+      .map(rebuild)
+      .collect::<Result<Vec<Transaction>>>()?;
+
+    let etchings = etchings
+      .into_iter()
+      .map(|(rune, entry)| Ok((rune, rebuild(&entry.reveal)?, entry)))
+      .collect::<Result<Vec<(Rune, Transaction, EtchingEntry)>>>()?;
+
+    wallet.broadcast(&replacement)?;
+
+    for descendant in &descendants {
+      wallet.broadcast(descendant)?;
+    }
+
+    for (rune, reveal, entry) in etchings {
+      wallet.save_etching(
+        &rune,
+        &replacement,
+        &reveal,
+        Self::update_batch_output(entry.output, replacement_txid, reveal.txid()),
+      )?;
+    }
+
+    Ok(Some(Box::new(Output {
+      descendants: descendants.iter().map(Transaction::txid).collect(),
+      fee,
+      original: self.txid,
+      txid: replacement_txid,
+    })))
+  }
+
+  /// Spend one of the transaction's wallet outputs in a child paying enough
+  /// to bring the fee rate of the child, the transaction, and the
+  /// transaction's unconfirmed ancestors to the target. The spent output's
+  /// value is sent back to its own script, keeping any inscriptions and runes
+  /// in it, and the fee is paid with cardinal inputs.
+  fn child(
+    &self,
+    wallet: &Wallet,
+    transaction: Transaction,
+    descendants: Vec<Transaction>,
+  ) -> SubcommandResult {
+    let spent = descendants
+      .iter()
+      .flat_map(|tx| tx.input.iter().map(|txin| txin.previous_output))
+      .collect::<BTreeSet<OutPoint>>();
+
+    let mut parent = None;
+
+    for (vout, output) in transaction.output.iter().enumerate().rev() {
+      let outpoint = OutPoint {
+        txid: self.txid,
+        vout: vout.try_into().unwrap(),
+      };
+
+      if !spent.contains(&outpoint) && wallet.is_mine(&output.script_pubkey)? {
+        parent = Some((outpoint, output.clone()));
+        break;
+      }
+    }
+
+    let Some((outpoint, output)) = parent else {
+      bail!(
+        "transaction {} has no unspent outputs belonging to wallet",
+        self.txid
+      );
+    };
+
+    let mut child = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![output.clone()],
+    };
+
+    let mut prevouts = [(outpoint, output)].into();
+
+    let excluded = self.excluded(&transaction, &descendants);
+
+    // Ancestor size and fees include the transaction itself
+    let entry = wallet.bitcoin_client().get_mempool_entry(&self.txid)?;
+
+    let ancestor_vsize = usize::try_from(entry.ancestor_size).unwrap();
+    let ancestor_fees = entry.fees.ancestor.to_sat();
+
+    let child_fee = wallet.fund_transaction(&mut child, &mut prevouts, &excluded, None, |tx| {
+      self
+        .fee_rate
+        .fee(ancestor_vsize + wallet.estimate_vsize(tx))
+        .to_sat()
+        .saturating_sub(ancestor_fees)
+    })?;
+
+    let child = wallet.sign_transaction(&child, &prevouts)?;
+
+    let txid = wallet.broadcast(&child)?;
+
+    Ok(Some(Box::new(Output {
+      descendants: Vec::new(),
+      fee: child_fee,
+      original: self.txid,
+      txid,
+    })))
+  }
+
+  /// Outputs which may not be used to pay the fee: those spent by the
+  /// transaction or its descendants, and the transaction's own outputs.
+  fn excluded(&self, transaction: &Transaction, descendants: &[Transaction]) -> BTreeSet<OutPoint> {
+    std::iter::once(transaction)
+      .chain(descendants)
+      .flat_map(|tx| tx.input.iter().map(|txin| txin.previous_output))
+      .chain((0..transaction.output.len()).map(|vout| OutPoint {
+        txid: self.txid,
+        vout: vout.try_into().unwrap(),
+      }))
+      .collect()
+  }
+
+  /// Sign script path spends of inscription reveal inputs with the key
+  /// recovered from the commit transaction's backed up recovery key, and
+  /// then have the wallet sign the remaining inputs.
+  fn sign(
+    wallet: &Wallet,
+    mut transaction: Transaction,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+    recovery_keys: &[KeyPair],
+  ) -> Result<Transaction> {
+    let secp256k1 = Secp256k1::new();
+
+    let spent = transaction
+      .input
+      .iter()
+      .map(|txin| prevouts[&txin.previous_output].clone())
+      .collect::<Vec<TxOut>>();
+
+    let script_paths = transaction
+      .input
+      .iter_mut()
+      .map(|txin| Self::script_path(&mem::take(&mut txin.witness)))
+      .collect::<Vec<Option<(ScriptBuf, ControlBlock)>>>();
+
+    let mut witnesses = Vec::new();
+
+    let mut sighash_cache = SighashCache::new(&transaction);
+
+    for (i, script_path) in script_paths.into_iter().enumerate() {
+      let Some((script, control_block)) = script_path else {
+        continue;
+      };
+
+      let key_pair = Self::script_path_key(recovery_keys, &spent[i], &script, &control_block)?;
+
+      let sighash = sighash_cache.taproot_script_spend_signature_hash(
+        i,
+        &Prevouts::All(&spent),
+        TapLeafHash::from_script(&script, LeafVersion::TapScript),
+        TapSighashType::Default,
+      )?;
+
+      let signature = Signature {
+        sig: secp256k1.sign_schnorr(&Message::from(sighash), &key_pair),
+        hash_ty: TapSighashType::Default,
+      };
+
+      witnesses.push((
+        i,
+        Witness::from_slice(&[
+          signature.to_vec(),
+          script.to_bytes(),
+          control_block.serialize(),
+        ]),
+      ));
+    }
+
+    for (i, witness) in witnesses {
+      transaction.input[i].witness = witness;
+    }
+
+    wallet.sign_transaction(&transaction, prevouts)
+  }
+
+  /// The leaf script and control block of a reveal input's witness, which
+  /// consists of a signature, the script, and the control block.
+  fn script_path(witness: &Witness) -> Option<(ScriptBuf, ControlBlock)> {
+    if witness.len() != 3 {
+      return None;
+    }
+
+    let control_block = ControlBlock::decode(witness.nth(2)?).ok()?;
+
+    Some((ScriptBuf::from(witness.nth(1)?.to_vec()), control_block))
+  }
+
+  /// Commit transaction recovery keys are the commit output's tweaked key,
+  /// so the reveal script's key is recovered by subtracting the tweak.
+  fn script_path_key(
+    recovery_keys: &[KeyPair],
+    prevout: &TxOut,
+    script: &Script,
+    control_block: &ControlBlock,
+  ) -> Result<KeyPair> {
+    let secp256k1 = Secp256k1::new();
+
+    let output_key = XOnlyPublicKey::from_slice(&prevout.script_pubkey.as_bytes()[2..])?;
+
+    let Some(recovery_key) = recovery_keys
+      .iter()
+      .find(|key_pair| key_pair.x_only_public_key().0 == output_key)
+    else {
+      bail!("recovery key for reveal input not found in wallet, use `--cpfp`");
+    };
+
+    let mut merkle_root = TapNodeHash::from_script(script, control_block.leaf_version);
+
+    for node in control_block.merkle_branch.as_inner() {
+      merkle_root = TapNodeHash::from_node_hashes(merkle_root, *node);
+    }
+
+    let tweak = TapTweakHash::from_key_and_tweak(control_block.internal_key, Some(merkle_root));
+
+    let key_pair = KeyPair::from_secret_key(
+      &secp256k1,
+      &recovery_key.secret_key().add_tweak(&Scalar::from(
+        SecretKey::from_slice(&tweak.to_scalar().to_be_bytes())?.negate(),
+      ))?,
+    );
+
+    ensure!(
+      key_pair.x_only_public_key().0 == control_block.internal_key,
+      "recovery key does not match reveal script"
+    );
+
+    Ok(key_pair)
+  }
+
+  /// Size of `transaction` once signed, keeping the witnesses of script path
+  /// spends, whose size does not change when re-signed.
+  fn estimate_vsize(wallet: &Wallet, transaction: &Transaction) -> usize {
+    let mut transaction = transaction.clone();
+
+    for txin in &mut transaction.input {
+      if Self::script_path(&txin.witness).is_none() {
+        txin.witness = TransactionBuilder::dummy_witness(wallet.satisfaction_weight());
+      }
+    }
+
+    transaction.vsize()
+  }
+
+  fn update_batch_output(output: batch::Output, commit: Txid, reveal: Txid) -> batch::Output {
+    batch::Output {
+      commit,
+      inscriptions: output
+        .inscriptions
+        .into_iter()
+        .map(|info| batch::InscriptionInfo {
+          id: InscriptionId {
+            txid: reveal,
+            ..info.id
+          },
+          location: SatPoint {
+            outpoint: OutPoint {
+              txid: reveal,
+              ..info.location.outpoint
+            },
+            ..info.location
+          },
+          ..info
+        })
+        .collect(),
+      reveal,
+      rune: output.rune.map(|rune| batch::RuneInfo {
+        allocations: rune
+          .allocations
+          .into_iter()
+          .map(|allocation| batch::AllocationInfo {
+            location: OutPoint {
+              txid: reveal,
+              ..allocation.location
+            },
+            ..allocation
+          })
+          .collect(),
+        location: rune.location.map(|location| OutPoint {
+          txid: reveal,
+          ..location
+        }),
+        ..rune
+      }),
+      ..output
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::{key::TapTweak, opcodes, secp256k1::rand, taproot::TaprootBuilder},
+  };
+
+  #[test]
+  fn script_path_key_is_recovered_from_recovery_key() {
+    let secp256k1 = Secp256k1::new();
+
+    for _ in 0..16 {
+      let key_pair = KeyPair::new(&secp256k1, &mut rand::thread_rng());
+
+      let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
+
+      let script = ScriptBuf::builder()
+        .push_slice(public_key.serialize())
+        .push_opcode(opcodes::all::OP_CHECKSIG)
+        .into_script();
+
+      let taproot_spend_info = TaprootBuilder::new()
+        .add_leaf(0, script.clone())
+        .unwrap()
+        .finalize(&secp256k1, public_key)
+        .unwrap();
+
+      let control_block = taproot_spend_info
+        .control_block(&(script.clone(), LeafVersion::TapScript))
+        .unwrap();
+
+      let recovery_key = key_pair
+        .tap_tweak(&secp256k1, taproot_spend_info.merkle_root())
+        .to_inner();
+
+      let prevout = TxOut {
+        value: 10_000,
+        script_pubkey: ScriptBuf::new_v1_p2tr_tweaked(taproot_spend_info.output_key()),
+      };
+
+      assert_eq!(
+        BumpFee::script_path_key(&[recovery_key], &prevout, &script, &control_block)
+          .unwrap()
+          .x_only_public_key()
+          .0,
+        public_key,
+      );
+    }
+  }
+
+  #[test]
+  fn script_path_key_requires_recovery_key() {
+    let secp256k1 = Secp256k1::new();
+
+    let key_pair = KeyPair::new(&secp256k1, &mut rand::thread_rng());
+
+    let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
+
+    let script = ScriptBuf::builder()
+      .push_slice(public_key.serialize())
+      .push_opcode(opcodes::all::OP_CHECKSIG)
+      .into_script();
+
+    let taproot_spend_info = TaprootBuilder::new()
+      .add_leaf(0, script.clone())
+      .unwrap()
+      .finalize(&secp256k1, public_key)
+      .unwrap();
+
+    let control_block = taproot_spend_info
+      .control_block(&(script.clone(), LeafVersion::TapScript))
+      .unwrap();
+
+    let prevout = TxOut {
+      value: 10_000,
+      script_pubkey: ScriptBuf::new_v1_p2tr_tweaked(taproot_spend_info.output_key()),
+    };
+
+    assert_eq!(
+      BumpFee::script_path_key(&[key_pair], &prevout, &script, &control_block)
+        .unwrap_err()
+        .to_string(),
+      "recovery key for reveal input not found in wallet, use `--cpfp`",
+    );
+  }
+}
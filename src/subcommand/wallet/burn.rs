@@ -0,0 +1,238 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("burn")
+    .required(true)
+    .args(&["inscription", "rune"]))
+)]
+pub(crate) struct Burn {
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    value_parser = parse_rune,
+    help = "Burn <DECIMAL:RUNE> with an edict allocating it to an OP_RETURN output."
+  )]
+  rune: Option<(Decimal, SpacedRune)>,
+  #[arg(help = "Burn <INSCRIPTION> by sending it to an OP_RETURN output.")]
+  inscription: Option<InscriptionId>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub burned: Option<Pile>,
+  pub fee: u64,
+  pub inscription: Option<InscriptionId>,
+  pub rune: Option<SpacedRune>,
+  pub total_burned: Option<Pile>,
+  pub txid: Txid,
+}
+
+impl Burn {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    wallet.check_can_sign(false)?;
+
+    match (self.inscription, self.rune) {
+      (Some(inscription_id), None) => self.burn_inscription(&wallet, inscription_id),
+      (None, Some((decimal, spaced_rune))) => self.burn_runes(&wallet, decimal, spaced_rune),
+      _ => unreachable!(),
+    }
+  }
+
+  fn burn_inscription(&self, wallet: &Wallet, inscription_id: InscriptionId) -> SubcommandResult {
+    let satpoint = wallet
+      .inscription_info()
+      .get(&inscription_id)
+      .ok_or_else(|| anyhow!("inscription {inscription_id} not in wallet"))?
+      .satpoint;
+
+    // Sats before and after the inscribed sat may be burned or paid as fees, so
+    // other inscriptions in the same output could be lost too.
+    ensure!(
+      wallet
+        .inscriptions()
+        .iter()
+        .filter(|(other, _inscriptions)| other.outpoint == satpoint.outpoint)
+        .flat_map(|(_satpoint, inscriptions)| inscriptions)
+        .all(|inscription| *inscription == inscription_id),
+      "output {} containing inscription {inscription_id} also contains other inscriptions",
+      satpoint.outpoint,
+    );
+
+    let change = wallet.get_change_address()?.script_pubkey();
+
+    let mut outputs = Vec::new();
+
+    // Sats before the inscribed sat are returned to the wallet, unless there
+    // are too few of them to make an output above the dust limit, in which
+    // case they are burned along with it.
+    let burn = if satpoint.offset >= change.dust_value().to_sat() {
+      outputs.push(TxOut {
+        script_pubkey: change,
+        value: satpoint.offset,
+      });
+      1
+    } else {
+      satpoint.offset + 1
+    };
+
+    outputs.push(TxOut {
+      script_pubkey: ScriptBuf::new_op_return(&[]),
+      value: burn,
+    });
+
+    let (transaction, fee) = self.fund(wallet, vec![satpoint.outpoint], outputs, None)?;
+
+    let signed_transaction = wallet.sign_transaction(&transaction, &BTreeMap::new())?;
+
+    // Bitcoin Core refuses to broadcast transactions with OP_RETURN outputs
+    // that have a value unless it is told how much may be burned.
+    let txid = wallet.bitcoin_client().call::<Txid>(
+      "sendrawtransaction",
+      &[
+        consensus::encode::serialize_hex(&signed_transaction).into(),
+        serde_json::Value::Null,
+        Amount::from_sat(burn).to_btc().into(),
+      ],
+    )?;
+
+    Ok(Some(Box::new(Output {
+      burned: None,
+      fee,
+      inscription: Some(inscription_id),
+      rune: None,
+      total_burned: None,
+      txid,
+    })))
+  }
+
+  fn burn_runes(
+    &self,
+    wallet: &Wallet,
+    decimal: Decimal,
+    spaced_rune: SpacedRune,
+  ) -> SubcommandResult {
+    ensure!(
+      wallet.has_rune_index(),
+      "burning runes with `ord wallet burn` requires index created with `--index-runes` flag",
+    );
+
+    let (id, entry, _parent) = wallet
+      .get_rune(spaced_rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
+
+    let amount = decimal.to_integer(entry.divisibility)?;
+
+    // An edict with an amount of zero allocates all remaining runes, so a
+    // zero burn would burn the entire balance of the inputs.
+    ensure!(amount > 0, "burn amount must be greater than zero");
+
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let mut inputs = Vec::new();
+    let mut balance = 0;
+
+    for output in wallet.get_runic_outputs()? {
+      if balance >= amount {
+        break;
+      }
+
+      if inscribed.contains(&output) {
+        continue;
+      }
+
+      let output_balance = wallet.get_rune_balance_in_output(&output, spaced_rune.rune)?;
+
+      if output_balance > 0 {
+        balance += output_balance;
+        inputs.push(output);
+      }
+    }
+
+    ensure!(
+      balance >= amount,
+      "insufficient `{}` balance, only {} in wallet",
+      spaced_rune,
+      entry.pile(balance),
+    );
+
+    // The edict allocates the burned runes to the OP_RETURN output holding the
+    // runestone. Unallocated runes go to the first non-OP_RETURN output, the
+    // postage output that follows.
+    let runestone = Runestone {
+      edicts: vec![Edict {
+        amount,
+        id,
+        output: 0,
+      }],
+      ..default()
+    };
+
+    let (transaction, fee) = self.fund(
+      wallet,
+      inputs,
+      vec![TxOut {
+        script_pubkey: runestone.encipher(),
+        value: 0,
+      }],
+      Some(TARGET_POSTAGE.to_sat()),
+    )?;
+
+    ensure!(
+      Runestone::decipher(&transaction) == Some(Artifact::Runestone(runestone)),
+      "burn transaction runestone does not decipher as intended",
+    );
+
+    let txid = wallet.send_transaction(&transaction)?;
+
+    Ok(Some(Box::new(Output {
+      burned: Some(entry.pile(amount)),
+      fee,
+      inscription: None,
+      rune: Some(entry.spaced_rune),
+      total_burned: Some(entry.pile(entry.burned + amount)),
+      txid,
+    })))
+  }
+
+  /// Build a transaction spending `inputs` to `outputs`, followed by a change
+  /// output, of at least `postage` if given, adding cardinal inputs to pay
+  /// the fee.
+  fn fund(
+    &self,
+    wallet: &Wallet,
+    inputs: Vec<OutPoint>,
+    outputs: Vec<TxOut>,
+    postage: Option<u64>,
+  ) -> Result<(Transaction, u64)> {
+    let mut transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: outputs,
+    };
+
+    let fee = wallet.fund_transaction(
+      &mut transaction,
+      &mut BTreeMap::new(),
+      &BTreeSet::new(),
+      postage,
+      |tx| wallet.estimate_fee(tx, self.fee_rate).to_sat(),
+    )?;
+
+    Ok((transaction, fee))
+  }
+}
@@ -0,0 +1,205 @@
+use {
+  super::*,
+  crate::wallet::transaction_builder::TransactionBuilder,
+  bitcoin::{
+    key::XOnlyPublicKey,
+    secp256k1::{Message, Secp256k1},
+    sighash::{Prevouts, SighashCache, TapSighashType},
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct Cancel {
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(help = "Sweep commit outputs of inscription or etching commit transaction <COMMIT>.")]
+  commit: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub commit: Txid,
+  pub etching: Option<SpacedRune>,
+  pub evicted: Vec<Txid>,
+  pub fee: u64,
+  pub inputs: Vec<OutPoint>,
+  pub txid: Txid,
+  pub value: u64,
+}
+
+impl Cancel {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    wallet.check_can_sign(false)?;
+
+    let bitcoin_client = wallet.bitcoin_client();
+
+    let commit = bitcoin_client
+      .get_raw_transaction(&self.commit, None)
+      .with_context(|| format!("transaction {} not found", self.commit))?;
+
+    let confirmed = bitcoin_client
+      .get_raw_transaction_info(&self.commit, None)?
+      .confirmations
+      .is_some();
+
+    let recovery_keys = wallet.recovery_keys()?;
+
+    let mut inputs = Vec::new();
+
+    for (vout, output) in commit.output.iter().enumerate() {
+      if !output.script_pubkey.is_v1_p2tr() {
+        continue;
+      }
+
+      let output_key = XOnlyPublicKey::from_slice(&output.script_pubkey.as_bytes()[2..])?;
+
+      if let Some(key_pair) = recovery_keys
+        .iter()
+        .find(|key_pair| key_pair.x_only_public_key().0 == output_key)
+      {
+        inputs.push((
+          OutPoint {
+            txid: self.commit,
+            vout: vout.try_into().unwrap(),
+          },
+          output.clone(),
+          *key_pair,
+        ));
+      }
+    }
+
+    ensure!(
+      !inputs.is_empty(),
+      "transaction {} has no outputs spendable with a recovery key in wallet",
+      self.commit,
+    );
+
+    #[derive(Deserialize)]
+    struct SpendingPrevout {
+      spendingtxid: Option<Txid>,
+    }
+
+    let mut evicted = Vec::new();
+
+    for (outpoint, _output, _key_pair) in &inputs {
+      if confirmed {
+        ensure!(
+          bitcoin_client
+            .get_tx_out(&outpoint.txid, outpoint.vout, Some(false))?
+            .is_some(),
+          "commit output {outpoint} has already been spent",
+        );
+      }
+
+      let spending = bitcoin_client.call::<Vec<SpendingPrevout>>(
+        "gettxspendingprevout",
+        &[serde_json::json!([{"txid": outpoint.txid, "vout": outpoint.vout}])],
+      )?;
+
+      if let Some(txid) = spending
+        .into_iter()
+        .find_map(|prevout| prevout.spendingtxid)
+      {
+        if !evicted.contains(&txid) {
+          evicted.push(txid);
+          evicted.extend(
+            bitcoin_client
+              .call::<Vec<Txid>>("getmempooldescendants", &[txid.to_string().into()])?,
+          );
+        }
+      }
+    }
+
+    let evicted_fees = evicted
+      .iter()
+      .map(|txid| wallet.get_fee(&bitcoin_client.get_raw_transaction(txid, None)?))
+      .sum::<Result<u64>>()?;
+
+    let value = inputs
+      .iter()
+      .map(|(_, output, _)| output.value)
+      .sum::<u64>();
+
+    let mut transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .iter()
+        .map(|(outpoint, _output, _key_pair)| TxIn {
+          previous_output: *outpoint,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: TransactionBuilder::dummy_witness(
+            TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+          ),
+        })
+        .collect(),
+      output: vec![TxOut {
+        script_pubkey: wallet.get_change_address()?.script_pubkey(),
+        value: 0,
+      }],
+    };
+
+    let vsize = transaction.vsize();
+
+    let fee = self
+      .fee_rate
+      .fee(vsize)
+      .to_sat()
+      .max(evicted_fees + u64::try_from(vsize).unwrap());
+
+    let dust = transaction.output[0].script_pubkey.dust_value().to_sat();
+
+    ensure!(
+      value >= fee + dust,
+      "commit outputs worth {value} sats are not enough to pay fee of {fee} sats"
+    );
+
+    transaction.output[0].value = value - fee;
+
+    let prevouts = inputs
+      .iter()
+      .map(|(_outpoint, output, _key_pair)| output.clone())
+      .collect::<Vec<TxOut>>();
+
+    let secp256k1 = Secp256k1::new();
+
+    let mut sighash_cache = SighashCache::new(&mut transaction);
+
+    for (i, (_outpoint, _output, key_pair)) in inputs.iter().enumerate() {
+      let sighash = sighash_cache.taproot_key_spend_signature_hash(
+        i,
+        &Prevouts::All(&prevouts),
+        TapSighashType::Default,
+      )?;
+
+      *sighash_cache.witness_mut(i).unwrap() = Witness::from_slice(&[secp256k1
+        .sign_schnorr(&Message::from(sighash), key_pair)
+        .as_ref()]);
+    }
+
+    let txid = wallet.broadcast(&transaction)?;
+
+    let mut etching = None;
+
+    for (rune, entry) in wallet.pending_etchings()? {
+      if entry.commit.txid() == self.commit {
+        wallet.clear_etching(rune)?;
+        etching = entry.output.rune.map(|rune| rune.rune);
+      }
+    }
+
+    Ok(Some(Box::new(Output {
+      commit: self.commit,
+      etching,
+      evicted,
+      fee,
+      inputs: inputs
+        .into_iter()
+        .map(|(outpoint, _output, _key_pair)| outpoint)
+        .collect(),
+      txid,
+      value: value - fee,
+    })))
+  }
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Consolidate {
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  pub(crate) dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Output unsigned PSBT for signing with an external signer instead of signing and broadcasting transaction."
+  )]
+  pub(crate) psbt: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub fee: u64,
+  pub inputs: Vec<OutPoint>,
+  pub output: OutPoint,
+  pub psbt: Option<String>,
+  pub txid: Txid,
+  pub value: u64,
+}
+
+impl Consolidate {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    wallet.check_can_sign(self.dry_run || self.psbt)?;
+
+    ensure!(
+      wallet.has_sat_index(),
+      "consolidate requires index created with `--index-sats` flag"
+    );
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    let rare_outputs = wallet.get_rare_outputs()?;
+
+    let inputs = wallet
+      .utxos()
+      .keys()
+      .filter(|outpoint| {
+        !inscribed_outputs.contains(outpoint)
+          && !runic_outputs.contains(outpoint)
+          && !rare_outputs.contains(outpoint)
+          && !wallet.locked_utxos().contains_key(outpoint)
+      })
+      .copied()
+      .collect::<Vec<OutPoint>>();
+
+    ensure!(
+      inputs.len() >= 2,
+      "wallet contains fewer than two cardinal outputs to consolidate"
+    );
+
+    let input_value = inputs
+      .iter()
+      .map(|outpoint| wallet.utxos()[outpoint].value)
+      .sum::<u64>();
+
+    let mut unsigned_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .iter()
+        .map(|outpoint| TxIn {
+          previous_output: *outpoint,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: vec![TxOut {
+        value: input_value,
+        script_pubkey: wallet.get_change_address()?.script_pubkey(),
+      }],
+    };
+
+    let fee = wallet
+      .estimate_fee(&unsigned_transaction, self.fee_rate)
+      .to_sat();
+
+    let dust = unsigned_transaction.output[0]
+      .script_pubkey
+      .dust_value()
+      .to_sat();
+
+    ensure!(
+      input_value >= fee + dust,
+      "cardinal outputs worth {input_value} sats are not enough to pay fee of {fee} sats"
+    );
+
+    unsigned_transaction.output[0].value -= fee;
+
+    let (txid, psbt) = if self.dry_run || self.psbt {
+      (
+        unsigned_transaction.txid(),
+        Some(wallet.unsigned_psbt(&unsigned_transaction, self.psbt.then_some(true))?),
+      )
+    } else {
+      (wallet.send_transaction(&unsigned_transaction)?, None)
+    };
+
+    Ok(Some(Box::new(Output {
+      fee,
+      inputs,
+      output: OutPoint { txid, vout: 0 },
+      psbt,
+      txid,
+      value: unsigned_transaction.output[0].value,
+    })))
+  }
+}
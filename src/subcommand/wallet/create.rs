@@ -17,6 +17,11 @@ pub(crate) struct Create {
     help = "Use <PASSPHRASE> to derive wallet seed."
   )]
   pub(crate) passphrase: String,
+  #[arg(
+    long,
+    help = "Keep wallet keys in the ord wallet database instead of a bitcoind wallet."
+  )]
+  pub(crate) native: bool,
 }
 
 impl Create {
@@ -26,7 +31,13 @@ impl Create {
 
     let mnemonic = Mnemonic::from_entropy(&entropy)?;
 
-    Wallet::initialize(name, settings, mnemonic.to_seed(&self.passphrase))?;
+    let seed = mnemonic.to_seed(&self.passphrase);
+
+    if self.native {
+      Wallet::initialize_native(name, settings, seed)?;
+    } else {
+      Wallet::initialize(name, settings, seed, false)?;
+    }
 
     Ok(Some(Box::new(Output {
       mnemonic,
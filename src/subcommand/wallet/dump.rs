@@ -1,6 +1,10 @@
 use super::*;
 
 pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
+  if wallet.is_watch_only() {
+    return Ok(Some(Box::new(wallet.dump()?)));
+  }
+
   eprintln!(
     "==========================================
 = THIS STRING CONTAINS YOUR PRIVATE KEYS =
@@ -8,7 +12,5 @@ pub(crate) fn run(wallet: Wallet) -> SubcommandResult {
 =========================================="
   );
 
-  Ok(Some(Box::new(
-    wallet.bitcoin_client().list_descriptors(Some(true))?,
-  )))
+  Ok(Some(Box::new(wallet.dump()?)))
 }
@@ -0,0 +1,89 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{psbt::Psbt, taproot::TapLeafHash},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct FinalizePsbt {
+  #[arg(
+    required = true,
+    help = "Finalize and broadcast signed base64 <PSBT>s. Commit PSBTs must precede their reveal PSBTs."
+  )]
+  psbts: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub txids: Vec<Txid>,
+}
+
+impl FinalizePsbt {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    let mut txids = Vec::new();
+
+    for psbt in self.psbts {
+      let mut psbt = Psbt::deserialize(
+        &base64::engine::general_purpose::STANDARD
+          .decode(psbt.trim())
+          .context("PSBT is not valid base64")?,
+      )
+      .context("invalid PSBT")?;
+
+      Self::finalize_reveal_inputs(&mut psbt);
+
+      let result = wallet.bitcoin_client().finalize_psbt(
+        &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+        Some(true),
+      )?;
+
+      ensure!(
+        result.complete,
+        "PSBT for transaction {} is not fully signed",
+        psbt.unsigned_tx.txid(),
+      );
+
+      let hex = result
+        .hex
+        .ok_or_else(|| anyhow!("unable to extract finalized transaction"))?;
+
+      txids.push(wallet.bitcoin_client().send_raw_transaction(&hex)?);
+    }
+
+    Ok(Some(Box::new(Output { txids })))
+  }
+
+  // Reveal PSBTs carry the signature for the inscription script path made with
+  // the ephemeral key, so the witness for that input can be assembled here
+  // without relying on the signer's finalizer understanding the script.
+  fn finalize_reveal_inputs(psbt: &mut Psbt) {
+    for input in &mut psbt.inputs {
+      if input.final_script_witness.is_some()
+        || input.tap_scripts.len() != 1
+        || input.tap_script_sigs.len() != 1
+      {
+        continue;
+      }
+
+      let (control_block, (script, leaf_version)) = input.tap_scripts.iter().next().unwrap();
+
+      let ((_public_key, leaf_hash), signature) = input.tap_script_sigs.iter().next().unwrap();
+
+      if *leaf_hash != TapLeafHash::from_script(script, *leaf_version) {
+        continue;
+      }
+
+      let mut witness = Witness::new();
+      witness.push(signature.to_vec());
+      witness.push(script);
+      witness.push(control_block.serialize());
+
+      input.final_script_witness = Some(witness);
+      input.tap_internal_key = None;
+      input.tap_key_origins.clear();
+      input.tap_merkle_root = None;
+      input.tap_script_sigs.clear();
+      input.tap_scripts.clear();
+    }
+  }
+}
@@ -83,9 +83,11 @@ impl Inscribe {
       no_limit: self.shared.no_limit,
       parent_info: wallet.get_parent_info(self.parent)?,
       postages: vec![self.postage.unwrap_or(TARGET_POSTAGE)],
+      psbt: self.shared.psbt,
       reinscribe: self.reinscribe,
       reveal_fee_rate: self.shared.fee_rate,
       reveal_satpoints: Vec::new(),
+      satisfaction_weight: wallet.satisfaction_weight(),
       satpoint: if let Some(sat) = self.sat {
         Some(wallet.find_sat_in_outputs(sat)?)
       } else {
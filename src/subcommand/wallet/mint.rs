@@ -1,4 +1,7 @@
-use super::*;
+use {super::*, crate::wallet::transaction_builder};
+
+// Bitcoin Core rejects transactions with more than 24 unconfirmed ancestors.
+const MAX_CHAIN_LENGTH: u16 = 25;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Mint {
@@ -13,6 +16,23 @@ pub(crate) struct Mint {
   postage: Option<Amount>,
   #[clap(long, help = "Send minted runes to <DESTINATION>.")]
   destination: Option<Address<NetworkUnchecked>>,
+  #[clap(
+    long,
+    help = "Output unsigned PSBT for signing with an external signer instead of signing and broadcasting mint transaction."
+  )]
+  psbt: bool,
+  #[clap(
+    long,
+    default_value_t = 1,
+    value_parser = clap::value_parser!(u16).range(1..=i64::from(MAX_CHAIN_LENGTH)),
+    help = "Mint <COUNT> times in a chain of transactions, each spending the change of the one before. May not exceed 25, the limit on unconfirmed transaction chains."
+  )]
+  count: u16,
+  #[clap(
+    long,
+    help = "Keep each minted pile in its own output, instead of gathering them into the output of the final mint transaction."
+  )]
+  split: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -20,6 +40,16 @@ pub struct Output {
   pub rune: SpacedRune,
   pub pile: Pile,
   pub mint: Txid,
+  pub psbt: Option<String>,
+  pub mints: Vec<MintTransaction>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct MintTransaction {
+  pub txid: Txid,
+  pub output: OutPoint,
+  pub pile: Pile,
+  pub psbt: Option<String>,
 }
 
 impl Mint {
@@ -29,11 +59,11 @@ impl Mint {
       "`ord wallet mint` requires index created with `--index-runes` flag",
     );
 
-    let rune = self.rune.rune;
+    wallet.check_can_sign(self.psbt)?;
 
-    let bitcoin_client = wallet.bitcoin_client();
+    let rune = self.rune.rune;
 
-    let block_height = bitcoin_client.get_block_count()?;
+    let block_height = wallet.bitcoin_client().get_block_count()?;
 
     let Some((id, rune_entry, _)) = wallet.get_rune(rune)? else {
       bail!("rune {rune} has not been etched");
@@ -45,9 +75,20 @@ impl Mint {
       .mintable(block_height)
       .map_err(|err| anyhow!("rune {rune} {err}"))?;
 
+    // The index only counts confirmed mints, so each transaction in the chain
+    // takes one more of the remaining mints.
+    if let Some(cap) = rune_entry.terms.and_then(|terms| terms.cap) {
+      let remaining = cap - rune_entry.mints;
+      ensure!(
+        remaining >= self.count.into(),
+        "rune {rune} has {remaining} mints remaining, fewer than requested count of {}",
+        self.count,
+      );
+    }
+
     let chain = wallet.chain();
 
-    let destination = match self.destination {
+    let destination = match self.destination.clone() {
       Some(destination) => destination.require_network(chain.network())?,
       None => wallet.get_change_address()?,
     };
@@ -63,56 +104,223 @@ impl Mint {
       ..default()
     };
 
-    let script_pubkey = runestone.encipher();
+    let runestone_script = runestone.encipher();
 
     ensure!(
-      script_pubkey.len() <= 82,
+      runestone_script.len() <= 82,
       "runestone greater than maximum OP_RETURN size: {} > 82",
-      script_pubkey.len()
+      runestone_script.len()
     );
 
-    let unfunded_transaction = Transaction {
+    let change = wallet.get_change_address()?.script_pubkey();
+
+    // Unless splitting, piles are gathered in the wallet and carried forward
+    // by each transaction, with the final one sending them to the destination.
+    let intermediate = if self.split || self.count == 1 {
+      destination.script_pubkey()
+    } else {
+      wallet.get_change_address()?.script_pubkey()
+    };
+
+    let transactions = self.chain(
+      &wallet,
+      &runestone_script,
+      &intermediate,
+      &destination.script_pubkey(),
+      &change,
+      postage.to_sat(),
+    )?;
+
+    // Outputs of earlier transactions in the chain, which later transactions
+    // spend before the wallet knows about them
+    let mut prevouts = BTreeMap::new();
+    let mut mints = Vec::new();
+
+    for (i, unsigned_transaction) in transactions.iter().enumerate() {
+      let txid = unsigned_transaction.txid();
+
+      mints.push(MintTransaction {
+        txid,
+        output: OutPoint { txid, vout: 1 },
+        pile: rune_entry.pile(if self.split {
+          amount
+        } else {
+          amount * u128::try_from(i + 1).unwrap()
+        }),
+        psbt: if self.psbt {
+          Some(wallet.unsigned_psbt_with_prevouts(unsigned_transaction, Some(true), &prevouts)?)
+        } else {
+          None
+        },
+      });
+
+      for (vout, output) in unsigned_transaction.output.iter().enumerate() {
+        prevouts.insert(
+          OutPoint {
+            txid,
+            vout: vout.try_into().unwrap(),
+          },
+          output.clone(),
+        );
+      }
+    }
+
+    if !self.psbt {
+      let mut signed_transactions = Vec::new();
+
+      // Sign the whole chain before broadcasting any of it, so a signing
+      // failure doesn't leave a partial chain in the mempool.
+      for unsigned_transaction in &transactions {
+        let signed_transaction = wallet.sign_transaction(unsigned_transaction, &prevouts)?;
+
+        assert_eq!(
+          Runestone::decipher(&signed_transaction),
+          Some(Artifact::Runestone(Runestone {
+            mint: Some(id),
+            ..default()
+          })),
+        );
+
+        signed_transactions.push(signed_transaction);
+      }
+
+      for signed_transaction in &signed_transactions {
+        wallet.broadcast(signed_transaction)?;
+      }
+    }
+
+    let last = mints.last().unwrap();
+
+    Ok(Some(Box::new(Output {
+      rune: self.rune,
+      pile: rune_entry.pile(amount * u128::from(self.count)),
+      mint: last.txid,
+      psbt: last.psbt.clone(),
+      mints,
+    })))
+  }
+
+  /// Build the chain of unsigned mint transactions. The first is funded with
+  /// enough cardinal outputs to pay for the whole chain, and each following
+  /// transaction spends the change of the one before.
+  fn chain(
+    &self,
+    wallet: &Wallet,
+    runestone: &ScriptBuf,
+    intermediate: &ScriptBuf,
+    destination: &ScriptBuf,
+    change: &ScriptBuf,
+    postage: u64,
+  ) -> Result<Vec<Transaction>> {
+    let count = usize::from(self.count);
+
+    let mint_script = |i: usize| {
+      if i + 1 == count {
+        destination.clone()
+      } else {
+        intermediate.clone()
+      }
+    };
+
+    let transaction = |input: Vec<OutPoint>, i: usize, change_value: u64| Transaction {
       version: 2,
       lock_time: LockTime::ZERO,
-      input: Vec::new(),
+      input: input
+        .into_iter()
+        .map(|previous_output| TxIn {
+          previous_output,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
       output: vec![
         TxOut {
-          script_pubkey,
+          script_pubkey: runestone.clone(),
           value: 0,
         },
         TxOut {
-          script_pubkey: destination.script_pubkey(),
-          value: postage.to_sat(),
+          script_pubkey: mint_script(i),
+          value: postage,
+        },
+        TxOut {
+          script_pubkey: change.clone(),
+          value: change_value,
         },
       ],
     };
 
-    wallet.lock_non_cardinal_outputs()?;
+    let chained_inputs = if self.split { 1 } else { 2 };
 
-    let unsigned_transaction =
-      fund_raw_transaction(bitcoin_client, self.fee_rate, &unfunded_transaction)?;
+    let chained_fees = (1..count)
+      .map(|i| {
+        wallet
+          .estimate_fee(
+            &transaction(vec![OutPoint::null(); chained_inputs], i, 0),
+            self.fee_rate,
+          )
+          .to_sat()
+      })
+      .collect::<Vec<u64>>();
 
-    let signed_transaction = bitcoin_client
-      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
-      .hex;
+    // When splitting, every transaction pays for its own postage, otherwise
+    // the postage of the first is carried forward. Either way, the change of
+    // the first must cover the fees and postage of the rest.
+    let carried = chained_fees.iter().sum::<u64>()
+      + if self.split {
+        postage * u64::from(self.count - 1)
+      } else {
+        0
+      }
+      + change.dust_value().to_sat();
 
-    let signed_transaction = consensus::encode::deserialize(&signed_transaction)?;
+    let mut first = transaction(Vec::new(), 0, 0);
 
-    assert_eq!(
-      Runestone::decipher(&signed_transaction),
-      Some(Artifact::Runestone(runestone)),
-    );
+    first.output.pop();
 
-    let transaction = bitcoin_client.send_raw_transaction(&signed_transaction)?;
+    wallet
+      .fund_transaction(
+        &mut first,
+        &mut BTreeMap::new(),
+        &BTreeSet::new(),
+        Some(carried),
+        |tx| wallet.estimate_fee(tx, self.fee_rate).to_sat(),
+      )
+      .map_err(
+        |err| match err.downcast_ref::<transaction_builder::Error>() {
+          Some(transaction_builder::Error::NotEnoughCardinalUtxos) => {
+            anyhow!("not enough cardinal utxos")
+          }
+          _ => err,
+        },
+      )?;
 
-    Ok(Some(Box::new(Output {
-      rune: self.rune,
-      pile: Pile {
-        amount,
-        divisibility: rune_entry.divisibility,
-        symbol: rune_entry.symbol,
-      },
-      mint: transaction,
-    })))
+    let mut transactions = vec![first];
+
+    for (i, fee) in chained_fees
+      .into_iter()
+      .enumerate()
+      .map(|(i, fee)| (i + 1, fee))
+    {
+      let previous = transactions.last().unwrap();
+      let txid = previous.txid();
+      let change_value = previous.output[2].value;
+
+      let (input, change_value) = if self.split {
+        (
+          vec![OutPoint { txid, vout: 2 }],
+          change_value - postage - fee,
+        )
+      } else {
+        (
+          vec![OutPoint { txid, vout: 1 }, OutPoint { txid, vout: 2 }],
+          change_value - fee,
+        )
+      };
+
+      transactions.push(transaction(input, i, change_value));
+    }
+
+    Ok(transactions)
   }
 }
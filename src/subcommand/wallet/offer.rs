@@ -0,0 +1,29 @@
+use super::*;
+
+pub mod accept;
+pub mod create;
+
+#[derive(Debug, Parser)]
+pub(crate) enum OfferSubcommand {
+  #[command(about = "Fund, sign, and broadcast offer to sell inscription or runes")]
+  Accept(accept::Accept),
+  #[command(about = "Create offer to sell inscription or runes")]
+  Create(create::Create),
+}
+
+impl OfferSubcommand {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    match self {
+      Self::Accept(accept) => accept.run(wallet),
+      Self::Create(create) => create.run(wallet),
+    }
+  }
+}
+
+/// Whether `runes` contains `amount` of `rune` and no other runes.
+fn contains_only(runes: &[(SpacedRune, Pile)], rune: Rune, amount: u128) -> bool {
+  matches!(
+    runes,
+    [(spaced_rune, pile)] if spaced_rune.rune == rune && pile.amount == amount,
+  )
+}
@@ -0,0 +1,206 @@
+use {
+  super::*,
+  crate::wallet::transaction_builder,
+  base64::Engine,
+  bitcoin::{psbt::Psbt, sighash::TapSighashType, taproot},
+};
+
+#[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("offer")
+    .required(true)
+    .args(&["inscription", "rune"]))
+)]
+pub(crate) struct Accept {
+  #[arg(long, help = "Pay <AMOUNT> for the offered output.")]
+  amount: Amount,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Require offered output to contain <INSCRIPTION>.")]
+  inscription: Option<InscriptionId>,
+  #[arg(
+    long,
+    value_parser = parse_rune,
+    help = "Require offered output to contain exactly <DECIMAL:RUNE> and no other runes or inscriptions."
+  )]
+  rune: Option<(Decimal, SpacedRune)>,
+  #[arg(help = "Accept offer in base64 <PSBT>.")]
+  psbt: String,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub fee: u64,
+  pub inscriptions: Vec<InscriptionId>,
+  pub outpoint: OutPoint,
+  pub runes: Vec<(SpacedRune, Pile)>,
+  pub txid: Txid,
+}
+
+impl Accept {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    wallet.check_can_sign(false)?;
+
+    ensure!(
+      wallet.has_rune_index(),
+      "accepting offers with `ord wallet offer accept` requires index created with `--index-runes` flag",
+    );
+
+    let psbt = Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(self.psbt.trim())
+        .context("PSBT is not valid base64")?,
+    )
+    .context("invalid PSBT")?;
+
+    ensure!(
+      psbt.unsigned_tx.input.len() == 1 && psbt.unsigned_tx.output.len() == 1,
+      "offer PSBT must have exactly one input and one output",
+    );
+
+    let outpoint = psbt.unsigned_tx.input[0].previous_output;
+
+    let witness = psbt.inputs[0]
+      .final_script_witness
+      .clone()
+      .ok_or_else(|| anyhow!("offer PSBT input is not signed"))?;
+
+    ensure!(
+      witness.len() == 1
+        && taproot::Signature::from_slice(&witness[0])
+          .is_ok_and(|signature| signature.hash_ty == TapSighashType::SinglePlusAnyoneCanPay),
+      "offer PSBT input is not signed with SIGHASH_SINGLE|ANYONECANPAY",
+    );
+
+    let payment = psbt.unsigned_tx.output[0].clone();
+
+    ensure!(
+      payment.value == self.amount.to_sat(),
+      "offer PSBT asks {} but expected {}",
+      Amount::from_sat(payment.value),
+      self.amount,
+    );
+
+    let info = wallet.get_output(outpoint)?;
+
+    ensure!(
+      !info.spent,
+      "offer output {outpoint} has already been spent"
+    );
+
+    match (self.inscription, self.rune) {
+      (Some(inscription_id), None) => {
+        ensure!(
+          info.inscriptions.contains(&inscription_id),
+          "offer output {outpoint} does not contain inscription {inscription_id}",
+        );
+
+        ensure!(
+          info.runes.is_empty(),
+          "offer output {outpoint} contains runes",
+        );
+      }
+      (None, Some((decimal, spaced_rune))) => {
+        let (_id, entry, _parent) = wallet
+          .get_rune(spaced_rune.rune)?
+          .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
+
+        ensure!(
+          info.inscriptions.is_empty(),
+          "offer output {outpoint} contains inscriptions",
+        );
+
+        ensure!(
+          contains_only(
+            &info.runes,
+            spaced_rune.rune,
+            decimal.to_integer(entry.divisibility)?,
+          ),
+          "offer output {outpoint} does not contain exactly {decimal}:{spaced_rune}",
+        );
+      }
+      _ => unreachable!(),
+    }
+
+    let offered = wallet.get_prevout(outpoint)?;
+
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic = wallet.get_runic_outputs()?;
+
+    // The seller's input and payment output must share an index, so the
+    // buyer's smallest cardinal goes first, and is received together with the
+    // offered output, so that the offered sats are not paid to the seller.
+    let (padding, padding_txout) = wallet
+      .utxos()
+      .iter()
+      .filter(|(candidate, _txout)| {
+        **candidate != outpoint
+          && !inscribed.contains(candidate)
+          && !runic.contains(candidate)
+          && !wallet.locked_utxos().contains_key(candidate)
+      })
+      .min_by_key(|(candidate, txout)| (txout.value, **candidate))
+      .map(|(candidate, txout)| (*candidate, txout.clone()))
+      .ok_or(transaction_builder::Error::NotEnoughCardinalUtxos)?;
+
+    let mut prevouts = BTreeMap::new();
+    prevouts.insert(padding, padding_txout.clone());
+    prevouts.insert(outpoint, offered.clone());
+
+    let mut transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![
+        TxIn {
+          previous_output: padding,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        },
+        TxIn {
+          previous_output: outpoint,
+          script_sig: psbt.unsigned_tx.input[0].script_sig.clone(),
+          sequence: psbt.unsigned_tx.input[0].sequence,
+          witness: witness.clone(),
+        },
+      ],
+      output: vec![
+        TxOut {
+          script_pubkey: wallet.get_change_address()?.script_pubkey(),
+          value: padding_txout.value + offered.value,
+        },
+        payment,
+      ],
+    };
+
+    let fee = wallet.fund_transaction(
+      &mut transaction,
+      &mut prevouts,
+      &BTreeSet::new(),
+      None,
+      |tx| wallet.estimate_fee(tx, self.fee_rate).to_sat(),
+    )?;
+
+    let signed_transaction = wallet.sign_transaction(&transaction, &prevouts)?;
+
+    ensure!(
+      signed_transaction.input[1].witness == witness,
+      "wallet replaced signature of offer PSBT input",
+    );
+
+    let txid = wallet.broadcast(&signed_transaction)?;
+
+    Ok(Some(Box::new(Output {
+      fee,
+      inscriptions: info.inscriptions,
+      outpoint,
+      runes: info.runes,
+      txid,
+    })))
+  }
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+#[clap(group(
+  ArgGroup::new("offer")
+    .required(true)
+    .args(&["inscription", "rune"]))
+)]
+pub(crate) struct Create {
+  #[arg(long, help = "Ask <AMOUNT> for the offered output.")]
+  amount: Amount,
+  #[arg(long, help = "Offer output containing <INSCRIPTION>.")]
+  inscription: Option<InscriptionId>,
+  #[arg(
+    long,
+    value_parser = parse_rune,
+    help = "Offer output containing exactly <DECIMAL:RUNE> and no other runes or inscriptions."
+  )]
+  rune: Option<(Decimal, SpacedRune)>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub amount: u64,
+  pub inscriptions: Vec<InscriptionId>,
+  pub outpoint: OutPoint,
+  pub psbt: String,
+  pub runes: Vec<(SpacedRune, Pile)>,
+}
+
+impl Create {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    wallet.check_can_sign(false)?;
+
+    let outpoint = match (self.inscription, self.rune) {
+      (Some(inscription_id), None) => Self::inscription_output(&wallet, inscription_id)?,
+      (None, Some((decimal, spaced_rune))) => Self::runic_output(&wallet, decimal, spaced_rune)?,
+      _ => unreachable!(),
+    };
+
+    let inscriptions = wallet
+      .inscriptions()
+      .iter()
+      .filter(|(satpoint, _inscriptions)| satpoint.outpoint == outpoint)
+      .flat_map(|(_satpoint, inscriptions)| inscriptions.clone())
+      .collect::<Vec<InscriptionId>>();
+
+    let runes = if wallet.has_rune_index() {
+      wallet.get_runes_balances_for_output(&outpoint)?
+    } else {
+      Vec::new()
+    };
+
+    // The seller's signature only commits to their own input and the payment
+    // output, so the buyer may add inputs and outputs to fund the purchase.
+    let unsigned_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        script_pubkey: wallet.get_change_address()?.script_pubkey(),
+        value: self.amount.to_sat(),
+      }],
+    };
+
+    Ok(Some(Box::new(Output {
+      amount: self.amount.to_sat(),
+      inscriptions,
+      outpoint,
+      psbt: wallet.sign_offer(&unsigned_transaction)?,
+      runes,
+    })))
+  }
+
+  fn inscription_output(wallet: &Wallet, inscription_id: InscriptionId) -> Result<OutPoint> {
+    let outpoint = wallet
+      .inscription_info()
+      .get(&inscription_id)
+      .ok_or_else(|| anyhow!("inscription {inscription_id} not in wallet"))?
+      .satpoint
+      .outpoint;
+
+    ensure!(
+      wallet
+        .inscriptions()
+        .iter()
+        .filter(|(satpoint, _inscriptions)| satpoint.outpoint == outpoint)
+        .flat_map(|(_satpoint, inscriptions)| inscriptions)
+        .all(|inscription| *inscription == inscription_id),
+      "output {outpoint} containing inscription {inscription_id} also contains other inscriptions",
+    );
+
+    ensure!(
+      !wallet.get_runic_outputs()?.contains(&outpoint),
+      "output {outpoint} containing inscription {inscription_id} also contains runes",
+    );
+
+    Ok(outpoint)
+  }
+
+  fn runic_output(wallet: &Wallet, decimal: Decimal, spaced_rune: SpacedRune) -> Result<OutPoint> {
+    ensure!(
+      wallet.has_rune_index(),
+      "creating rune offers with `ord wallet offer create` requires index created with `--index-runes` flag",
+    );
+
+    let (_id, entry, _parent) = wallet
+      .get_rune(spaced_rune.rune)?
+      .with_context(|| format!("rune `{}` has not been etched", spaced_rune.rune))?;
+
+    let amount = decimal.to_integer(entry.divisibility)?;
+
+    let inscribed = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    for outpoint in wallet.get_runic_outputs()? {
+      if !inscribed.contains(&outpoint)
+        && contains_only(
+          &wallet.get_runes_balances_for_output(&outpoint)?,
+          spaced_rune.rune,
+          amount,
+        )
+      {
+        return Ok(outpoint);
+      }
+    }
+
+    bail!(
+      "wallet contains no output holding exactly {decimal}:{spaced_rune} and no other runes or inscriptions, use `ord wallet send` to send that amount to the wallet",
+    );
+  }
+}
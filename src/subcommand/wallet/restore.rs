@@ -6,16 +6,48 @@ pub(crate) struct Restore {
   from: Source,
   #[arg(long, help = "Use <PASSPHRASE> when deriving wallet")]
   pub(crate) passphrase: Option<String>,
+  #[arg(
+    long,
+    help = "Restore wallet without private keys. Spending commands require `--psbt`. Implied by `--from xpub` and by output descriptors without private keys."
+  )]
+  pub(crate) watch_only: bool,
+  #[arg(
+    long,
+    conflicts_with = "watch_only",
+    help = "Keep wallet keys in the ord wallet database instead of a bitcoind wallet. Requires `--from mnemonic`."
+  )]
+  pub(crate) native: bool,
 }
 
 #[derive(clap::ValueEnum, Debug, Clone)]
 enum Source {
   Descriptor,
   Mnemonic,
+  OutputDescriptor,
+  Xpub,
 }
 
 impl Restore {
   pub(crate) fn run(self, name: String, settings: &Settings) -> SubcommandResult {
+    let mut buffer = String::new();
+
+    if self.native {
+      ensure!(
+        matches!(self.from, Source::Mnemonic),
+        "native wallets can only be restored from a mnemonic"
+      );
+
+      io::stdin().read_line(&mut buffer)?;
+      let mnemonic = Mnemonic::from_str(&buffer)?;
+      Wallet::initialize_native(
+        name,
+        settings,
+        mnemonic.to_seed(self.passphrase.unwrap_or_default()),
+      )?;
+
+      return Ok(None);
+    }
+
     ensure!(
       !settings
         .bitcoin_rpc_client(None)?
@@ -26,8 +58,6 @@ impl Restore {
       name
     );
 
-    let mut buffer = String::new();
-
     match self.from {
       Source::Descriptor => {
         io::stdin().read_to_string(&mut buffer)?;
@@ -36,7 +66,12 @@ impl Restore {
           "descriptor does not take a passphrase"
         );
         let wallet_descriptors: ListDescriptorsResult = serde_json::from_str(&buffer)?;
-        Wallet::initialize_from_descriptors(name, settings, wallet_descriptors.descriptors)?;
+        Wallet::initialize_from_descriptors(
+          name,
+          settings,
+          wallet_descriptors.descriptors,
+          self.watch_only,
+        )?;
       }
       Source::Mnemonic => {
         io::stdin().read_line(&mut buffer)?;
@@ -45,8 +80,22 @@ impl Restore {
           name,
           settings,
           mnemonic.to_seed(self.passphrase.unwrap_or_default()),
+          self.watch_only,
         )?;
       }
+      Source::OutputDescriptor => {
+        io::stdin().read_to_string(&mut buffer)?;
+        ensure!(
+          self.passphrase.is_none(),
+          "output descriptor does not take a passphrase"
+        );
+        Wallet::initialize_from_output_descriptor(name, settings, &buffer, self.watch_only)?;
+      }
+      Source::Xpub => {
+        io::stdin().read_line(&mut buffer)?;
+        ensure!(self.passphrase.is_none(), "xpub does not take a passphrase");
+        Wallet::initialize_from_xpub(name, settings, &buffer)?;
+      }
     }
 
     Ok(None)
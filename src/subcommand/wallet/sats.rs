@@ -7,6 +7,12 @@ pub(crate) struct Sats {
     help = "Find satoshis listed in first column of tab-separated value file <TSV>."
   )]
   tsv: Option<PathBuf>,
+  #[arg(
+    long,
+    conflicts_with = "tsv",
+    help = "Find satoshis with satribute <RARITY>."
+  )]
+  rarity: Option<Satribute>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -23,6 +29,14 @@ pub struct OutputRare {
   pub rarity: Rarity,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct OutputSatribute {
+  pub sat: Sat,
+  pub size: u64,
+  pub output: OutPoint,
+  pub offset: u64,
+}
+
 impl Sats {
   pub(crate) fn run(&self, wallet: Wallet) -> SubcommandResult {
     ensure!(
@@ -47,6 +61,22 @@ impl Sats {
         .collect();
 
       Ok(Some(Box::new(OutputTsv { found, lost })))
+    } else if let Some(satribute) = self.rarity {
+      ensure!(
+        !satribute.mainnet() || wallet.chain() == Chain::Mainnet,
+        "satribute `{satribute}` only exists on mainnet",
+      );
+
+      let mut output = Vec::new();
+      for (outpoint, sat, offset, size) in Self::satribute_sats(satribute, haystacks) {
+        output.push(OutputSatribute {
+          sat,
+          size,
+          output: outpoint,
+          offset,
+        });
+      }
+      Ok(Some(Box::new(output)))
     } else {
       let mut output = Vec::new();
       for (outpoint, sat, offset, rarity) in Self::rare_sats(haystacks) {
@@ -145,6 +175,33 @@ impl Sats {
       })
       .collect()
   }
+
+  fn satribute_sats(
+    satribute: Satribute,
+    haystacks: Vec<(OutPoint, Vec<(u64, u64)>)>,
+  ) -> Vec<(OutPoint, Sat, u64, u64)> {
+    haystacks
+      .into_iter()
+      .flat_map(|(outpoint, sat_ranges)| {
+        let mut offset = 0;
+        sat_ranges.into_iter().flat_map(move |(start, end)| {
+          let start_offset = offset;
+          offset += end - start;
+          satribute
+            .ranges(start, end)
+            .into_iter()
+            .map(move |(first, last)| {
+              (
+                outpoint,
+                Sat(first),
+                start_offset + first - start,
+                last - first,
+              )
+            })
+        })
+      })
+      .collect()
+  }
 }
 
 #[cfg(test)]
@@ -201,6 +258,46 @@ mod tests {
     )
   }
 
+  #[test]
+  fn identify_satribute_sats() {
+    assert_eq!(
+      Sats::satribute_sats(
+        Satribute::Palindrome,
+        vec![
+          (outpoint(1), vec![(5, 12), (100, 102)]),
+          (outpoint(2), vec![(120, 123)]),
+        ],
+      ),
+      vec![
+        (outpoint(1), Sat(5), 0, 1),
+        (outpoint(1), Sat(6), 1, 1),
+        (outpoint(1), Sat(7), 2, 1),
+        (outpoint(1), Sat(8), 3, 1),
+        (outpoint(1), Sat(9), 4, 1),
+        (outpoint(1), Sat(11), 6, 1),
+        (outpoint(1), Sat(101), 8, 1),
+        (outpoint(2), Sat(121), 1, 1),
+      ]
+    )
+  }
+
+  #[test]
+  fn identify_satribute_ranges() {
+    assert_eq!(
+      Sats::satribute_sats(
+        Satribute::Block78,
+        vec![(
+          outpoint(1),
+          vec![
+            (10, 20),
+            (78 * 50 * COIN_VALUE - 5, 78 * 50 * COIN_VALUE + 5)
+          ]
+        )],
+      ),
+      vec![(outpoint(1), Sat(78 * 50 * COIN_VALUE), 15, 5)]
+    )
+  }
+
   #[track_caller]
   fn case(tsv: &str, haystacks: &[(OutPoint, Vec<(u64, u64)>)], expected: &[(&str, SatPoint)]) {
     assert_eq!(
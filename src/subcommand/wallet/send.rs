@@ -1,5 +1,7 @@
 use {super::*, crate::outgoing::Outgoing, base64::Engine, bitcoin::psbt::Psbt};
 
+pub mod batch;
+
 #[derive(Debug, Parser)]
 pub(crate) struct Send {
   #[arg(long, help = "Don't sign or broadcast transaction")]
@@ -11,8 +13,22 @@ pub(crate) struct Send {
     help = "Target <AMOUNT> postage with sent inscriptions. [default: 10000 sat]"
   )]
   pub(crate) postage: Option<Amount>,
-  address: Address<NetworkUnchecked>,
-  outgoing: Outgoing,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Output unsigned PSBT for signing with an external signer instead of signing and broadcasting transaction."
+  )]
+  pub(crate) psbt: bool,
+  #[arg(
+    long,
+    conflicts_with_all = ["address", "outgoing"],
+    help = "Send to each address and outgoing pair listed in YAML <BATCH> file, in as few transactions as possible."
+  )]
+  pub(crate) batch: Option<PathBuf>,
+  #[arg(required_unless_present = "batch")]
+  address: Option<Address<NetworkUnchecked>>,
+  #[arg(required_unless_present = "batch")]
+  outgoing: Option<Outgoing>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -25,12 +41,21 @@ pub struct Output {
 
 impl Send {
   pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    wallet.check_can_sign(self.dry_run || self.psbt)?;
+
+    if let Some(batch) = &self.batch {
+      return batch::Batchfile::load(batch)?.send(&self, &wallet);
+    }
+
     let address = self
       .address
       .clone()
+      .unwrap()
       .require_network(wallet.chain().network())?;
 
-    let unsigned_transaction = match self.outgoing {
+    let outgoing = self.outgoing.clone().unwrap();
+
+    let unsigned_transaction = match outgoing {
       Outgoing::Amount(amount) => {
         Self::create_unsigned_send_amount_transaction(&wallet, address, amount, self.fee_rate)?
       }
@@ -73,19 +98,18 @@ impl Send {
 
     let unspent_outputs = wallet.utxos();
 
-    let (txid, psbt) = if self.dry_run {
-      let psbt = wallet
-        .bitcoin_client()
-        .wallet_process_psbt(
-          &base64::engine::general_purpose::STANDARD
-            .encode(Psbt::from_unsigned_tx(unsigned_transaction.clone())?.serialize()),
-          Some(false),
-          None,
-          None,
-        )?
-        .psbt;
+    let (txid, psbt) = if self.dry_run || self.psbt {
+      (
+        unsigned_transaction.txid(),
+        wallet.unsigned_psbt(&unsigned_transaction, self.psbt.then_some(true))?,
+      )
+    } else if wallet.is_native() {
+      let signed_transaction = wallet.sign_transaction(&unsigned_transaction, &BTreeMap::new())?;
 
-      (unsigned_transaction.txid(), psbt)
+      (
+        wallet.broadcast(&signed_transaction)?,
+        wallet.unsigned_psbt(&unsigned_transaction, None)?,
+      )
     } else {
       let psbt = wallet
         .bitcoin_client()
@@ -125,7 +149,7 @@ impl Send {
     Ok(Some(Box::new(Output {
       txid,
       psbt,
-      outgoing: self.outgoing,
+      outgoing,
       fee,
     })))
   }
@@ -136,6 +160,15 @@ impl Send {
     amount: Amount,
     fee_rate: FeeRate,
   ) -> Result<Transaction> {
+    if wallet.is_native() {
+      return Self::create_unsigned_native_send_amount_transaction(
+        wallet,
+        destination,
+        amount,
+        fee_rate,
+      );
+    }
+
     wallet.lock_non_cardinal_outputs()?;
 
     let unfunded_transaction = Transaction {
@@ -157,6 +190,56 @@ impl Send {
     Ok(unsigned_transaction)
   }
 
+  // Native wallets have no bitcoind wallet to fund transactions, so the
+  // transaction builder is used instead, sending from the first sat of the
+  // largest cardinal output.
+  fn create_unsigned_native_send_amount_transaction(
+    wallet: &Wallet,
+    destination: Address,
+    amount: Amount,
+    fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    let outpoint = wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, txout)| {
+        txout.value > 0
+          && !inscribed_outputs.contains(outpoint)
+          && !runic_outputs.contains(outpoint)
+          && !wallet.locked_utxos().contains_key(outpoint)
+      })
+      .max_by_key(|(_outpoint, txout)| txout.value)
+      .map(|(outpoint, _txout)| *outpoint)
+      .ok_or_else(|| anyhow!("wallet contains no cardinal utxos"))?;
+
+    Ok(
+      TransactionBuilder::new(
+        SatPoint {
+          outpoint,
+          offset: 0,
+        },
+        wallet.inscriptions().clone(),
+        wallet.utxos().clone(),
+        wallet.locked_utxos().clone().into_keys().collect(),
+        runic_outputs,
+        destination,
+        [wallet.get_change_address()?, wallet.get_change_address()?],
+        fee_rate,
+        Target::Value(amount),
+      )
+      .satisfaction_weight(wallet.satisfaction_weight())
+      .build_transaction()?,
+    )
+  }
+
   fn create_unsigned_send_satpoint_transaction(
     wallet: &Wallet,
     destination: Address,
@@ -200,6 +283,7 @@ impl Send {
         fee_rate,
         postage,
       )
+      .satisfaction_weight(wallet.satisfaction_weight())
       .build_transaction()?,
     )
   }
@@ -216,6 +300,8 @@ impl Send {
       "sending runes with `ord send` requires index created with `--index-runes` flag",
     );
 
+    wallet.check_native_support("sending runes")?;
+
     let inscriptions = wallet.inscriptions();
     let runic_outputs = wallet.get_runic_outputs()?;
     let bitcoin_client = wallet.bitcoin_client();
@@ -0,0 +1,556 @@
+use {super::*, crate::wallet::transaction_builder};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Batchfile {
+  pub sends: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Entry {
+  pub address: Address<NetworkUnchecked>,
+  pub outgoing: Outgoing,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub transactions: Vec<BatchTransaction>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchTransaction {
+  pub fee: u64,
+  pub psbt: Option<String>,
+  pub sends: Vec<Entry>,
+  pub txid: Txid,
+}
+
+enum Payment {
+  Amount {
+    address: Address,
+    amount: Amount,
+  },
+  Rune {
+    address: Address,
+    amount: u128,
+    entry: RuneEntry,
+    id: RuneId,
+  },
+  SatPoint {
+    address: Address,
+    satpoint: SatPoint,
+  },
+}
+
+struct Planner<'a> {
+  dust: u64,
+  fee_rate: FeeRate,
+  inscribed: BTreeMap<OutPoint, BTreeSet<u64>>,
+  payments: Vec<Payment>,
+  postage: u64,
+  runic: BTreeSet<OutPoint>,
+  used: BTreeSet<OutPoint>,
+  wallet: &'a Wallet,
+}
+
+impl Batchfile {
+  pub(crate) fn load(path: &Path) -> Result<Self> {
+    let batchfile: Self = serde_yaml::from_reader(
+      fs::File::open(path).with_context(|| format!("I/O error reading `{}`", path.display()))?,
+    )?;
+
+    ensure!(
+      !batchfile.sends.is_empty(),
+      "batchfile must contain at least one send"
+    );
+
+    Ok(batchfile)
+  }
+
+  pub(crate) fn send(self, send: &Send, wallet: &Wallet) -> SubcommandResult {
+    let inscription_info = wallet.inscription_info();
+
+    let mut payments = Vec::new();
+
+    for entry in &self.sends {
+      let address = entry
+        .address
+        .clone()
+        .require_network(wallet.chain().network())?;
+
+      payments.push(match &entry.outgoing {
+        Outgoing::Amount(amount) => Payment::Amount {
+          address,
+          amount: *amount,
+        },
+        Outgoing::InscriptionId(id) => Payment::SatPoint {
+          address,
+          satpoint: inscription_info
+            .get(id)
+            .ok_or_else(|| anyhow!("inscription {id} not found"))?
+            .satpoint,
+        },
+        Outgoing::Rune { decimal, rune } => {
+          ensure!(
+            wallet.has_rune_index(),
+            "sending runes with `ord send` requires index created with `--index-runes` flag",
+          );
+
+          let (id, entry, _parent) = wallet
+            .get_rune(rune.rune)?
+            .with_context(|| format!("rune `{}` has not been etched", rune.rune))?;
+
+          Payment::Rune {
+            address,
+            amount: decimal.to_integer(entry.divisibility)?,
+            entry,
+            id,
+          }
+        }
+        Outgoing::Sat(sat) => Payment::SatPoint {
+          address,
+          satpoint: wallet.find_sat_in_outputs(*sat)?,
+        },
+        Outgoing::SatPoint(satpoint) => {
+          ensure!(
+            !wallet.inscriptions().contains_key(satpoint),
+            "inscriptions must be sent by inscription ID"
+          );
+
+          Payment::SatPoint {
+            address,
+            satpoint: *satpoint,
+          }
+        }
+      });
+    }
+
+    let runic = wallet.get_runic_outputs()?;
+
+    let mut satpoints = BTreeSet::new();
+
+    // Sends of sats in the same output must be made in the same transaction,
+    // so they are grouped together, while all other sends are independent.
+    let mut groups = BTreeMap::<OutPoint, Vec<usize>>::new();
+    let mut independent = Vec::new();
+
+    for (i, payment) in payments.iter().enumerate() {
+      let Payment::SatPoint { satpoint, .. } = payment else {
+        independent.push(vec![i]);
+        continue;
+      };
+
+      ensure!(
+        satpoints.insert(*satpoint),
+        "batch sends satpoint {satpoint} more than once"
+      );
+
+      let value = wallet
+        .utxos()
+        .get(&satpoint.outpoint)
+        .ok_or(transaction_builder::Error::NotInWallet(*satpoint))?
+        .value;
+
+      ensure!(
+        satpoint.offset < value,
+        transaction_builder::Error::OutOfRange(*satpoint, value - 1)
+      );
+
+      ensure!(
+        !runic.contains(&satpoint.outpoint),
+        "runic outpoints may not be sent by satpoint"
+      );
+
+      ensure!(
+        !wallet.locked_utxos().contains_key(&satpoint.outpoint),
+        "output {} is locked",
+        satpoint.outpoint
+      );
+
+      groups.entry(satpoint.outpoint).or_default().push(i);
+    }
+
+    let mut inscribed = BTreeMap::<OutPoint, BTreeSet<u64>>::new();
+
+    for satpoint in wallet.inscriptions().keys() {
+      inscribed
+        .entry(satpoint.outpoint)
+        .or_default()
+        .insert(satpoint.offset);
+    }
+
+    let mut planner = Planner {
+      dust: wallet
+        .get_change_address()?
+        .script_pubkey()
+        .dust_value()
+        .to_sat(),
+      fee_rate: send.fee_rate,
+      inscribed,
+      payments,
+      postage: send.postage.unwrap_or(TARGET_POSTAGE).to_sat(),
+      runic,
+      used: BTreeSet::new(),
+      wallet,
+    };
+
+    let mut transactions = Vec::new();
+
+    for (unsigned_transaction, payments) in
+      planner.plan(groups.into_values().chain(independent).collect())?
+    {
+      let fee = unsigned_transaction
+        .input
+        .iter()
+        .map(|txin| wallet.utxos()[&txin.previous_output].value)
+        .sum::<u64>()
+        - unsigned_transaction
+          .output
+          .iter()
+          .map(|txout| txout.value)
+          .sum::<u64>();
+
+      let (txid, psbt) = if send.dry_run || send.psbt {
+        (
+          unsigned_transaction.txid(),
+          Some(wallet.unsigned_psbt(&unsigned_transaction, send.psbt.then_some(true))?),
+        )
+      } else {
+        (wallet.send_transaction(&unsigned_transaction)?, None)
+      };
+
+      transactions.push(BatchTransaction {
+        fee,
+        psbt,
+        sends: payments
+          .into_iter()
+          .map(|i| self.sends[i].clone())
+          .collect(),
+        txid,
+      });
+    }
+
+    Ok(Some(Box::new(Output { transactions })))
+  }
+}
+
+impl Planner<'_> {
+  /// Build transactions for groups of sends, splitting them across as few
+  /// transactions as fit under the standard transaction weight limit.
+  fn plan(&mut self, groups: Vec<Vec<usize>>) -> Result<Vec<(Transaction, Vec<usize>)>> {
+    let mut pending = VecDeque::from([groups]);
+    let mut transactions = Vec::new();
+
+    while let Some(mut chunk) = pending.pop_front() {
+      let transaction = self.build(&chunk)?;
+
+      if self.wallet.estimate_vsize(&transaction)
+        > usize::try_from(bitcoin::policy::MAX_STANDARD_TX_WEIGHT / 4).unwrap()
+        && chunk.len() > 1
+      {
+        let second = chunk.split_off(chunk.len() / 2);
+        pending.push_front(second);
+        pending.push_front(chunk);
+        continue;
+      }
+
+      self
+        .used
+        .extend(transaction.input.iter().map(|txin| txin.previous_output));
+
+      let mut payments = chunk.into_iter().flatten().collect::<Vec<usize>>();
+      payments.sort();
+
+      transactions.push((transaction, payments));
+    }
+
+    Ok(transactions)
+  }
+
+  fn build(&self, chunk: &[Vec<usize>]) -> Result<Transaction> {
+    let payments = chunk
+      .iter()
+      .flatten()
+      .map(|i| &self.payments[*i])
+      .collect::<Vec<&Payment>>();
+
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+
+    let mut satpoints = BTreeMap::<OutPoint, Vec<(u64, &Address)>>::new();
+
+    for payment in &payments {
+      if let Payment::SatPoint { address, satpoint } = payment {
+        satpoints
+          .entry(satpoint.outpoint)
+          .or_default()
+          .push((satpoint.offset, address));
+      }
+    }
+
+    // Sent sats are placed at the start of their own outputs, in the same way
+    // as `TransactionBuilder`, with the sats between them returned as change.
+    for (outpoint, mut sends) in satpoints {
+      sends.sort_by_key(|(offset, _address)| *offset);
+
+      let value = self.wallet.utxos()[&outpoint].value;
+
+      let unsent = self
+        .inscribed
+        .get(&outpoint)
+        .into_iter()
+        .flatten()
+        .filter(|offset| !sends.iter().any(|(sent, _address)| sent == *offset))
+        .copied()
+        .collect::<BTreeSet<u64>>();
+
+      let mut aligned = Vec::<TxOut>::new();
+      let mut cursor = 0;
+
+      for (i, (offset, address)) in sends.iter().enumerate() {
+        let satpoint = SatPoint {
+          outpoint,
+          offset: *offset,
+        };
+
+        let gap = offset - cursor;
+
+        if gap >= self.dust {
+          aligned.push(self.change(gap)?);
+        } else if gap > 0 {
+          if i == 0 {
+            let padding = self
+              .cardinals(&inputs)
+              .into_iter()
+              .last()
+              .ok_or(transaction_builder::Error::NotEnoughCardinalUtxos)?;
+
+            outputs.push(self.change(self.wallet.utxos()[&padding].value + gap)?);
+            inputs.push(padding);
+          } else if unsent.range(cursor..*offset).next().is_none() {
+            aligned.last_mut().unwrap().value += gap;
+          } else {
+            bail!("cannot send {satpoint} without also sending the inscription in the {gap} sats before it");
+          }
+        }
+
+        let next = [
+          sends.get(i + 1).map(|(next, _address)| *next),
+          unsent.range(offset + 1..).next().copied(),
+          Some(value),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap();
+
+        let end = (offset + self.postage).min(next);
+
+        let script_pubkey = address.script_pubkey();
+
+        ensure!(
+          end - offset >= script_pubkey.dust_value().to_sat(),
+          "cannot send {satpoint} with postage of {} sats, which is below the dust limit",
+          end - offset,
+        );
+
+        aligned.push(TxOut {
+          value: end - offset,
+          script_pubkey,
+        });
+
+        cursor = end;
+      }
+
+      let tail = value - cursor;
+
+      if tail >= self.dust {
+        aligned.push(self.change(tail)?);
+      } else if tail > 0 {
+        ensure!(
+          unsent.range(cursor..).next().is_none(),
+          "cannot send {} without also sending the inscription in the {tail} sats after it",
+          SatPoint {
+            outpoint,
+            offset: sends.last().unwrap().0,
+          },
+        );
+
+        aligned.last_mut().unwrap().value += tail;
+      }
+
+      inputs.push(outpoint);
+      outputs.extend(aligned);
+    }
+
+    let mut edicts = Vec::new();
+    let mut needed = BTreeMap::<Rune, (u128, &RuneEntry)>::new();
+
+    for payment in &payments {
+      if let Payment::Rune {
+        address,
+        amount,
+        entry,
+        id,
+      } = payment
+      {
+        needed.entry(entry.spaced_rune.rune).or_insert((0, entry)).0 += amount;
+
+        edicts.push(Edict {
+          amount: *amount,
+          id: *id,
+          output: outputs.len().try_into().unwrap(),
+        });
+
+        outputs.push(TxOut {
+          value: TARGET_POSTAGE.to_sat(),
+          script_pubkey: address.script_pubkey(),
+        });
+      }
+    }
+
+    // All rune transfers share a single runestone, with unallocated runes
+    // from the selected inputs sent to a change output by the pointer.
+    if !needed.is_empty() {
+      let mut balances = BTreeMap::<Rune, u128>::new();
+
+      for output in &self.runic {
+        let satisfied = needed.iter().all(|(rune, (amount, _entry))| {
+          balances.get(rune).copied().unwrap_or_default() >= *amount
+        });
+
+        if satisfied {
+          break;
+        }
+
+        if self.used.contains(output)
+          || inputs.contains(output)
+          || self.inscribed.contains_key(output)
+          || self.wallet.locked_utxos().contains_key(output)
+        {
+          continue;
+        }
+
+        let mut contributes = false;
+
+        for rune in needed.keys() {
+          let balance = self.wallet.get_rune_balance_in_output(output, *rune)?;
+          if balance > 0 {
+            *balances.entry(*rune).or_default() += balance;
+            contributes = true;
+          }
+        }
+
+        if contributes {
+          inputs.push(*output);
+        }
+      }
+
+      for (rune, (amount, entry)) in &needed {
+        let balance = balances.get(rune).copied().unwrap_or_default();
+
+        ensure! {
+          balance >= *amount,
+          "insufficient `{}` balance, only {} in wallet",
+          entry.spaced_rune,
+          Pile {
+            amount: balance,
+            divisibility: entry.divisibility,
+            symbol: entry.symbol
+          },
+        }
+      }
+
+      let pointer = outputs.len().try_into().unwrap();
+
+      outputs.push(self.change(TARGET_POSTAGE.to_sat())?);
+
+      outputs.push(TxOut {
+        value: 0,
+        script_pubkey: Runestone {
+          edicts,
+          pointer: Some(pointer),
+          ..default()
+        }
+        .encipher(),
+      });
+    }
+
+    for payment in &payments {
+      if let Payment::Amount { address, amount } = payment {
+        let script_pubkey = address.script_pubkey();
+
+        ensure!(
+          *amount >= script_pubkey.dust_value(),
+          "cannot send {amount} to {address}, which is below the dust limit"
+        );
+
+        outputs.push(TxOut {
+          value: amount.to_sat(),
+          script_pubkey,
+        });
+      }
+    }
+
+    let mut transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .iter()
+        .map(|outpoint| Self::txin(*outpoint))
+        .collect(),
+      output: outputs,
+    };
+
+    self.wallet.fund_transaction(
+      &mut transaction,
+      &mut BTreeMap::new(),
+      &self.used,
+      None,
+      |tx| self.wallet.estimate_fee(tx, self.fee_rate).to_sat(),
+    )?;
+
+    Ok(transaction)
+  }
+
+  /// Unused cardinal outputs, largest first.
+  fn cardinals(&self, inputs: &[OutPoint]) -> Vec<OutPoint> {
+    let mut cardinals = self
+      .wallet
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _txout)| {
+        !self.used.contains(outpoint)
+          && !inputs.contains(outpoint)
+          && !self.inscribed.contains_key(outpoint)
+          && !self.runic.contains(outpoint)
+          && !self.wallet.locked_utxos().contains_key(outpoint)
+      })
+      .map(|(outpoint, txout)| (txout.value, *outpoint))
+      .collect::<Vec<(u64, OutPoint)>>();
+
+    cardinals.sort_by(|a, b| b.cmp(a));
+
+    cardinals
+      .into_iter()
+      .map(|(_value, outpoint)| outpoint)
+      .collect()
+  }
+
+  fn change(&self, value: u64) -> Result<TxOut> {
+    Ok(TxOut {
+      value,
+      script_pubkey: self.wallet.get_change_address()?.script_pubkey(),
+    })
+  }
+
+  fn txin(previous_output: OutPoint) -> TxIn {
+    TxIn {
+      previous_output,
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }
+  }
+}
@@ -0,0 +1,41 @@
+use {super::*, base64::Engine, bitcoin::psbt::Psbt};
+
+#[derive(Debug, Parser)]
+pub(crate) struct SignPsbt {
+  #[arg(help = "Add signatures from this wallet to base64 <PSBT>.")]
+  psbt: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Output {
+  pub complete: bool,
+  pub psbt: String,
+}
+
+impl SignPsbt {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    ensure!(
+      !wallet.is_watch_only(),
+      "watch-only wallets cannot sign PSBTs"
+    );
+
+    let psbt = Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(self.psbt.trim())
+        .context("PSBT is not valid base64")?,
+    )
+    .context("invalid PSBT")?;
+
+    let result = wallet.bitcoin_client().wallet_process_psbt(
+      &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+      Some(true),
+      None,
+      None,
+    )?;
+
+    Ok(Some(Box::new(Output {
+      complete: result.complete,
+      psbt: result.psbt,
+    })))
+  }
+}
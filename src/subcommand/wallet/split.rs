@@ -0,0 +1,556 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Split {
+  #[arg(long, help = "Don't sign or broadcast transaction")]
+  pub(crate) dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Carve single sats into outputs of <POSTAGE>, starting with the selected sat. [default: 10000 sat]"
+  )]
+  postage: Option<Amount>,
+  #[arg(
+    long,
+    conflicts_with = "dry_run",
+    help = "Output unsigned PSBT for signing with an external signer instead of signing and broadcasting transaction."
+  )]
+  pub(crate) psbt: bool,
+  #[arg(
+    required = true,
+    help = "Carve <SELECTION> into its own output. Either a sat or a `<START>-<END>` sat range."
+  )]
+  selections: Vec<Selection>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Selection {
+  Range(Sat, Sat),
+  Sat(Sat),
+}
+
+impl Display for Selection {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    match self {
+      Self::Range(start, end) => write!(f, "{start}-{end}"),
+      Self::Sat(sat) => write!(f, "{sat}"),
+    }
+  }
+}
+
+impl FromStr for Selection {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.split_once('-') {
+      Some((start, end)) => {
+        let start = start.parse::<Sat>()?;
+        let end = end.parse::<Sat>()?;
+        ensure!(start < end, "sat range `{s}` is empty");
+        Ok(Self::Range(start, end))
+      }
+      None => Ok(Self::Sat(s.parse()?)),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub fee: u64,
+  pub outputs: Vec<SplitOutput>,
+  pub psbt: Option<String>,
+  pub txid: Txid,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SplitOutput {
+  pub output: OutPoint,
+  pub sat_ranges: Vec<(u64, u64)>,
+  pub selection: Option<String>,
+  pub value: u64,
+}
+
+#[derive(Debug, PartialEq)]
+struct Piece {
+  selection: Option<Selection>,
+  value: u64,
+}
+
+impl Split {
+  pub(crate) fn run(self, wallet: Wallet) -> SubcommandResult {
+    wallet.check_can_sign(self.dry_run || self.psbt)?;
+
+    ensure!(
+      wallet.has_sat_index(),
+      "split requires index created with `--index-sats` flag"
+    );
+
+    let sat_ranges = wallet
+      .get_output_sat_ranges()?
+      .into_iter()
+      .collect::<BTreeMap<OutPoint, Vec<(u64, u64)>>>();
+
+    let mut selected = BTreeMap::<OutPoint, Vec<(u64, u64, Selection)>>::new();
+
+    for selection in &self.selections {
+      let (outpoint, start, end) = self.locate(*selection, &sat_ranges)?;
+      selected
+        .entry(outpoint)
+        .or_default()
+        .push((start, end, *selection));
+    }
+
+    let inscribed_outputs = wallet
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic_outputs = wallet.get_runic_outputs()?;
+
+    for outpoint in selected.keys() {
+      ensure!(
+        !inscribed_outputs.contains(outpoint),
+        "output {outpoint} contains inscriptions and cannot be split"
+      );
+
+      ensure!(
+        !runic_outputs.contains(outpoint),
+        "output {outpoint} contains runes and cannot be split"
+      );
+
+      ensure!(
+        !wallet.locked_utxos().contains_key(outpoint),
+        "output {outpoint} is locked and cannot be split"
+      );
+    }
+
+    let dust = wallet
+      .get_change_address()?
+      .script_pubkey()
+      .dust_value()
+      .to_sat();
+
+    let mut inputs = Vec::new();
+    let mut pieces = Vec::new();
+
+    for (outpoint, selections) in selected {
+      let value = sat_ranges[&outpoint]
+        .iter()
+        .map(|(start, end)| end - start)
+        .sum();
+
+      pieces.extend(Self::pieces(value, selections, dust)?);
+      inputs.push(outpoint);
+    }
+
+    let mut unsigned_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: inputs
+        .iter()
+        .map(|outpoint| TxIn {
+          previous_output: *outpoint,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: pieces
+        .iter()
+        .map(|piece| {
+          Ok(TxOut {
+            value: piece.value,
+            script_pubkey: wallet.get_change_address()?.script_pubkey(),
+          })
+        })
+        .collect::<Result<Vec<TxOut>>>()?,
+    };
+
+    let fee = wallet
+      .estimate_fee(&unsigned_transaction, self.fee_rate)
+      .to_sat();
+
+    let last = pieces.last().unwrap();
+
+    let last_sat_ranges = Self::output_sat_ranges(
+      &inputs
+        .iter()
+        .map(|outpoint| sat_ranges[outpoint].clone())
+        .collect::<Vec<Vec<(u64, u64)>>>(),
+      &pieces.iter().map(|piece| piece.value).collect::<Vec<u64>>(),
+    )
+    .pop()
+    .unwrap();
+
+    // Fees are paid with the last sats of the transaction's inputs, so unless
+    // the final piece is a gap of common sats large enough to cover them, an
+    // additional cardinal input is added to keep selected or rare sats from
+    // being spent as fees.
+    if last.selection.is_none() && last.value >= fee + dust && Self::common(&last_sat_ranges) {
+      unsigned_transaction.output.last_mut().unwrap().value -= fee;
+    } else {
+      let rare_outputs = wallet.get_rare_outputs()?;
+
+      let (outpoint, txout) = wallet
+        .utxos()
+        .iter()
+        .filter(|(outpoint, _txout)| {
+          !inputs.contains(outpoint)
+            && !inscribed_outputs.contains(outpoint)
+            && !runic_outputs.contains(outpoint)
+            && !rare_outputs.contains(outpoint)
+            && !wallet.locked_utxos().contains_key(outpoint)
+        })
+        .max_by_key(|(_outpoint, txout)| txout.value)
+        .ok_or_else(|| anyhow!("wallet contains no cardinal outputs to pay fee"))?;
+
+      inputs.push(*outpoint);
+
+      unsigned_transaction.input.push(TxIn {
+        previous_output: *outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      });
+
+      unsigned_transaction.output.push(TxOut {
+        value: txout.value,
+        script_pubkey: wallet.get_change_address()?.script_pubkey(),
+      });
+
+      pieces.push(Piece {
+        selection: None,
+        value: txout.value,
+      });
+
+      let fee = wallet
+        .estimate_fee(&unsigned_transaction, self.fee_rate)
+        .to_sat();
+
+      ensure!(
+        txout.value >= fee + dust,
+        "wallet contains no cardinal output large enough to pay fee of {fee} sats"
+      );
+
+      unsigned_transaction.output.last_mut().unwrap().value -= fee;
+    }
+
+    let fee = inputs
+      .iter()
+      .map(|outpoint| wallet.utxos()[outpoint].value)
+      .sum::<u64>()
+      - unsigned_transaction
+        .output
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<u64>();
+
+    let output_sat_ranges = Self::output_sat_ranges(
+      &inputs
+        .iter()
+        .map(|outpoint| sat_ranges.get(outpoint).cloned().unwrap_or_default())
+        .collect::<Vec<Vec<(u64, u64)>>>(),
+      &unsigned_transaction
+        .output
+        .iter()
+        .map(|txout| txout.value)
+        .collect::<Vec<u64>>(),
+    );
+
+    let (txid, psbt) = if self.dry_run || self.psbt {
+      (
+        unsigned_transaction.txid(),
+        Some(wallet.unsigned_psbt(&unsigned_transaction, self.psbt.then_some(true))?),
+      )
+    } else {
+      (wallet.send_transaction(&unsigned_transaction)?, None)
+    };
+
+    Ok(Some(Box::new(Output {
+      fee,
+      outputs: pieces
+        .into_iter()
+        .zip(output_sat_ranges)
+        .zip(&unsigned_transaction.output)
+        .enumerate()
+        .map(|(vout, ((piece, sat_ranges), txout))| SplitOutput {
+          output: OutPoint {
+            txid,
+            vout: vout.try_into().unwrap(),
+          },
+          sat_ranges,
+          selection: piece.selection.map(|selection| selection.to_string()),
+          value: txout.value,
+        })
+        .collect(),
+      psbt,
+      txid,
+    })))
+  }
+
+  /// Find the wallet output containing `selection` and the offsets within it
+  /// of the first sat of the selection and of the sat after it.
+  fn locate(
+    &self,
+    selection: Selection,
+    sat_ranges: &BTreeMap<OutPoint, Vec<(u64, u64)>>,
+  ) -> Result<(OutPoint, u64, u64)> {
+    let first = match selection {
+      Selection::Range(start, _end) => start.n(),
+      Selection::Sat(sat) => sat.n(),
+    };
+
+    for (outpoint, ranges) in sat_ranges {
+      let value = ranges.iter().map(|(start, end)| end - start).sum::<u64>();
+
+      let mut offset = 0;
+
+      for (start, end) in ranges {
+        if *start <= first && first < *end {
+          let first_offset = offset + first - start;
+
+          let end_offset = match selection {
+            Selection::Range(_start, last) => {
+              ensure!(
+                last.n() <= *end,
+                "sat range `{selection}` is not contiguous within a single wallet output"
+              );
+              offset + last.n() - start
+            }
+            Selection::Sat(_sat) => {
+              (first_offset + self.postage.unwrap_or(TARGET_POSTAGE).to_sat()).min(value)
+            }
+          };
+
+          return Ok((*outpoint, first_offset, end_offset));
+        }
+
+        offset += end - start;
+      }
+    }
+
+    bail!("`{selection}` not found in wallet outputs")
+  }
+
+  /// Divide an output of `value` sats into pieces, one for each selection
+  /// and one for each gap between them. Gaps too small to be their own output
+  /// are merged into a neighboring selection, and single sats are carved with
+  /// postage up to the start of the next selection.
+  fn pieces(
+    value: u64,
+    mut selections: Vec<(u64, u64, Selection)>,
+    dust: u64,
+  ) -> Result<Vec<Piece>> {
+    selections.sort_by_key(|(start, _end, _selection)| *start);
+
+    let mut pieces = Vec::new();
+    let mut offset = 0;
+
+    for i in 0..selections.len() {
+      let (start, mut end, selection) = selections[i];
+
+      ensure!(start >= offset, "`{selection}` overlaps another selection");
+
+      if let (Selection::Sat(_), Some((next, _end, _selection))) =
+        (selection, selections.get(i + 1))
+      {
+        end = end.min(*next);
+      }
+
+      let gap = start - offset;
+
+      let leading = if gap < dust {
+        gap
+      } else {
+        pieces.push(Piece {
+          selection: None,
+          value: gap,
+        });
+        0
+      };
+
+      pieces.push(Piece {
+        selection: Some(selection),
+        value: leading + end - start,
+      });
+
+      offset = end;
+    }
+
+    let tail = value - offset;
+
+    if tail >= dust {
+      pieces.push(Piece {
+        selection: None,
+        value: tail,
+      });
+    } else {
+      pieces.last_mut().unwrap().value += tail;
+    }
+
+    for piece in &pieces {
+      if let Some(selection) = piece.selection {
+        ensure!(
+          piece.value >= dust,
+          "`{selection}` is smaller than the dust limit of {dust} sats"
+        );
+      }
+    }
+
+    Ok(pieces)
+  }
+
+  /// Whether every sat in `ranges` is common. Only the first sat of each block
+  /// is uncommon or rarer, so a range is common if its first sat is common and
+  /// its last sat was mined in the same block.
+  fn common(ranges: &[(u64, u64)]) -> bool {
+    ranges
+      .iter()
+      .all(|(start, end)| Sat(*start).common() && Sat(*start).height() == Sat(end - 1).height())
+  }
+
+  /// Assign sat ranges to outputs in order, following ordinal theory.
+  fn output_sat_ranges(inputs: &[Vec<(u64, u64)>], values: &[u64]) -> Vec<Vec<(u64, u64)>> {
+    let mut ranges = inputs.iter().flatten().copied();
+    let mut current = ranges.next();
+    let mut outputs = Vec::new();
+
+    for value in values {
+      let mut remaining = *value;
+      let mut output = Vec::new();
+
+      while remaining > 0 {
+        let Some((start, end)) = current else {
+          break;
+        };
+
+        let size = (end - start).min(remaining);
+
+        output.push((start, start + size));
+
+        remaining -= size;
+
+        current = if start + size == end {
+          ranges.next()
+        } else {
+          Some((start + size, end))
+        };
+      }
+
+      outputs.push(output);
+    }
+
+    outputs
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_selection() {
+    assert_eq!(
+      "1000".parse::<Selection>().unwrap(),
+      Selection::Sat(Sat(1000))
+    );
+    assert_eq!(
+      "1000-2000".parse::<Selection>().unwrap(),
+      Selection::Range(Sat(1000), Sat(2000))
+    );
+    assert_eq!(
+      "2000-1000".parse::<Selection>().unwrap_err().to_string(),
+      "sat range `2000-1000` is empty"
+    );
+  }
+
+  #[test]
+  fn common_ranges() {
+    assert!(Split::common(&[
+      (1, COIN_VALUE),
+      (50 * COIN_VALUE + 1, 100 * COIN_VALUE)
+    ]));
+    assert!(!Split::common(&[
+      (1, COIN_VALUE),
+      (50 * COIN_VALUE, 51 * COIN_VALUE)
+    ]));
+    assert!(!Split::common(&[(49 * COIN_VALUE, 51 * COIN_VALUE)]));
+  }
+
+  #[test]
+  fn selections_are_carved_from_gaps() {
+    assert_eq!(
+      Split::pieces(10_000, vec![(5000, 6000, Selection::Sat(Sat(5000)))], 330).unwrap(),
+      vec![
+        Piece {
+          selection: None,
+          value: 5000
+        },
+        Piece {
+          selection: Some(Selection::Sat(Sat(5000))),
+          value: 1000
+        },
+        Piece {
+          selection: None,
+          value: 4000
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn small_gaps_are_merged_into_selections() {
+    assert_eq!(
+      Split::pieces(
+        10_000,
+        vec![
+          (9000, 9800, Selection::Range(Sat(9000), Sat(9800))),
+          (100, 10_000, Selection::Sat(Sat(100))),
+        ],
+        330
+      )
+      .unwrap(),
+      vec![
+        Piece {
+          selection: Some(Selection::Sat(Sat(100))),
+          value: 9000
+        },
+        Piece {
+          selection: Some(Selection::Range(Sat(9000), Sat(9800))),
+          value: 1000
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn overlapping_selections_are_rejected() {
+    assert_eq!(
+      Split::pieces(
+        10_000,
+        vec![
+          (1000, 3000, Selection::Range(Sat(1000), Sat(3000))),
+          (2000, 4000, Selection::Range(Sat(2000), Sat(4000))),
+        ],
+        330
+      )
+      .unwrap_err()
+      .to_string(),
+      "`2000-4000` overlaps another selection"
+    );
+  }
+
+  #[test]
+  fn sat_ranges_follow_outputs() {
+    assert_eq!(
+      Split::output_sat_ranges(&[vec![(0, 10), (20, 30)], vec![(50, 60)]], &[5, 10, 10]),
+      vec![
+        vec![(0, 5)],
+        vec![(5, 10), (20, 25)],
+        vec![(25, 30), (50, 55)],
+      ]
+    );
+  }
+}
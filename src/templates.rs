@@ -2,14 +2,17 @@ use {super::*, boilerplate::Boilerplate};
 
 pub(crate) use {
   crate::subcommand::server::ServerConfig,
+  address::AddressHtml,
   block::BlockHtml,
   children::ChildrenHtml,
   clock::ClockSvg,
   collections::CollectionsHtml,
+  content_hash::ContentHashHtml,
   home::HomeHtml,
   iframe::Iframe,
   input::InputHtml,
   inscription::InscriptionHtml,
+  inscription_search::InscriptionSearchHtml,
   inscriptions::InscriptionsHtml,
   inscriptions_block::InscriptionsBlockHtml,
   metadata::MetadataHtml,
@@ -25,19 +28,23 @@ pub(crate) use {
 };
 
 pub use {
-  blocks::BlocksHtml, rune::RuneHtml, runes::RunesHtml, status::StatusHtml,
-  transaction::TransactionHtml,
+  blocks::BlocksHtml, collection_verification::CollectionVerificationHtml, rune::RuneHtml,
+  runes::RunesHtml, sat_trace::SatTraceHtml, status::StatusHtml, transaction::TransactionHtml,
 };
 
+mod address;
 pub mod block;
 pub mod blocks;
 mod children;
 mod clock;
+pub mod collection_verification;
 pub mod collections;
+mod content_hash;
 mod home;
 mod iframe;
 mod input;
 pub mod inscription;
+mod inscription_search;
 pub mod inscriptions;
 mod inscriptions_block;
 mod metadata;
@@ -49,6 +56,7 @@ mod rare;
 pub mod rune;
 pub mod runes;
 pub mod sat;
+pub mod sat_trace;
 pub mod status;
 pub mod transaction;
 
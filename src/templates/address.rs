@@ -0,0 +1,76 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct AddressHtml {
+  pub(crate) address: Address,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) outputs: Vec<OutPoint>,
+  pub(crate) runes_balances: Vec<(SpacedRune, Pile)>,
+  pub(crate) sat_balance: u64,
+}
+
+impl PageContent for AddressHtml {
+  fn title(&self) -> String {
+    format!("Address {}", self.address)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn address_html() {
+    assert_regex_match!(
+      AddressHtml {
+        address: address(),
+        inscriptions: vec![inscription_id(1)],
+        outputs: vec![outpoint(1), outpoint(2)],
+        runes_balances: vec![(
+          SpacedRune {
+            rune: Rune(26),
+            spacers: 0,
+          },
+          Pile {
+            amount: 1000,
+            divisibility: 1,
+            symbol: Some('%'),
+          },
+        )],
+        sat_balance: 99,
+      },
+      "
+        <h1>Address <span class=monospace>bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4</span></h1>
+        <dl>
+          <dt>sat balance</dt>
+          <dd>99</dd>
+          <dt>inscriptions</dt>
+          <dd class=thumbnails>
+            <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          </dd>
+          <dt>runes</dt>
+          <dd>
+            <table>
+              <tr>
+                <th>rune</th>
+                <th>balance</th>
+              </tr>
+              <tr>
+                <td><a href=/rune/AA>AA</a></td>
+                <td>100\u{A0}%</td>
+              </tr>
+            </table>
+          </dd>
+          <dt>outputs</dt>
+          <dd>
+            <ul class=monospace>
+              <li><a href=/output/1{64}:1>1{64}:1</a></li>
+              <li><a href=/output/2{64}:2>2{64}:2</a></li>
+            </ul>
+          </dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
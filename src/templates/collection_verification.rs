@@ -0,0 +1,82 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CollectionVerificationHtml {
+  pub manifest: InscriptionId,
+  pub mismatched: Vec<InscriptionId>,
+  pub missing: Vec<usize>,
+  pub parent: InscriptionId,
+  pub parent_number: i32,
+  pub verified: Vec<InscriptionId>,
+}
+
+impl PageContent for CollectionVerificationHtml {
+  fn title(&self) -> String {
+    format!("Inscription {} Collection", self.parent_number)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn verified() {
+    assert_regex_match!(
+      CollectionVerificationHtml {
+        manifest: inscription_id(2),
+        mismatched: Vec::new(),
+        missing: Vec::new(),
+        parent: inscription_id(1),
+        parent_number: 0,
+        verified: vec![inscription_id(3), inscription_id(4)],
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <dl>
+          <dt>manifest</dt>
+          <dd><a class=monospace href=/inscription/2{64}i2>2{64}i2</a></dd>
+          <dt>items</dt>
+          <dd>2</dd>
+          <dt>verified</dt>
+          <dd>2</dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn with_mismatched_and_missing() {
+    assert_regex_match!(
+      CollectionVerificationHtml {
+        manifest: inscription_id(2),
+        mismatched: vec![inscription_id(4)],
+        missing: vec![1, 2],
+        parent: inscription_id(1),
+        parent_number: 0,
+        verified: vec![inscription_id(3)],
+      },
+      "
+        <h1><a href=/inscription/1{64}i1>Inscription 0</a> Collection</h1>
+        <dl>
+          <dt>manifest</dt>
+          <dd><a class=monospace href=/inscription/2{64}i2>2{64}i2</a></dd>
+          <dt>items</dt>
+          <dd>3</dd>
+          <dt>verified</dt>
+          <dd>1</dd>
+          <dt>mismatched</dt>
+          <dd>
+            <div class=thumbnails>
+              <a href=/inscription/4{64}i4><iframe .* src=/preview/4{64}i4></iframe></a>
+            </div>
+          </dd>
+          <dt>missing items</dt>
+          <dd>1 2</dd>
+        </dl>
+      "
+      .unindent()
+    );
+  }
+}
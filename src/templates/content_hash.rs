@@ -0,0 +1,63 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct ContentHashHtml {
+  pub(crate) content_hash: sha256::Hash,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) prev_page: Option<usize>,
+  pub(crate) next_page: Option<usize>,
+}
+
+impl PageContent for ContentHashHtml {
+  fn title(&self) -> String {
+    format!("Content Hash {}", self.content_hash)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_regex_match!(
+      ContentHashHtml {
+        content_hash: sha256::Hash::all_zeros(),
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        prev_page: Some(1),
+        next_page: Some(3),
+      },
+      "
+        <h1>Content Hash</h1>
+        <p class=monospace>0{64}</p>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        <div class=center>
+          <a class=prev href=/content-hash/0{64}/1>prev</a>
+          <a class=next href=/content-hash/0{64}/3>next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn without_inscriptions() {
+    assert_regex_match!(
+      ContentHashHtml {
+        content_hash: sha256::Hash::all_zeros(),
+        inscriptions: Vec::new(),
+        prev_page: None,
+        next_page: None,
+      },
+      "
+        <h1>Content Hash</h1>
+        <p class=monospace>0{64}</p>
+        <h3>No inscriptions</h3>
+      "
+      .unindent()
+    );
+  }
+}
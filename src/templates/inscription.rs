@@ -5,7 +5,9 @@ pub(crate) struct InscriptionHtml {
   pub(crate) chain: Chain,
   pub(crate) charms: u16,
   pub(crate) children: Vec<InscriptionId>,
+  pub(crate) content_hash: Option<sha256::Hash>,
   pub(crate) fee: u64,
+  pub(crate) first_inscribed: Option<(InscriptionId, i32)>,
   pub(crate) height: u32,
   pub(crate) inscription: Inscription,
   pub(crate) id: InscriptionId,
@@ -18,6 +20,7 @@ pub(crate) struct InscriptionHtml {
   pub(crate) sat: Option<Sat>,
   pub(crate) satpoint: SatPoint,
   pub(crate) timestamp: DateTime<Utc>,
+  pub(crate) transfers: Option<(Vec<api::InscriptionTransfer>, bool)>,
 }
 
 impl PageContent for InscriptionHtml {
@@ -440,4 +443,44 @@ mod tests {
       .unindent()
     );
   }
+
+  #[test]
+  fn with_paginated_transfers() {
+    assert_regex_match!(
+      InscriptionHtml {
+        fee: 1,
+        inscription: inscription("text/plain;charset=utf-8", "HELLOWORLD"),
+        id: inscription_id(1),
+        number: 1,
+        satpoint: satpoint(1, 0),
+        transfers: Some((
+          vec![api::InscriptionTransfer {
+            height: 2,
+            satpoint: satpoint(1, 0),
+            txid: txid(1),
+          }],
+          true,
+        )),
+        ..default()
+      },
+      "
+        <h1>Inscription 1</h1>
+        .*
+        <dl>
+          .*
+          <dt>transfers</dt>
+          <dd>
+            <ol class=monospace>
+              <li><a href=/block/2>2</a> <a href=/tx/1{64}>1{64}:1:0</a></li>
+            </ol>
+            <div class=center>
+              <a href=/inscription/1{64}i1/history>all transfers</a>
+            </div>
+          </dd>
+          .*
+        </dl>
+      "
+      .unindent()
+    );
+  }
 }
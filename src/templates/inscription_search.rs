@@ -0,0 +1,69 @@
+use super::*;
+
+#[derive(Boilerplate)]
+pub(crate) struct InscriptionSearchHtml {
+  pub(crate) query: String,
+  pub(crate) inscriptions: Vec<InscriptionId>,
+  pub(crate) prev_page: Option<usize>,
+  pub(crate) next_page: Option<usize>,
+}
+
+impl PageContent for InscriptionSearchHtml {
+  fn title(&self) -> String {
+    format!("Inscription Search: {}", self.query)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn with_prev_and_next() {
+    assert_regex_match!(
+      InscriptionSearchHtml {
+        query: "foo bar".into(),
+        inscriptions: vec![inscription_id(1), inscription_id(2)],
+        prev_page: Some(1),
+        next_page: Some(3),
+      },
+      "
+        <h1>Inscription Search</h1>
+        <form action=/inscriptions/search method=get>
+          <input .* name=query spellcheck=false value=\"foo bar\">
+          <input type=submit value=Search>
+        </form>
+        <div class=thumbnails>
+          <a href=/inscription/1{64}i1><iframe .* src=/preview/1{64}i1></iframe></a>
+          <a href=/inscription/2{64}i2><iframe .* src=/preview/2{64}i2></iframe></a>
+        </div>
+        <div class=center>
+          <a class=prev href=\"/inscriptions/search/1\\?query=foo%20bar\">prev</a>
+          <a class=next href=\"/inscriptions/search/3\\?query=foo%20bar\">next</a>
+        </div>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn without_inscriptions() {
+    assert_regex_match!(
+      InscriptionSearchHtml {
+        query: "<foo>".into(),
+        inscriptions: Vec::new(),
+        prev_page: None,
+        next_page: None,
+      },
+      "
+        <h1>Inscription Search</h1>
+        <form action=/inscriptions/search method=get>
+          <input .* value=\"&lt;foo&gt;\">
+          <input type=submit value=Search>
+        </form>
+        <h3>No inscriptions</h3>
+      "
+      .unindent()
+    );
+  }
+}
@@ -2,10 +2,13 @@ use super::*;
 
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct RuneHtml {
+  pub circulating: u128,
   pub entry: RuneEntry,
+  pub holders: u64,
   pub id: RuneId,
   pub mintable: bool,
   pub parent: Option<InscriptionId>,
+  pub top_holders: Vec<(OutPoint, u128)>,
 }
 
 impl PageContent for RuneHtml {
@@ -22,6 +25,7 @@ mod tests {
   fn display() {
     assert_regex_match!(
       RuneHtml {
+        circulating: 100000000,
         entry: RuneEntry {
           block: 1,
           burned: 123456789123456789,
@@ -44,12 +48,14 @@ mod tests {
           timestamp: 0,
           turbo: true,
         },
+        holders: 2,
         id: RuneId { block: 10, tx: 9 },
         mintable: true,
         parent: Some(InscriptionId {
           txid: Txid::all_zeros(),
           index: 0,
         }),
+        top_holders: vec![(OutPoint::null(), 1000000000)],
       },
       "<h1>B•CGDENLQRQWDSLRUGSNLBTMFIJAV</h1>
 .*<a href=/inscription/.*<iframe .* src=/preview/0{64}i0></iframe></a>.*
@@ -89,6 +95,16 @@ mod tests {
   <dd>0.123456789\u{A0}%</dd>
   <dt>burned</dt>
   <dd>123456789.123456789\u{A0}%</dd>
+  <dt>circulating</dt>
+  <dd>0.1\u{A0}%</dd>
+  <dt>holders</dt>
+  <dd>2</dd>
+  <dt>top holders</dt>
+  <dd>
+    <ul class=monospace>
+      <li><a href=/output/0{64}:4294967295>0{64}:4294967295</a> 1\u{A0}%</li>
+    </ul>
+  </dd>
   <dt>divisibility</dt>
   <dd>9</dd>
   <dt>symbol</dt>
@@ -108,6 +124,7 @@ mod tests {
   fn display_no_mint() {
     assert_regex_match!(
       RuneHtml {
+        circulating: 0,
         entry: RuneEntry {
           block: 0,
           burned: 123456789123456789,
@@ -125,9 +142,11 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: 0,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
+        top_holders: Vec::new(),
       },
       "<h1>B•CGDENLQRQWDSLRUGSNLBTMFIJAV</h1>
 <dl>.*
@@ -142,6 +161,7 @@ mod tests {
   fn display_no_turbo() {
     assert_regex_match!(
       RuneHtml {
+        circulating: 0,
         entry: RuneEntry {
           block: 0,
           burned: 123456789123456789,
@@ -159,9 +179,11 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: 0,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
+        top_holders: Vec::new(),
       },
       "<h1>B•CGDENLQRQWDSLRUGSNLBTMFIJAV</h1>
 <dl>.*
@@ -176,6 +198,7 @@ mod tests {
   fn display_empty_mint() {
     assert_regex_match!(
       RuneHtml {
+        circulating: 0,
         entry: RuneEntry {
           block: 0,
           burned: 123456789123456789,
@@ -198,9 +221,11 @@ mod tests {
           timestamp: 0,
           turbo: false,
         },
+        holders: 0,
         id: RuneId { block: 10, tx: 9 },
         mintable: false,
         parent: None,
+        top_holders: Vec::new(),
       },
       "<h1>B•CGDENLQRQWDSLRUGSNLBTMFIJAV</h1>
 <dl>.*
@@ -3,6 +3,7 @@ use super::*;
 #[derive(Boilerplate)]
 pub(crate) struct SatHtml {
   pub(crate) blocktime: Blocktime,
+  pub(crate) chain: Chain,
   pub(crate) inscriptions: Vec<InscriptionId>,
   pub(crate) sat: Sat,
   pub(crate) satpoint: Option<SatPoint>,
@@ -22,6 +23,7 @@ mod tests {
   fn first() {
     assert_regex_match!(
       SatHtml {
+        chain: Chain::Mainnet,
         sat: Sat(0),
         satpoint: None,
         blocktime: Blocktime::confirmed(0),
@@ -46,6 +48,13 @@ mod tests {
             <span title=coin>🪙</span>
             <span title=mythic>🎃</span>
           </dd>
+          <dt>satributes</dt>
+          <dd>
+            <span class=satribute>coin</span>
+            <span class=satribute>mythic</span>
+            <span class=satribute>palindrome</span>
+            <span class=satribute>vintage</span>
+          </dd>
         </dl>
         .*
         prev
@@ -60,6 +69,7 @@ mod tests {
   fn last() {
     assert_regex_match!(
       SatHtml {
+        chain: Chain::Mainnet,
         sat: Sat(2099999997689999),
         satpoint: None,
         blocktime: Blocktime::confirmed(0),
@@ -83,6 +93,10 @@ mod tests {
           <dd>
             <span title=uncommon>🌱</span>
           </dd>
+          <dt>satributes</dt>
+          <dd>
+            <span class=satribute>uncommon</span>
+          </dd>
         </dl>
         .*
         <a class=prev href=/sat/2099999997689998>prev</a>
@@ -97,6 +111,7 @@ mod tests {
   fn sat_with_next_and_prev() {
     assert_regex_match!(
       SatHtml {
+        chain: Chain::Mainnet,
         sat: Sat(1),
         satpoint: None,
         blocktime: Blocktime::confirmed(0),
@@ -110,6 +125,7 @@ mod tests {
   fn sat_with_inscription() {
     assert_regex_match!(
       SatHtml {
+        chain: Chain::Mainnet,
         sat: Sat(0),
         satpoint: None,
         blocktime: Blocktime::confirmed(0),
@@ -131,6 +147,7 @@ mod tests {
   fn sat_with_reinscription() {
     assert_regex_match!(
       SatHtml {
+        chain: Chain::Mainnet,
         sat: Sat(0),
         satpoint: None,
         blocktime: Blocktime::confirmed(0),
@@ -153,6 +170,7 @@ mod tests {
   fn last_sat_next_link_is_disabled() {
     assert_regex_match!(
       SatHtml {
+        chain: Chain::Mainnet,
         sat: Sat::LAST,
         satpoint: None,
         blocktime: Blocktime::confirmed(0),
@@ -166,6 +184,7 @@ mod tests {
   fn sat_with_satpoint() {
     assert_regex_match!(
       SatHtml {
+        chain: Chain::Mainnet,
         sat: Sat(0),
         satpoint: Some(satpoint(1, 0)),
         blocktime: Blocktime::confirmed(0),
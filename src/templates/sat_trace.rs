@@ -0,0 +1,64 @@
+use super::*;
+
+#[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SatTraceHtml {
+  pub sat: Sat,
+  pub locations: Vec<api::SatLocation>,
+  pub lost: bool,
+}
+
+impl PageContent for SatTraceHtml {
+  fn title(&self) -> String {
+    format!("Sat {} Trace", self.sat)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn with_locations() {
+    assert_regex_match!(
+      SatTraceHtml {
+        sat: Sat(1),
+        locations: vec![
+          api::SatLocation {
+            height: 0,
+            satpoint: satpoint(1, 1),
+          },
+          api::SatLocation {
+            height: 2,
+            satpoint: satpoint(2, 0),
+          },
+        ],
+        lost: false,
+      },
+      "
+        <h1>Sat 1 Trace</h1>
+        <ol class=monospace>
+          <li><a href=/block/0>0</a> <a href=/output/1{64}:1>1{64}:1:1</a></li>
+          <li><a href=/block/2>2</a> <a href=/output/2{64}:2>2{64}:2:0</a></li>
+        </ol>
+      "
+      .unindent()
+    );
+  }
+
+  #[test]
+  fn lost() {
+    assert_regex_match!(
+      SatTraceHtml {
+        sat: Sat(1),
+        locations: Vec::new(),
+        lost: true,
+      },
+      "
+        <h1>Sat 1 Trace</h1>
+        <h3>No outputs</h3>
+        <p>lost</p>
+      "
+      .unindent()
+    );
+  }
+}
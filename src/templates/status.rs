@@ -2,6 +2,7 @@ use super::*;
 
 #[derive(Boilerplate, Debug, PartialEq, Serialize, Deserialize)]
 pub struct StatusHtml {
+  pub address_index: bool,
   pub blessed_inscriptions: u64,
   pub chain: Chain,
   pub content_type_counts: Vec<(Option<Vec<u8>>, u64)>,
@@ -2,18 +2,29 @@ use {
   super::*,
   base64::{self, Engine},
   batch::ParentInfo,
-  bitcoin::secp256k1::{All, Secp256k1},
+  bitcoin::secp256k1::{All, KeyPair, Secp256k1},
   bitcoin::{
-    bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, Fingerprint},
+    bip32::{ChildNumber, DerivationPath, ExtendedPrivKey},
     psbt::Psbt,
+    sighash::{EcdsaSighashType, TapSighashType},
+    PrivateKey,
+  },
+  bitcoincore_rpc::{
+    bitcoincore_rpc_json::{
+      AddressType, Descriptor, ImportDescriptors, ListDescriptorsResult, SignRawTransactionInput,
+      Timestamp,
+    },
+    RawTx,
   },
-  bitcoincore_rpc::bitcoincore_rpc_json::{Descriptor, ImportDescriptors, Timestamp},
   entry::{EtchingEntry, EtchingEntryValue},
   fee_rate::FeeRate,
   index::entry::Entry,
   indicatif::{ProgressBar, ProgressStyle},
+  keychain::Keychain,
   log::log_enabled,
-  miniscript::descriptor::{DescriptorSecretKey, DescriptorXKey, Wildcard},
+  miniscript::descriptor::{
+    DescriptorPublicKey, DescriptorSecretKey, DescriptorXKey, KeyMap, Wildcard,
+  },
   redb::{Database, DatabaseError, ReadableTable, RepairSession, StorageError, TableDefinition},
   reqwest::header,
   std::sync::Once,
@@ -22,17 +33,22 @@ use {
 
 pub mod batch;
 pub mod entry;
+mod keychain;
 pub mod transaction_builder;
 pub mod wallet_constructor;
 
-const SCHEMA_VERSION: u64 = 1;
+const SCHEMA_VERSION: u64 = 2;
 
+define_table! { CHANGE_TO_DESCRIPTOR, bool, &str }
 define_table! { RUNE_TO_ETCHING, u128, EtchingEntryValue }
 define_table! { STATISTICS, u64, u64 }
+define_table! { TXID_TO_PENDING_TRANSACTION, &[u8; 32], &[u8] }
 
 #[derive(Copy, Clone)]
 pub(crate) enum Statistic {
   Schema = 0,
+  NextReceiveIndex = 1,
+  NextChangeIndex = 2,
 }
 
 impl Statistic {
@@ -58,8 +74,12 @@ pub(crate) struct Wallet {
   inscription_info: BTreeMap<InscriptionId, api::Inscription>,
   output_info: BTreeMap<OutPoint, api::Output>,
   inscriptions: BTreeMap<SatPoint, Vec<InscriptionId>>,
+  keychain: Option<Keychain>,
   locked_utxos: BTreeMap<OutPoint, TxOut>,
+  name: String,
   settings: Settings,
+  spending_descriptor: Option<miniscript::descriptor::Descriptor<DescriptorPublicKey>>,
+  watch_only: bool,
 }
 
 impl Wallet {
@@ -217,6 +237,23 @@ impl Wallet {
     Ok(runic_outputs)
   }
 
+  /// Outputs containing sats rarer than common. Only the first sat of a range
+  /// can be rare, so only range starts need to be checked.
+  pub(crate) fn get_rare_outputs(&self) -> Result<BTreeSet<OutPoint>> {
+    Ok(
+      self
+        .get_output_sat_ranges()?
+        .into_iter()
+        .filter(|(_output, sat_ranges)| {
+          sat_ranges
+            .iter()
+            .any(|(start, _end)| Sat(*start).rarity() > Rarity::Common)
+        })
+        .map(|(output, _sat_ranges)| output)
+        .collect(),
+    )
+  }
+
   pub(crate) fn get_runes_balances_for_output(
     &self,
     output: &OutPoint,
@@ -271,15 +308,454 @@ impl Wallet {
   }
 
   pub(crate) fn get_change_address(&self) -> Result<Address> {
+    if let Some(keychain) = &self.keychain {
+      return keychain.next_address(&self.database, true);
+    }
+
     Ok(
       self
         .bitcoin_client
-        .call::<Address<NetworkUnchecked>>("getrawchangeaddress", &["bech32m".into()])
+        .call::<Address<NetworkUnchecked>>(
+          "getrawchangeaddress",
+          &[serde_json::to_value(self.address_type())?],
+        )
         .context("could not get change addresses from wallet")?
         .require_network(self.chain().network())?,
     )
   }
 
+  pub(crate) fn get_receive_address(&self) -> Result<Address<NetworkUnchecked>> {
+    if let Some(keychain) = &self.keychain {
+      return Ok(
+        keychain
+          .next_address(&self.database, false)?
+          .to_string()
+          .parse()?,
+      );
+    }
+
+    Ok(
+      self
+        .bitcoin_client
+        .get_new_address(None, Some(self.address_type()))?,
+    )
+  }
+
+  /// Commit transaction recovery keys backed up to the bitcoind wallet with
+  /// `rawtr` descriptors.
+  pub(crate) fn recovery_keys(&self) -> Result<Vec<KeyPair>> {
+    let secp256k1 = Secp256k1::new();
+
+    self
+      .dump()?
+      .descriptors
+      .iter()
+      .filter_map(|descriptor| {
+        descriptor
+          .desc
+          .strip_prefix("rawtr(")
+          .and_then(|desc| desc.split(')').next())
+      })
+      .map(|wif| {
+        Ok(KeyPair::from_secret_key(
+          &secp256k1,
+          &PrivateKey::from_wif(wif)?.inner,
+        ))
+      })
+      .collect()
+  }
+
+  /// Inscriptions, runes, and spent status of any output known to the `ord`
+  /// server, not just those belonging to the wallet.
+  pub(crate) fn get_output(&self, outpoint: OutPoint) -> Result<api::Output> {
+    let response = self
+      .ord_client
+      .get(self.rpc_url.join(&format!("/output/{outpoint}")).unwrap())
+      .send()?;
+
+    if !response.status().is_success() {
+      bail!("output {outpoint} not found");
+    }
+
+    Ok(serde_json::from_str(&response.text()?)?)
+  }
+
+  pub(crate) fn get_prevout(&self, outpoint: OutPoint) -> Result<TxOut> {
+    self
+      .bitcoin_client
+      .get_raw_transaction(&outpoint.txid, None)?
+      .output
+      .into_iter()
+      .nth(outpoint.vout.try_into().unwrap())
+      .ok_or_else(|| anyhow!("output {outpoint} not found"))
+  }
+
+  pub(crate) fn get_prevouts(
+    &self,
+    transaction: &Transaction,
+  ) -> Result<BTreeMap<OutPoint, TxOut>> {
+    transaction
+      .input
+      .iter()
+      .map(|txin| {
+        Ok((
+          txin.previous_output,
+          self.get_prevout(txin.previous_output)?,
+        ))
+      })
+      .collect()
+  }
+
+  pub(crate) fn get_fee(&self, transaction: &Transaction) -> Result<u64> {
+    Ok(Self::fee(transaction, &self.get_prevouts(transaction)?))
+  }
+
+  pub(crate) fn fee(transaction: &Transaction, prevouts: &BTreeMap<OutPoint, TxOut>) -> u64 {
+    transaction
+      .input
+      .iter()
+      .map(|txin| prevouts[&txin.previous_output].value)
+      .sum::<u64>()
+      - transaction
+        .output
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<u64>()
+  }
+
+  /// Whether outputs paying to `script_pubkey` belong to the wallet.
+  pub(crate) fn is_mine(&self, script_pubkey: &Script) -> Result<bool> {
+    if let Some(keychain) = &self.keychain {
+      return Ok(keychain.is_mine(script_pubkey));
+    }
+
+    let Ok(address) = self.chain().address_from_script(script_pubkey) else {
+      return Ok(false);
+    };
+
+    #[derive(Deserialize)]
+    struct AddressInfo {
+      ismine: bool,
+    }
+
+    Ok(
+      self
+        .bitcoin_client
+        .call::<AddressInfo>("getaddressinfo", &[address.to_string().into()])?
+        .ismine,
+    )
+  }
+
+  /// Whether the wallet's keys are kept in the wallet database instead of a
+  /// bitcoind wallet.
+  pub(crate) fn is_native(&self) -> bool {
+    self.keychain.is_some()
+  }
+
+  pub(crate) fn check_native_support(&self, feature: &str) -> Result {
+    ensure!(
+      !self.is_native(),
+      "{feature} is not supported by native wallets"
+    );
+
+    Ok(())
+  }
+
+  /// Sign the wallet's inputs to `transaction`. `prevouts` contains outputs
+  /// being spent that are not yet known to the wallet, such as those of an
+  /// unbroadcast commit transaction.
+  pub(crate) fn sign_transaction(
+    &self,
+    transaction: &Transaction,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+  ) -> Result<Transaction> {
+    if let Some(keychain) = &self.keychain {
+      let mut outputs = self.utxos.clone();
+      outputs.extend(prevouts.clone());
+      return keychain.sign(transaction, &outputs, TapSighashType::Default);
+    }
+
+    let result = self.bitcoin_client.sign_raw_transaction_with_wallet(
+      transaction,
+      (!prevouts.is_empty())
+        .then(|| {
+          prevouts
+            .iter()
+            .map(|(outpoint, output)| SignRawTransactionInput {
+              txid: outpoint.txid,
+              vout: outpoint.vout,
+              script_pub_key: output.script_pubkey.clone(),
+              redeem_script: None,
+              amount: Some(Amount::from_sat(output.value)),
+            })
+            .collect::<Vec<SignRawTransactionInput>>()
+        })
+        .as_deref(),
+      None,
+    )?;
+
+    ensure!(
+      result.complete,
+      format!("Failed to sign transaction: {:?}", result.errors)
+    );
+
+    Ok(consensus::encode::deserialize(&result.hex)?)
+  }
+
+  /// Size of `transaction` once its inputs, which must all spend wallet
+  /// outputs, are signed.
+  pub(crate) fn estimate_vsize(&self, transaction: &Transaction) -> usize {
+    let mut transaction = transaction.clone();
+
+    for txin in &mut transaction.input {
+      txin.witness = TransactionBuilder::dummy_witness(self.satisfaction_weight());
+    }
+
+    transaction.vsize()
+  }
+
+  pub(crate) fn estimate_fee(&self, transaction: &Transaction, fee_rate: FeeRate) -> Amount {
+    fee_rate.fee(self.estimate_vsize(transaction))
+  }
+
+  /// Append cardinal inputs, largest first, and a change output, until
+  /// `transaction` pays the fee returned by `fee`. Outputs in `excluded` are
+  /// not spent, and the values of inputs not in the wallet are taken from
+  /// `prevouts`, to which added inputs are also recorded. If `postage` is
+  /// given, a change output of at least `postage` sats is always added,
+  /// otherwise change is only added if it would not be dust. Returns the fee
+  /// paid.
+  pub(crate) fn fund_transaction(
+    &self,
+    transaction: &mut Transaction,
+    prevouts: &mut BTreeMap<OutPoint, TxOut>,
+    excluded: &BTreeSet<OutPoint>,
+    postage: Option<u64>,
+    fee: impl Fn(&Transaction) -> u64,
+  ) -> Result<u64> {
+    let inscribed = self
+      .inscriptions()
+      .keys()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
+    let runic = self.get_runic_outputs()?;
+
+    let mut cardinals = self
+      .utxos()
+      .iter()
+      .filter(|(outpoint, _txout)| {
+        !excluded.contains(outpoint)
+          && !transaction
+            .input
+            .iter()
+            .any(|txin| txin.previous_output == **outpoint)
+          && !inscribed.contains(outpoint)
+          && !runic.contains(outpoint)
+          && !self.locked_utxos().contains_key(outpoint)
+      })
+      .map(|(outpoint, txout)| (txout.value, *outpoint, txout.clone()))
+      .collect::<Vec<(u64, OutPoint, TxOut)>>();
+
+    cardinals.sort_by(|a, b| b.cmp(a));
+
+    let mut cardinals = cardinals.into_iter();
+
+    let change = TxOut {
+      script_pubkey: self.get_change_address()?.script_pubkey(),
+      value: 0,
+    };
+
+    let minimum_change = postage
+      .unwrap_or_default()
+      .max(change.script_pubkey.dust_value().to_sat());
+
+    loop {
+      let input_value = transaction
+        .input
+        .iter()
+        .map(|txin| {
+          prevouts
+            .get(&txin.previous_output)
+            .or_else(|| self.utxos.get(&txin.previous_output))
+            .map(|txout| txout.value)
+            .ok_or_else(|| anyhow!("unknown input {}", txin.previous_output))
+        })
+        .sum::<Result<u64>>()?;
+
+      let output_value = transaction
+        .output
+        .iter()
+        .map(|txout| txout.value)
+        .sum::<u64>();
+
+      let mut with_change = transaction.clone();
+      with_change.output.push(change.clone());
+
+      let required = fee(&with_change);
+
+      if input_value >= output_value + required + minimum_change {
+        with_change.output.last_mut().unwrap().value = input_value - output_value - required;
+        *transaction = with_change;
+        return Ok(required);
+      }
+
+      if postage.is_none() && input_value >= output_value + fee(transaction) {
+        return Ok(input_value - output_value);
+      }
+
+      let (_value, outpoint, txout) = cardinals
+        .next()
+        .ok_or(transaction_builder::Error::NotEnoughCardinalUtxos)?;
+
+      transaction.input.push(TxIn {
+        previous_output: outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      });
+
+      prevouts.insert(outpoint, txout);
+    }
+  }
+
+  /// Sign a transaction spending only the wallet's outputs and broadcast it.
+  pub(crate) fn send_transaction(&self, unsigned_transaction: &Transaction) -> Result<Txid> {
+    let signed_transaction = self.sign_transaction(unsigned_transaction, &BTreeMap::new())?;
+
+    self.broadcast(&signed_transaction)
+  }
+
+  /// Broadcast a signed transaction. Native wallets only learn about outputs
+  /// from the `ord` server once they are confirmed, so they record the
+  /// transaction until then, to avoid spending its inputs again.
+  pub(crate) fn broadcast(&self, transaction: impl RawTx) -> Result<Txid> {
+    let hex = transaction.raw_hex();
+
+    let txid = self.bitcoin_client.send_raw_transaction(hex.as_str())?;
+
+    if self.is_native() {
+      let wtx = self.database.begin_write()?;
+
+      wtx
+        .open_table(TXID_TO_PENDING_TRANSACTION)?
+        .insert(&txid.to_byte_array(), hex::decode(hex)?.as_slice())?;
+
+      wtx.commit()?;
+    }
+
+    Ok(txid)
+  }
+
+  pub(crate) fn pending_transactions(database: &Database) -> Result<Vec<Transaction>> {
+    database
+      .begin_read()?
+      .open_table(TXID_TO_PENDING_TRANSACTION)?
+      .iter()?
+      .map(|result| Ok(consensus::encode::deserialize(result?.1.value())?))
+      .collect()
+  }
+
+  pub(crate) fn clear_pending_transaction(database: &Database, txid: Txid) -> Result {
+    let wtx = database.begin_write()?;
+
+    wtx
+      .open_table(TXID_TO_PENDING_TRANSACTION)?
+      .remove(&txid.to_byte_array())?;
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  /// Sign the wallet's inputs to `transaction` with
+  /// `SIGHASH_SINGLE|ANYONECANPAY`, so that each signature only commits to
+  /// its own input and the output with the same index, and return the signed
+  /// inputs as a finalized PSBT that others may add inputs and outputs to.
+  pub(crate) fn sign_offer(&self, transaction: &Transaction) -> Result<String> {
+    let mut psbt = Psbt::from_unsigned_tx(transaction.clone())?;
+
+    let Some(keychain) = &self.keychain else {
+      let result = self.bitcoin_client.wallet_process_psbt(
+        &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+        Some(true),
+        Some(EcdsaSighashType::SinglePlusAnyoneCanPay.into()),
+        None,
+      )?;
+
+      ensure!(result.complete, "failed to sign offer PSBT");
+
+      return Ok(result.psbt);
+    };
+
+    let signed = keychain.sign(
+      transaction,
+      &self.utxos,
+      TapSighashType::SinglePlusAnyoneCanPay,
+    )?;
+
+    for ((input, txin), signed) in psbt
+      .inputs
+      .iter_mut()
+      .zip(&transaction.input)
+      .zip(signed.input)
+    {
+      input.witness_utxo = self.utxos.get(&txin.previous_output).cloned();
+      input.final_script_witness = Some(signed.witness);
+    }
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(psbt.serialize()))
+  }
+
+  /// Build an unsigned PSBT spending the wallet's outputs. Bitcoind wallets
+  /// add their key origins and UTXO information, native wallets only the
+  /// UTXO information.
+  pub(crate) fn unsigned_psbt(
+    &self,
+    transaction: &Transaction,
+    bip32derivs: Option<bool>,
+  ) -> Result<String> {
+    self.unsigned_psbt_with_prevouts(transaction, bip32derivs, &BTreeMap::new())
+  }
+
+  /// Create an unsigned PSBT whose inputs may spend `prevouts`, outputs of
+  /// earlier transactions in a chain which haven't been broadcast, and so
+  /// aren't known to the wallet.
+  pub(crate) fn unsigned_psbt_with_prevouts(
+    &self,
+    transaction: &Transaction,
+    bip32derivs: Option<bool>,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+  ) -> Result<String> {
+    let mut psbt = Psbt::from_unsigned_tx(transaction.clone())?;
+
+    for (input, txin) in psbt.inputs.iter_mut().zip(&transaction.input) {
+      input.witness_utxo = prevouts.get(&txin.previous_output).cloned();
+    }
+
+    if self.keychain.is_none() {
+      return Ok(
+        self
+          .bitcoin_client
+          .wallet_process_psbt(
+            &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+            Some(false),
+            None,
+            bip32derivs,
+          )?
+          .psbt,
+      );
+    }
+
+    for (input, txin) in psbt.inputs.iter_mut().zip(&transaction.input) {
+      if input.witness_utxo.is_none() {
+        input.witness_utxo = self.utxos.get(&txin.previous_output).cloned();
+      }
+    }
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(psbt.serialize()))
+  }
+
   pub(crate) fn has_sat_index(&self) -> bool {
     self.has_sat_index
   }
@@ -288,6 +764,77 @@ impl Wallet {
     self.has_rune_index
   }
 
+  pub(crate) fn is_watch_only(&self) -> bool {
+    self.watch_only
+  }
+
+  pub(crate) fn check_can_sign(&self, unsigned: bool) -> Result {
+    ensure!(
+      unsigned || !self.watch_only,
+      "watch-only wallets cannot sign transactions, use `--psbt` to create unsigned PSBTs"
+    );
+
+    ensure!(
+      unsigned || self.is_single_key(),
+      "multisig and miniscript wallets must be signed by their cosigners, use `--psbt` to create unsigned PSBTs"
+    );
+
+    Ok(())
+  }
+
+  /// Whether the wallet's outputs can be spent with a single signature from
+  /// the wallet, as opposed to requiring a script path or cosigners.
+  pub(crate) fn is_single_key(&self) -> bool {
+    match &self.spending_descriptor {
+      Some(miniscript::descriptor::Descriptor::Tr(tr)) => tr.taptree().is_none(),
+      Some(_) => false,
+      None => true,
+    }
+  }
+
+  pub(crate) fn dump(&self) -> Result<ListDescriptorsResult> {
+    let Some(keychain) = &self.keychain else {
+      return Ok(
+        self
+          .bitcoin_client
+          .list_descriptors(Some(!self.watch_only))?,
+      );
+    };
+
+    Ok(ListDescriptorsResult {
+      wallet_name: self.name.clone(),
+      descriptors: keychain
+        .descriptors()
+        .into_iter()
+        .map(|(change, desc)| Descriptor {
+          desc,
+          timestamp: Timestamp::Now,
+          active: true,
+          internal: Some(change),
+          range: None,
+          next: None,
+        })
+        .collect(),
+    })
+  }
+
+  pub(crate) fn address_type(&self) -> AddressType {
+    match &self.spending_descriptor {
+      Some(miniscript::descriptor::Descriptor::Wsh(_)) => AddressType::Bech32,
+      _ => AddressType::Bech32m,
+    }
+  }
+
+  /// The witness weight needed to spend one of the wallet's outputs. `ord`
+  /// signs key path spends with `SIGHASH_DEFAULT`, which omits the sighash
+  /// byte that miniscript accounts for, so those are sized separately.
+  pub(crate) fn satisfaction_weight(&self) -> usize {
+    match &self.spending_descriptor {
+      Some(descriptor) if !self.is_single_key() => descriptor.max_weight_to_satisfy().unwrap(),
+      _ => TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+    }
+  }
+
   pub(crate) fn chain(&self) -> Chain {
     self.settings.chain()
   }
@@ -353,7 +900,7 @@ impl Wallet {
   }
 
   pub(crate) fn send_etching(&self, rune: Rune, entry: &EtchingEntry) -> Result<batch::Output> {
-    match self.bitcoin_client().send_raw_transaction(&entry.reveal) {
+    match self.broadcast(&entry.reveal) {
       Ok(txid) => txid,
       Err(err) => {
         return Err(anyhow!(
@@ -371,10 +918,14 @@ impl Wallet {
     })
   }
 
+  fn is_spending_descriptor(descriptor: &Descriptor) -> bool {
+    descriptor.desc.starts_with("tr(") || descriptor.desc.starts_with("wsh(")
+  }
+
   fn check_descriptors(wallet_name: &str, descriptors: Vec<Descriptor>) -> Result<Vec<Descriptor>> {
-    let tr = descriptors
+    let spending = descriptors
       .iter()
-      .filter(|descriptor| descriptor.desc.starts_with("tr("))
+      .filter(|descriptor| Self::is_spending_descriptor(descriptor))
       .count();
 
     let rawtr = descriptors
@@ -382,23 +933,62 @@ impl Wallet {
       .filter(|descriptor| descriptor.desc.starts_with("rawtr("))
       .count();
 
-    if tr != 2 || descriptors.len() != 2 + rawtr {
+    if spending != 2 || descriptors.len() != 2 + rawtr {
       bail!("wallet \"{}\" contains unexpected output descriptors, and does not appear to be an `ord` wallet, create a new wallet with `ord wallet create`", wallet_name);
     }
 
     Ok(descriptors)
   }
 
+  pub(crate) fn spending_descriptor(
+    descriptors: &[Descriptor],
+  ) -> Result<Option<miniscript::descriptor::Descriptor<DescriptorPublicKey>>> {
+    let Some(descriptor) = descriptors
+      .iter()
+      .find(|descriptor| Self::is_spending_descriptor(descriptor))
+    else {
+      return Ok(None);
+    };
+
+    let (descriptor, _key_map) =
+      miniscript::descriptor::Descriptor::parse_descriptor(&Secp256k1::new(), &descriptor.desc)
+        .with_context(|| format!("failed to parse wallet descriptor `{}`", descriptor.desc))?;
+
+    descriptor
+      .max_weight_to_satisfy()
+      .context("wallet descriptor cannot be satisfied")?;
+
+    Ok(Some(descriptor))
+  }
+
   pub(crate) fn initialize_from_descriptors(
     name: String,
     settings: &Settings,
     descriptors: Vec<Descriptor>,
+    watch_only: bool,
   ) -> Result {
     let client = Self::check_version(settings.bitcoin_rpc_client(Some(name.clone()))?)?;
 
     let descriptors = Self::check_descriptors(&name, descriptors)?;
 
-    client.create_wallet(&name, None, Some(true), None, None)?;
+    // bitcoind refuses to import private keys into watch-only wallets, so
+    // secrets are stripped before the wallet is created, to avoid leaving
+    // behind an empty wallet if they cannot be.
+    let descriptors = if watch_only {
+      descriptors
+        .into_iter()
+        .map(|descriptor| {
+          Ok(Descriptor {
+            desc: Self::public_descriptor(&descriptor.desc)?,
+            ..descriptor
+          })
+        })
+        .collect::<Result<Vec<Descriptor>>>()?
+    } else {
+      descriptors
+    };
+
+    client.create_wallet(&name, Some(watch_only), Some(true), None, None)?;
 
     let descriptors = descriptors
       .into_iter()
@@ -425,17 +1015,112 @@ impl Wallet {
     Ok(())
   }
 
-  pub(crate) fn initialize(name: String, settings: &Settings, seed: [u8; 64]) -> Result {
+  /// `descriptor` with any private keys replaced by their public keys.
+  fn public_descriptor(descriptor: &str) -> Result<String> {
+    let secp256k1 = Secp256k1::new();
+
+    // miniscript does not support `rawtr` descriptors, which hold a single
+    // commit transaction recovery key
+    if let Some(key) = descriptor
+      .strip_prefix("rawtr(")
+      .and_then(|desc| desc.split(')').next())
+    {
+      let Ok(private_key) = PrivateKey::from_wif(key) else {
+        return Ok(descriptor.into());
+      };
+
+      let descriptor = format!(
+        "rawtr({})",
+        private_key.inner.x_only_public_key(&secp256k1).0
+      );
+
+      let checksum = miniscript::descriptor::checksum::desc_checksum(&descriptor)?;
+
+      return Ok(format!("{descriptor}#{checksum}"));
+    }
+
+    let (descriptor, _key_map) =
+      miniscript::descriptor::Descriptor::parse_descriptor(&secp256k1, descriptor)
+        .context("invalid output descriptor")?;
+
+    Ok(descriptor.to_string())
+  }
+
+  pub(crate) fn initialize(
+    name: String,
+    settings: &Settings,
+    seed: [u8; 64],
+    watch_only: bool,
+  ) -> Result {
     Self::check_version(settings.bitcoin_rpc_client(None)?)?.create_wallet(
       &name,
-      None,
+      Some(watch_only),
       Some(true),
       None,
       None,
     )?;
 
-    let network = settings.chain().network();
+    for (change, (descriptor, key_map)) in [false, true]
+      .into_iter()
+      .zip(Self::derive_descriptors(settings.chain().network(), seed)?)
+    {
+      Self::import_descriptor(
+        name.clone(),
+        settings,
+        if watch_only {
+          descriptor.to_string()
+        } else {
+          descriptor.to_string_with_secret(&key_map)
+        },
+        change,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn initialize_native(name: String, settings: &Settings, seed: [u8; 64]) -> Result {
+    ensure!(
+      settings.chain() != Chain::Mainnet,
+      "native wallets store private keys unencrypted and are not supported on mainnet"
+    );
+
+    let database = Self::open_database(&name, settings)?;
+
+    ensure!(
+      Keychain::load(&database, settings.chain().network())?.is_none(),
+      "wallet `{name}` already exists"
+    );
+
+    Keychain::store(
+      &database,
+      Self::derive_descriptors(settings.chain().network(), seed)?
+        .map(|(descriptor, key_map)| descriptor.to_string_with_secret(&key_map)),
+    )?;
+
+    eprintln!(
+      "warning: native wallet private keys are stored UNENCRYPTED in `{}`, protect it as you \
+      would the wallet's mnemonic",
+      settings
+        .data_dir()
+        .join("wallets")
+        .join(format!("{name}.redb"))
+        .display(),
+    );
+
+    Ok(())
+  }
 
+  /// Derive the BIP-86 receive and change descriptors of an `ord` wallet.
+  pub(crate) fn derive_descriptors(
+    network: Network,
+    seed: [u8; 64],
+  ) -> Result<
+    [(
+      miniscript::descriptor::Descriptor<DescriptorPublicKey>,
+      KeyMap,
+    ); 2],
+  > {
     let secp = Secp256k1::new();
 
     let master_private_key = ExtendedPrivKey::new_master(network, &seed)?;
@@ -451,13 +1136,59 @@ impl Wallet {
 
     let derived_private_key = master_private_key.derive_priv(&secp, &derivation_path)?;
 
+    let derive = |change: bool| -> Result<_> {
+      let secret_key = DescriptorSecretKey::XPrv(DescriptorXKey {
+        origin: Some((fingerprint, derivation_path.clone())),
+        xkey: derived_private_key,
+        derivation_path: DerivationPath::master().child(ChildNumber::Normal {
+          index: change.into(),
+        }),
+        wildcard: Wildcard::Unhardened,
+      });
+
+      let public_key = secret_key.to_public(&secp)?;
+
+      let mut key_map = HashMap::new();
+      key_map.insert(public_key.clone(), secret_key);
+
+      Ok((
+        miniscript::descriptor::Descriptor::new_tr(public_key, None)?,
+        key_map,
+      ))
+    };
+
+    Ok([derive(false)?, derive(true)?])
+  }
+
+  pub(crate) fn initialize_from_xpub(name: String, settings: &Settings, xpub: &str) -> Result {
+    Self::check_version(settings.bitcoin_rpc_client(None)?)?.create_wallet(
+      &name,
+      Some(true),
+      Some(true),
+      None,
+      None,
+    )?;
+
     for change in [false, true] {
-      Self::derive_and_import_descriptor(
+      let public_key =
+        DescriptorPublicKey::from_str(&format!("{}/{}/*", xpub.trim(), u8::from(change)))
+          .context("invalid extended public key")?;
+
+      ensure!(
+        matches!(public_key, DescriptorPublicKey::XPub(_)),
+        "watch-only wallets must be restored from an extended public key"
+      );
+
+      // signers need the key origin to find their key in PSBTs
+      ensure!(
+        matches!(&public_key, DescriptorPublicKey::XPub(xkey) if xkey.origin.is_some()),
+        "extended public key must be prefixed with its key origin, e.g. `[fingerprint/86'/0'/0']xpub…`"
+      );
+
+      Self::import_descriptor(
         name.clone(),
         settings,
-        &secp,
-        (fingerprint, derivation_path.clone()),
-        derived_private_key,
+        miniscript::descriptor::Descriptor::new_tr(public_key, None)?.to_string(),
         change,
       )?;
     }
@@ -465,34 +1196,101 @@ impl Wallet {
     Ok(())
   }
 
-  fn derive_and_import_descriptor(
+  pub(crate) fn initialize_from_output_descriptor(
     name: String,
     settings: &Settings,
-    secp: &Secp256k1<All>,
-    origin: (Fingerprint, DerivationPath),
-    derived_private_key: ExtendedPrivKey,
-    change: bool,
+    descriptor: &str,
+    watch_only: bool,
   ) -> Result {
-    let secret_key = DescriptorSecretKey::XPrv(DescriptorXKey {
-      origin: Some(origin),
-      xkey: derived_private_key,
-      derivation_path: DerivationPath::master().child(ChildNumber::Normal {
-        index: change.into(),
-      }),
-      wildcard: Wildcard::Unhardened,
-    });
+    lazy_static! {
+      static ref MULTIPATH: Regex = Regex::new(r"<(\d+);(\d+)>").unwrap();
+    }
+
+    let descriptor = descriptor.trim();
+
+    let descriptor = match descriptor.split_once('#') {
+      Some((descriptor, checksum)) => {
+        ensure!(
+          miniscript::descriptor::checksum::desc_checksum(descriptor)? == checksum,
+          "invalid output descriptor checksum"
+        );
+        descriptor
+      }
+      None => descriptor,
+    };
+
+    ensure!(
+      MULTIPATH.is_match(descriptor),
+      "output descriptor must contain receive and change paths, e.g. `/<0;1>/*`"
+    );
+
+    // miniscript cannot derive public keys from multipath private keys, so
+    // each path is split out before parsing.
+    let mut descriptors = Vec::new();
+
+    for (i, change) in [false, true].into_iter().enumerate() {
+      let (descriptor, key_map) = miniscript::descriptor::Descriptor::parse_descriptor(
+        &Secp256k1::new(),
+        &MULTIPATH.replace_all(descriptor, |captures: &regex::Captures| {
+          captures[i + 1].to_string()
+        }),
+      )
+      .context("invalid output descriptor")?;
+
+      ensure!(
+        matches!(
+          descriptor,
+          miniscript::descriptor::Descriptor::Tr(_) | miniscript::descriptor::Descriptor::Wsh(_)
+        ),
+        "output descriptor must be a `tr()` or `wsh()` descriptor"
+      );
 
-    let public_key = secret_key.to_public(secp)?;
+      descriptor
+        .max_weight_to_satisfy()
+        .context("output descriptor cannot be satisfied")?;
 
-    let mut key_map = HashMap::new();
-    key_map.insert(public_key.clone(), secret_key);
+      descriptors.push((change, descriptor, key_map));
+    }
 
-    let descriptor = miniscript::descriptor::Descriptor::new_tr(public_key, None)?;
+    let watch_only = watch_only
+      || descriptors
+        .iter()
+        .any(|(_change, _descriptor, key_map)| key_map.is_empty());
 
+    Self::check_version(settings.bitcoin_rpc_client(None)?)?.create_wallet(
+      &name,
+      Some(watch_only),
+      Some(true),
+      None,
+      None,
+    )?;
+
+    for (change, descriptor, key_map) in descriptors {
+      Self::import_descriptor(
+        name.clone(),
+        settings,
+        if watch_only {
+          descriptor.to_string()
+        } else {
+          descriptor.to_string_with_secret(&key_map)
+        },
+        change,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  fn import_descriptor(
+    name: String,
+    settings: &Settings,
+    descriptor: String,
+    change: bool,
+  ) -> Result {
     settings
       .bitcoin_rpc_client(Some(name.clone()))?
       .import_descriptors(vec![ImportDescriptors {
-        descriptor: descriptor.to_string_with_secret(&key_map),
+        descriptor,
         timestamp: Timestamp::Now,
         active: Some(true),
         range: None,
@@ -608,7 +1406,9 @@ impl Wallet {
 
         let tx = database.begin_write()?;
 
+        tx.open_table(CHANGE_TO_DESCRIPTOR)?;
         tx.open_table(RUNE_TO_ETCHING)?;
+        tx.open_table(TXID_TO_PENDING_TRANSACTION)?;
 
         tx.open_table(STATISTICS)?
           .insert(&Statistic::Schema.key(), &SCHEMA_VERSION)?;
@@ -8,7 +8,7 @@ use {
     secp256k1::{self, constants::SCHNORR_SIGNATURE_SIZE, rand, Secp256k1, XOnlyPublicKey},
     sighash::{Prevouts, SighashCache, TapSighashType},
     taproot::Signature,
-    taproot::{ControlBlock, LeafVersion, TapLeafHash, TaprootBuilder},
+    taproot::{ControlBlock, LeafVersion, TapLeafHash, TapTree, TaprootBuilder, TaprootSpendInfo},
   },
   bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, SignRawTransactionInput, Timestamp},
   wallet::transaction_builder::Target,
@@ -17,9 +17,11 @@ use {
 pub(crate) use transactions::Transactions;
 
 pub use {
-  entry::Entry, etching::Etching, file::File, mode::Mode, plan::Plan, range::Range, terms::Terms,
+  allocation::Allocation, entry::Entry, etching::Etching, file::File, mode::Mode, plan::Plan,
+  range::Range, terms::Terms,
 };
 
+mod allocation;
 pub mod entry;
 mod etching;
 pub mod file;
@@ -49,8 +51,17 @@ pub struct InscriptionInfo {
   pub location: SatPoint,
 }
 
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct AllocationInfo {
+  pub address: Address<NetworkUnchecked>,
+  pub amount: Decimal,
+  pub location: OutPoint,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct RuneInfo {
+  #[serde(default)]
+  pub allocations: Vec<AllocationInfo>,
   pub destination: Option<Address<NetworkUnchecked>>,
   pub location: Option<OutPoint>,
   pub rune: SpacedRune,
@@ -0,0 +1,9 @@
+use super::*;
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct Allocation {
+  pub address: Address<NetworkUnchecked>,
+  pub amount: Decimal,
+  pub postage: Option<u64>,
+}
@@ -1,8 +1,10 @@
 use super::*;
 
-#[derive(Serialize, Deserialize, PartialEq, Debug, Copy, Clone, Default)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default)]
 #[serde(deny_unknown_fields)]
 pub struct Etching {
+  #[serde(default)]
+  pub allocations: Vec<batch::Allocation>,
   pub divisibility: u8,
   pub premine: Decimal,
   pub rune: SpacedRune,
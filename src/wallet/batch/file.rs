@@ -4,6 +4,8 @@ use super::*;
 #[serde(deny_unknown_fields)]
 pub struct File {
   pub inscriptions: Vec<Entry>,
+  #[serde(default)]
+  pub manifest: bool,
   pub mode: Mode,
   pub parent: Option<InscriptionId>,
   pub postage: Option<u64>,
@@ -100,6 +102,23 @@ impl File {
       }
     }
 
+    if batchfile.manifest {
+      ensure!(batchfile.parent.is_some(), "`manifest` requires `parent`");
+
+      ensure!(
+        batchfile.mode != Mode::SatPoints,
+        "`manifest` cannot be set in `satpoints` mode"
+      );
+
+      ensure!(
+        batchfile
+          .inscriptions
+          .iter()
+          .all(|entry| entry.delegate.is_none()),
+        "`manifest` cannot be set if any inscription has a `delegate`"
+      );
+    }
+
     Ok(batchfile)
   }
 
@@ -140,6 +159,7 @@ impl File {
         Some(pointer),
         self
           .etching
+          .as_ref()
           .and_then(|etch| (i == 0).then_some(etch.rune.rune)),
       )?);
 
@@ -172,6 +192,31 @@ impl File {
       }
     }
 
+    if self.manifest {
+      let manifest = Manifest {
+        items: inscriptions
+          .iter()
+          .enumerate()
+          .map(|(i, inscription)| {
+            Manifest::item(inscription).with_context(|| format!("inscription {i}"))
+          })
+          .collect::<Result<Vec<_>>>()?,
+      };
+
+      inscriptions.push(
+        manifest.inscription(
+          self
+            .parent
+            .ok_or_else(|| anyhow!("`manifest` requires `parent`"))?,
+          pointer,
+        )?,
+      );
+
+      if self.mode != Mode::SameSat {
+        postages.push(self.postage.map(Amount::from_sat).unwrap_or(TARGET_POSTAGE));
+      }
+    }
+
     let destinations = match self.mode {
       Mode::SharedOutput | Mode::SameSat => vec![wallet.get_change_address()?],
       Mode::SeparateOutputs | Mode::SatPoints => self
@@ -188,6 +233,7 @@ impl File {
             },
           )
         })
+        .chain(self.manifest.then(|| wallet.get_change_address()))
         .collect::<Result<Vec<_>, _>>()?,
     };
 
@@ -371,11 +417,19 @@ inscriptions:
             .parse()
             .unwrap()
         ),
+        manifest: false,
         postage: Some(12345),
         reinscribe: true,
         sat: None,
         satpoint: None,
         etching: Some(Etching {
+          allocations: vec![batch::Allocation {
+            address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+              .parse()
+              .unwrap(),
+            amount: "250.00".parse().unwrap(),
+            postage: Some(546),
+          }],
           rune: "THE•BEST•RUNE".parse().unwrap(),
           divisibility: 2,
           premine: "1000.00".parse().unwrap(),
@@ -464,4 +518,47 @@ inscriptions:
 
     assert!(batch::File::load(batch_file.as_path()).is_ok());
   }
+
+  #[test]
+  fn batchfile_manifest_requires_parent() {
+    let tempdir = TempDir::new().unwrap();
+    let batch_file = tempdir.path().join("batch.yaml");
+    fs::write(
+      batch_file.clone(),
+      r#"
+mode: separate-outputs
+manifest: true
+inscriptions:
+- file: inscription.txt
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      File::load(batch_file.as_path()).unwrap_err().to_string(),
+      "`manifest` requires `parent`"
+    );
+  }
+
+  #[test]
+  fn batchfile_manifest_no_delegates() {
+    let tempdir = TempDir::new().unwrap();
+    let batch_file = tempdir.path().join("batch.yaml");
+    fs::write(
+      batch_file.clone(),
+      r#"
+mode: separate-outputs
+manifest: true
+parent: 6ac5cacb768794f4fd7a78bf00f2074891fce68bd65c4ff36e77177237aacacai0
+inscriptions:
+- delegate: 6ac5cacb768794f4fd7a78bf00f2074891fce68bd65c4ff36e77177237aacacai0
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+      File::load(batch_file.as_path()).unwrap_err().to_string(),
+      "`manifest` cannot be set if any inscription has a `delegate`"
+    );
+  }
 }
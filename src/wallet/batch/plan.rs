@@ -11,9 +11,11 @@ pub struct Plan {
   pub(crate) no_limit: bool,
   pub(crate) parent_info: Option<ParentInfo>,
   pub(crate) postages: Vec<Amount>,
+  pub(crate) psbt: bool,
   pub(crate) reinscribe: bool,
   pub(crate) reveal_fee_rate: FeeRate,
   pub(crate) reveal_satpoints: Vec<(SatPoint, TxOut)>,
+  pub(crate) satisfaction_weight: usize,
   pub(crate) satpoint: Option<SatPoint>,
 }
 
@@ -30,9 +32,11 @@ impl Default for Plan {
       no_limit: false,
       parent_info: None,
       postages: vec![Amount::from_sat(10_000)],
+      psbt: false,
       reinscribe: false,
       reveal_fee_rate: 1.0.try_into().unwrap(),
       reveal_satpoints: Vec::new(),
+      satisfaction_weight: TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       satpoint: None,
     }
   }
@@ -46,11 +50,42 @@ impl Plan {
     utxos: &BTreeMap<OutPoint, TxOut>,
     wallet: &Wallet,
   ) -> SubcommandResult {
+    wallet.check_can_sign(self.dry_run || self.psbt)?;
+
+    ensure!(
+      self.dry_run || self.no_backup || !wallet.is_watch_only(),
+      "watch-only wallets cannot back up the commit transaction recovery key, use `--no-backup`"
+    );
+
+    // the reveal transaction of an etching can only be broadcast once the
+    // commitment has matured, which `ord wallet resume` waits for, but PSBT
+    // mode neither saves the etching nor locks the commitment output
+    ensure!(
+      !self.psbt || self.etching.is_none(),
+      "`--psbt` cannot be used to etch runes"
+    );
+
+    if wallet.is_native() {
+      ensure!(
+        self.dry_run || self.no_backup,
+        "native wallets cannot back up the commit transaction recovery key, use `--no-backup`"
+      );
+
+      ensure!(!self.psbt, "`--psbt` is not supported by native wallets");
+
+      ensure!(
+        self.etching.is_none(),
+        "etching runes is not supported by native wallets"
+      );
+    }
+
     let Transactions {
       commit_tx,
       commit_vout,
+      reveal_script,
       reveal_tx,
       recovery_key_pair,
+      taproot_spend_info,
       total_fees,
       rune,
     } = self.create_batch_transactions(
@@ -64,16 +99,7 @@ impl Plan {
     )?;
 
     if self.dry_run {
-      let commit_psbt = wallet
-        .bitcoin_client()
-        .wallet_process_psbt(
-          &base64::engine::general_purpose::STANDARD
-            .encode(Psbt::from_unsigned_tx(Self::remove_witnesses(commit_tx.clone()))?.serialize()),
-          Some(false),
-          None,
-          None,
-        )?
-        .psbt;
+      let commit_psbt = wallet.unsigned_psbt(&Self::remove_witnesses(commit_tx.clone()), None)?;
 
       let reveal_psbt = Psbt::from_unsigned_tx(Self::remove_witnesses(reveal_tx.clone()))?;
 
@@ -89,44 +115,103 @@ impl Plan {
       ))));
     }
 
-    let signed_commit_tx = wallet
-      .bitcoin_client()
-      .sign_raw_transaction_with_wallet(&commit_tx, None, None)?
-      .hex;
-
-    let result = wallet.bitcoin_client().sign_raw_transaction_with_wallet(
-      &reveal_tx,
-      Some(
-        &commit_tx
-          .output
-          .iter()
-          .enumerate()
-          .map(|(vout, output)| SignRawTransactionInput {
-            txid: commit_tx.txid(),
-            vout: vout.try_into().unwrap(),
-            script_pub_key: output.script_pubkey.clone(),
-            redeem_script: None,
-            amount: Some(Amount::from_sat(output.value)),
-          })
-          .collect::<Vec<SignRawTransactionInput>>(),
-      ),
-      None,
-    )?;
+    if self.psbt {
+      if !self.no_backup {
+        Self::backup_recovery_key(wallet, recovery_key_pair)?;
+      }
 
-    ensure!(
-      result.complete,
-      format!("Failed to sign reveal transaction: {:?}", result.errors)
-    );
+      let commit_psbt = Self::commit_psbt(
+        wallet,
+        &commit_tx,
+        commit_vout,
+        &reveal_script,
+        &taproot_spend_info,
+      )?;
+
+      let reveal_psbt = Self::reveal_psbt(
+        wallet,
+        &commit_tx,
+        commit_vout,
+        reveal_tx.clone(),
+        &reveal_script,
+        &taproot_spend_info,
+      )?;
+
+      return Ok(Some(Box::new(self.output(
+        commit_tx.txid(),
+        Some(commit_psbt),
+        reveal_tx.txid(),
+        false,
+        Some(reveal_psbt),
+        total_fees,
+        self.inscriptions.clone(),
+        rune,
+      ))));
+    }
+
+    let (signed_commit_tx, signed_reveal_tx) = if wallet.is_native() {
+      (
+        consensus::encode::serialize(&wallet.sign_transaction(&commit_tx, &BTreeMap::new())?),
+        consensus::encode::serialize(
+          &wallet.sign_transaction(
+            &reveal_tx,
+            &commit_tx
+              .output
+              .iter()
+              .enumerate()
+              .map(|(vout, output)| {
+                (
+                  OutPoint {
+                    txid: commit_tx.txid(),
+                    vout: vout.try_into().unwrap(),
+                  },
+                  output.clone(),
+                )
+              })
+              .collect(),
+          )?,
+        ),
+      )
+    } else {
+      let signed_commit_tx = wallet
+        .bitcoin_client()
+        .sign_raw_transaction_with_wallet(&commit_tx, None, None)?
+        .hex;
+
+      let result = wallet.bitcoin_client().sign_raw_transaction_with_wallet(
+        &reveal_tx,
+        Some(
+          &commit_tx
+            .output
+            .iter()
+            .enumerate()
+            .map(|(vout, output)| SignRawTransactionInput {
+              txid: commit_tx.txid(),
+              vout: vout.try_into().unwrap(),
+              script_pub_key: output.script_pubkey.clone(),
+              redeem_script: None,
+              amount: Some(Amount::from_sat(output.value)),
+            })
+            .collect::<Vec<SignRawTransactionInput>>(),
+        ),
+        None,
+      )?;
+
+      ensure!(
+        result.complete,
+        format!("Failed to sign reveal transaction: {:?}", result.errors)
+      );
 
-    let signed_reveal_tx = result.hex;
+      let signed_reveal_tx = result.hex;
+
+      (signed_commit_tx, signed_reveal_tx)
+    };
 
     if !self.no_backup {
       Self::backup_recovery_key(wallet, recovery_key_pair)?;
     }
 
-    let commit_txid = wallet
-      .bitcoin_client()
-      .send_raw_transaction(&signed_commit_tx)?;
+    let commit_txid = wallet.broadcast(&signed_commit_tx)?;
 
     if let Some(ref rune_info) = rune {
       wallet.bitcoin_client().lock_unspent(&[OutPoint {
@@ -157,10 +242,7 @@ impl Plan {
         wallet.wait_for_maturation(rune_info.rune.rune)?,
       )))
     } else {
-      let reveal = match wallet
-        .bitcoin_client()
-        .send_raw_transaction(&signed_reveal_tx)
-      {
+      let reveal = match wallet.broadcast(&signed_reveal_tx) {
         Ok(txid) => txid,
         Err(err) => {
           return Err(anyhow!(
@@ -182,6 +264,102 @@ impl Plan {
     }
   }
 
+  fn commit_psbt(
+    wallet: &Wallet,
+    commit_tx: &Transaction,
+    commit_vout: usize,
+    reveal_script: &Script,
+    taproot_spend_info: &TaprootSpendInfo,
+  ) -> Result<String> {
+    let mut psbt = Psbt::from_unsigned_tx(Self::remove_witnesses(commit_tx.clone()))?;
+
+    let output = &mut psbt.outputs[commit_vout];
+
+    output.tap_internal_key = Some(taproot_spend_info.internal_key());
+    output.tap_tree = Some(
+      TapTree::try_from(
+        TaprootBuilder::new()
+          .add_leaf(0, reveal_script.into())
+          .expect("adding leaf should work"),
+      )
+      .expect("taproot builder should be complete"),
+    );
+
+    Ok(
+      wallet
+        .bitcoin_client()
+        .wallet_process_psbt(
+          &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+          Some(false),
+          None,
+          Some(true),
+        )?
+        .psbt,
+    )
+  }
+
+  fn reveal_psbt(
+    wallet: &Wallet,
+    commit_tx: &Transaction,
+    commit_vout: usize,
+    reveal_tx: Transaction,
+    reveal_script: &Script,
+    taproot_spend_info: &TaprootSpendInfo,
+  ) -> Result<String> {
+    let commit_outpoint = OutPoint {
+      txid: commit_tx.txid(),
+      vout: commit_vout.try_into().unwrap(),
+    };
+
+    let commit_input = reveal_tx
+      .input
+      .iter()
+      .position(|txin| txin.previous_output == commit_outpoint)
+      .expect("reveal transaction should spend commit output");
+
+    let signature = Signature::from_slice(
+      reveal_tx.input[commit_input]
+        .witness
+        .nth(0)
+        .expect("reveal transaction should be signed"),
+    )?;
+
+    let control_block = taproot_spend_info
+      .control_block(&(reveal_script.into(), LeafVersion::TapScript))
+      .expect("should compute control block");
+
+    let mut psbt = Psbt::from_unsigned_tx(Self::remove_witnesses(reveal_tx))?;
+
+    let input = &mut psbt.inputs[commit_input];
+
+    input.witness_utxo = Some(commit_tx.output[commit_vout].clone());
+    input.tap_internal_key = Some(taproot_spend_info.internal_key());
+    input.tap_merkle_root = taproot_spend_info.merkle_root();
+    input.tap_script_sigs.insert(
+      (
+        taproot_spend_info.internal_key(),
+        TapLeafHash::from_script(reveal_script, LeafVersion::TapScript),
+      ),
+      signature,
+    );
+    input.tap_scripts.insert(
+      control_block,
+      (reveal_script.into(), LeafVersion::TapScript),
+    );
+
+    Ok(
+      wallet
+        .bitcoin_client()
+        .wallet_process_psbt(
+          &base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+          Some(false),
+          None,
+          Some(true),
+        )?
+        .psbt,
+    )
+  }
+
   fn remove_witnesses(mut transaction: Transaction) -> Transaction {
     for txin in transaction.input.iter_mut() {
       txin.witness = Witness::new();
@@ -429,15 +607,52 @@ impl Plan {
     }
 
     let rune;
-    let premine;
     let runestone;
+    let mut runic_postage = 0;
 
-    if let Some(etching) = self.etching {
+    if let Some(etching) = &self.etching {
       let vout;
       let destination;
-      premine = etching.premine.to_integer(etching.divisibility)?;
+      let premine = etching.premine.to_integer(etching.divisibility)?;
+
+      // Premine allocations each get their own output, and are assigned with
+      // edicts referring to the rune being etched by its default ID.
+      let mut allocations = Vec::new();
+      let mut allocated = 0u128;
+      let mut edicts = Vec::new();
+
+      for allocation in &etching.allocations {
+        let amount = allocation.amount.to_integer(etching.divisibility)?;
+        let address = allocation
+          .address
+          .clone()
+          .require_network(chain.network())?;
+        let postage = allocation.postage.unwrap_or(TARGET_POSTAGE.to_sat());
+        let output = u32::try_from(reveal_outputs.len()).unwrap();
+
+        allocated = allocated
+          .checked_add(amount)
+          .ok_or_else(|| anyhow!("premine allocations over maximum"))?;
+
+        edicts.push(Edict {
+          id: RuneId::default(),
+          amount,
+          output,
+        });
+
+        reveal_outputs.push(TxOut {
+          script_pubkey: address.script_pubkey(),
+          value: postage,
+        });
+
+        runic_postage += postage;
+
+        allocations.push((address, allocation.amount, output));
+      }
 
-      if premine > 0 {
+      ensure!(allocated <= premine, "premine allocations exceed `premine`");
+
+      if premine > allocated {
         let output = u32::try_from(reveal_outputs.len()).unwrap();
         destination = Some(reveal_change.clone());
 
@@ -446,6 +661,8 @@ impl Plan {
           value: TARGET_POSTAGE.to_sat(),
         });
 
+        runic_postage += TARGET_POSTAGE.to_sat();
+
         vout = Some(output);
       } else {
         vout = None;
@@ -453,7 +670,7 @@ impl Plan {
       }
 
       let inner = Runestone {
-        edicts: Vec::new(),
+        edicts,
         etching: Some(ordinals::Etching {
           divisibility: (etching.divisibility > 0).then_some(etching.divisibility),
           premine: (premine > 0).then_some(premine),
@@ -480,7 +697,7 @@ impl Plan {
           turbo: etching.turbo,
         }),
         mint: None,
-        pointer: (premine > 0).then_some((reveal_outputs.len() - 1).try_into().unwrap()),
+        pointer: vout,
       };
 
       let script_pubkey = inner.encipher();
@@ -498,9 +715,8 @@ impl Plan {
         value: 0,
       });
 
-      rune = Some((destination, etching.rune, vout));
+      rune = Some((destination, etching.rune, vout, allocations));
     } else {
-      premine = 0;
       rune = None;
       runestone = None;
     }
@@ -515,6 +731,7 @@ impl Plan {
       reveal_inputs.clone(),
       &reveal_script,
       rune.is_some(),
+      self.satisfaction_weight,
     );
 
     let mut target_value = reveal_fee;
@@ -523,9 +740,7 @@ impl Plan {
       target_value += Amount::from_sat(total_postage);
     }
 
-    if premine > 0 {
-      target_value += TARGET_POSTAGE;
-    }
+    target_value += Amount::from_sat(runic_postage);
 
     let unsigned_commit_tx = TransactionBuilder::new(
       satpoint,
@@ -538,6 +753,7 @@ impl Plan {
       self.commit_fee_rate,
       Target::Value(target_value),
     )
+    .satisfaction_weight(self.satisfaction_weight)
     .build_transaction()?;
 
     let (vout, _commit_output) = unsigned_commit_tx
@@ -560,6 +776,7 @@ impl Plan {
       reveal_inputs,
       &reveal_script,
       rune.is_some(),
+      self.satisfaction_weight,
     );
 
     for output in reveal_tx.output.iter() {
@@ -612,7 +829,7 @@ impl Plan {
       .to_vec(),
     );
 
-    witness.push(reveal_script);
+    witness.push(&reveal_script);
     witness.push(&control_block.serialize());
 
     let recovery_key_pair = key_pair.tap_tweak(&secp256k1, taproot_spend_info.merkle_root());
@@ -656,7 +873,18 @@ impl Plan {
       (None, None) => {}
     }
 
-    let rune = rune.map(|(destination, rune, vout)| RuneInfo {
+    let rune = rune.map(|(destination, rune, vout, allocations)| RuneInfo {
+      allocations: allocations
+        .into_iter()
+        .map(|(address, amount, vout)| AllocationInfo {
+          address: uncheck(&address),
+          amount,
+          location: OutPoint {
+            txid: reveal_tx.txid(),
+            vout,
+          },
+        })
+        .collect(),
       destination: destination.map(|destination| uncheck(&destination)),
       location: vout.map(|vout| OutPoint {
         txid: reveal_tx.txid(),
@@ -669,8 +897,10 @@ impl Plan {
       commit_tx: unsigned_commit_tx,
       commit_vout: vout,
       recovery_key_pair,
+      reveal_script,
       reveal_tx,
       rune,
+      taproot_spend_info,
       total_fees,
     })
   }
@@ -714,6 +944,7 @@ impl Plan {
     input: Vec<OutPoint>,
     script: &Script,
     etching: bool,
+    satisfaction_weight: usize,
   ) -> (Transaction, Amount) {
     let reveal_tx = Transaction {
       input: input
@@ -748,7 +979,7 @@ impl Plan {
           txin.witness.push(script);
           txin.witness.push(&control_block.serialize());
         } else {
-          txin.witness = Witness::from_slice(&[&[0; SCHNORR_SIGNATURE_SIZE]]);
+          txin.witness = TransactionBuilder::dummy_witness(satisfaction_weight);
         }
       }
 
@@ -6,6 +6,8 @@ pub(crate) struct Transactions {
   pub(crate) commit_tx: Transaction,
   pub(crate) commit_vout: usize,
   pub(crate) recovery_key_pair: TweakedKeyPair,
+  pub(crate) reveal_script: ScriptBuf,
   pub(crate) reveal_tx: Transaction,
+  pub(crate) taproot_spend_info: TaprootSpendInfo,
   pub(crate) total_fees: u64,
 }
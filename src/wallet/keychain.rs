@@ -0,0 +1,506 @@
+//! In-process key management for wallets that do not use a bitcoind wallet.
+//!
+//! Native wallets store their receive and change descriptors, including
+//! private keys, unencrypted in the wallet database, so they are refused on
+//! mainnet. Addresses are derived locally, the `ord` server's address index is
+//! used to find their outputs, and transactions are signed with taproot key
+//! path spends.
+
+use {
+  super::*,
+  bitcoin::{
+    key::{KeyPair, TapTweak},
+    secp256k1::Message,
+    sighash::{Prevouts, SighashCache, TapSighashType},
+    taproot,
+  },
+  miniscript::descriptor::KeyMap,
+  redb::TableError,
+};
+
+/// Number of addresses past both the last address handed out and the last
+/// address with outputs that are scanned for outputs, so that wallets restored
+/// from a mnemonic find their funds.
+const GAP_LIMIT: u32 = 20;
+
+pub(crate) struct Keychain {
+  derivations: Mutex<BTreeMap<ScriptBuf, (bool, u32)>>,
+  descriptors: [(
+    miniscript::descriptor::Descriptor<DescriptorPublicKey>,
+    KeyMap,
+  ); 2],
+  network: Network,
+  secp: Secp256k1<All>,
+}
+
+impl Keychain {
+  pub(crate) fn load(database: &Database, network: Network) -> Result<Option<Self>> {
+    let rtx = database.begin_read()?;
+
+    let table = match rtx.open_table(CHANGE_TO_DESCRIPTOR) {
+      Ok(table) => table,
+      Err(TableError::TableDoesNotExist(_)) => return Ok(None),
+      Err(err) => return Err(err.into()),
+    };
+
+    let (Some(receive), Some(change)) = (table.get(false)?, table.get(true)?) else {
+      return Ok(None);
+    };
+
+    let secp = Secp256k1::new();
+
+    let descriptors = [receive.value(), change.value()].map(|descriptor| {
+      miniscript::descriptor::Descriptor::parse_descriptor(&secp, descriptor)
+        .context("invalid wallet descriptor")
+    });
+
+    let [receive, change] = descriptors;
+
+    let keychain = Self {
+      derivations: Mutex::new(BTreeMap::new()),
+      descriptors: [receive?, change?],
+      network,
+      secp,
+    };
+
+    for change in [false, true] {
+      for index in 0..Self::next_index(database, change)? + GAP_LIMIT {
+        keychain.derive(change, index)?;
+      }
+    }
+
+    Ok(Some(keychain))
+  }
+
+  /// Check addresses for outputs with `has_outputs`, until `GAP_LIMIT`
+  /// addresses past both the last address handed out and the last address
+  /// with outputs have been checked, and persist the next index past the last
+  /// address with outputs, so that those addresses are not handed out again.
+  pub(crate) fn scan(
+    &self,
+    database: &Database,
+    mut has_outputs: impl FnMut(&Address) -> Result<bool>,
+  ) -> Result {
+    for change in [false, true] {
+      let handed_out = Self::next_index(database, change)?;
+
+      let mut next = handed_out;
+      let mut index = 0;
+
+      while index < next + GAP_LIMIT {
+        if has_outputs(&self.derive(change, index)?)? {
+          next = next.max(index + 1);
+        }
+
+        index += 1;
+      }
+
+      if next > handed_out {
+        let wtx = database.begin_write()?;
+
+        wtx
+          .open_table(STATISTICS)?
+          .insert(&Self::statistic(change).key(), &u64::from(next))?;
+
+        wtx.commit()?;
+      }
+    }
+
+    Ok(())
+  }
+
+  pub(crate) fn store(database: &Database, descriptors: [String; 2]) -> Result {
+    let wtx = database.begin_write()?;
+
+    {
+      let mut table = wtx.open_table(CHANGE_TO_DESCRIPTOR)?;
+
+      for (change, descriptor) in [false, true].into_iter().zip(descriptors) {
+        table.insert(change, descriptor.as_str())?;
+      }
+    }
+
+    wtx.commit()?;
+
+    Ok(())
+  }
+
+  pub(crate) fn descriptors(&self) -> Vec<(bool, String)> {
+    [false, true]
+      .into_iter()
+      .zip(&self.descriptors)
+      .map(|(change, (descriptor, key_map))| (change, descriptor.to_string_with_secret(key_map)))
+      .collect()
+  }
+
+  pub(crate) fn receive_descriptor(
+    &self,
+  ) -> &miniscript::descriptor::Descriptor<DescriptorPublicKey> {
+    &self.descriptors[0].0
+  }
+
+  pub(crate) fn is_mine(&self, script_pubkey: &Script) -> bool {
+    self.derivations.lock().unwrap().contains_key(script_pubkey)
+  }
+
+  pub(crate) fn next_address(&self, database: &Database, change: bool) -> Result<Address> {
+    let statistic = Self::statistic(change);
+
+    let wtx = database.begin_write()?;
+
+    let index = {
+      let mut statistics = wtx.open_table(STATISTICS)?;
+
+      let index = statistics
+        .get(&statistic.key())?
+        .map(|next| next.value())
+        .unwrap_or_default();
+
+      statistics.insert(&statistic.key(), &(index + 1))?;
+
+      u32::try_from(index).context("wallet address index overflow")?
+    };
+
+    wtx.commit()?;
+
+    // keep `GAP_LIMIT` addresses past the last address handed out derived
+    self.derive(change, index + GAP_LIMIT)?;
+
+    self.derive(change, index)
+  }
+
+  /// Sign every input spending one of the keychain's outputs that does not
+  /// already have a witness. `prevouts` must contain the outputs spent by
+  /// all inputs, which taproot signature hashes commit to.
+  pub(crate) fn sign(
+    &self,
+    transaction: &Transaction,
+    prevouts: &BTreeMap<OutPoint, TxOut>,
+    sighash_type: TapSighashType,
+  ) -> Result<Transaction> {
+    let prevouts = transaction
+      .input
+      .iter()
+      .map(|input| {
+        prevouts
+          .get(&input.previous_output)
+          .cloned()
+          .ok_or_else(|| anyhow!("output {} being spent not found", input.previous_output))
+      })
+      .collect::<Result<Vec<TxOut>>>()?;
+
+    let mut sighash_cache = SighashCache::new(transaction);
+
+    let mut signed = transaction.clone();
+
+    for (i, (input, prevout)) in signed.input.iter_mut().zip(&prevouts).enumerate() {
+      if !input.witness.is_empty() {
+        continue;
+      }
+
+      let Some((change, index)) = self
+        .derivations
+        .lock()
+        .unwrap()
+        .get(&prevout.script_pubkey)
+        .copied()
+      else {
+        bail!(
+          "output {} is not spendable by this wallet",
+          input.previous_output
+        );
+      };
+
+      let sighash = sighash_cache.taproot_key_spend_signature_hash(
+        i,
+        &Prevouts::All(&prevouts),
+        sighash_type,
+      )?;
+
+      let signature = self.secp.sign_schnorr(
+        &Message::from(sighash),
+        &self
+          .key_pair(change, index)?
+          .tap_tweak(&self.secp, None)
+          .to_inner(),
+      );
+
+      input.witness = Witness::from_slice(&[taproot::Signature {
+        sig: signature,
+        hash_ty: sighash_type,
+      }
+      .to_vec()]);
+    }
+
+    Ok(signed)
+  }
+
+  fn statistic(change: bool) -> Statistic {
+    if change {
+      Statistic::NextChangeIndex
+    } else {
+      Statistic::NextReceiveIndex
+    }
+  }
+
+  fn next_index(database: &Database, change: bool) -> Result<u32> {
+    Ok(
+      database
+        .begin_read()?
+        .open_table(STATISTICS)?
+        .get(&Self::statistic(change).key())?
+        .map(|next| u32::try_from(next.value()).unwrap())
+        .unwrap_or_default(),
+    )
+  }
+
+  /// Derive the address at `index`, and remember it so that its outputs are
+  /// recognized and can be signed for.
+  fn derive(&self, change: bool, index: u32) -> Result<Address> {
+    let address = self.address(change, index)?;
+
+    self
+      .derivations
+      .lock()
+      .unwrap()
+      .insert(address.script_pubkey(), (change, index));
+
+    Ok(address)
+  }
+
+  fn address(&self, change: bool, index: u32) -> Result<Address> {
+    Ok(
+      self.descriptors[usize::from(change)]
+        .0
+        .at_derivation_index(index)?
+        .address(self.network)?,
+    )
+  }
+
+  fn key_pair(&self, change: bool, index: u32) -> Result<KeyPair> {
+    let Some(DescriptorSecretKey::XPrv(secret_key)) =
+      self.descriptors[usize::from(change)].1.values().next()
+    else {
+      bail!("wallet descriptor does not contain an extended private key");
+    };
+
+    Ok(
+      secret_key
+        .xkey
+        .derive_priv(
+          &self.secp,
+          &secret_key
+            .derivation_path
+            .child(ChildNumber::Normal { index }),
+        )?
+        .to_keypair(&self.secp),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {
+    super::*,
+    bitcoin::{key::XOnlyPublicKey, secp256k1::schnorr::Signature},
+  };
+
+  fn keychain() -> (TempDir, Database, Keychain) {
+    let tempdir = TempDir::new().unwrap();
+
+    let database = Database::create(tempdir.path().join("wallet.redb")).unwrap();
+
+    let wtx = database.begin_write().unwrap();
+    wtx.open_table(STATISTICS).unwrap();
+    wtx.commit().unwrap();
+
+    Keychain::store(
+      &database,
+      Wallet::derive_descriptors(Network::Regtest, [0; 64])
+        .unwrap()
+        .map(|(descriptor, key_map)| descriptor.to_string_with_secret(&key_map)),
+    )
+    .unwrap();
+
+    let keychain = Keychain::load(&database, Network::Regtest)
+      .unwrap()
+      .unwrap();
+
+    (tempdir, database, keychain)
+  }
+
+  #[test]
+  fn signatures_are_valid_key_path_spends() {
+    let (_tempdir, database, keychain) = keychain();
+
+    let receive_address = keychain.next_address(&database, false).unwrap();
+    let change_address = keychain.next_address(&database, true).unwrap();
+
+    assert_ne!(receive_address, change_address);
+    assert_eq!(
+      keychain.next_address(&database, false).unwrap(),
+      keychain.address(false, 1).unwrap()
+    );
+
+    let prevouts = [receive_address, change_address]
+      .iter()
+      .enumerate()
+      .map(|(i, address)| {
+        (
+          outpoint(u64::try_from(i).unwrap() + 1),
+          TxOut {
+            value: 10_000,
+            script_pubkey: address.script_pubkey(),
+          },
+        )
+      })
+      .collect::<BTreeMap<OutPoint, TxOut>>();
+
+    let unsigned = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: prevouts
+        .keys()
+        .map(|outpoint| TxIn {
+          previous_output: *outpoint,
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        })
+        .collect(),
+      output: vec![TxOut {
+        value: 15_000,
+        script_pubkey: change(0).script_pubkey(),
+      }],
+    };
+
+    let signed = keychain
+      .sign(&unsigned, &prevouts, TapSighashType::Default)
+      .unwrap();
+
+    let prevouts = prevouts.into_values().collect::<Vec<TxOut>>();
+
+    let secp = Secp256k1::verification_only();
+
+    for (i, input) in signed.input.iter().enumerate() {
+      let sighash = SighashCache::new(&unsigned)
+        .taproot_key_spend_signature_hash(i, &Prevouts::All(&prevouts), TapSighashType::Default)
+        .unwrap();
+
+      let output_key =
+        XOnlyPublicKey::from_slice(&prevouts[i].script_pubkey.as_bytes()[2..]).unwrap();
+
+      secp
+        .verify_schnorr(
+          &Signature::from_slice(&input.witness[0]).unwrap(),
+          &Message::from(sighash),
+          &output_key,
+        )
+        .unwrap();
+    }
+  }
+
+  #[test]
+  fn single_anyone_can_pay_signatures_survive_added_inputs_and_outputs() {
+    let (_tempdir, database, keychain) = keychain();
+
+    let prevout = TxOut {
+      value: 10_000,
+      script_pubkey: keychain
+        .next_address(&database, false)
+        .unwrap()
+        .script_pubkey(),
+    };
+
+    let payment = TxOut {
+      value: 50_000,
+      script_pubkey: change(0).script_pubkey(),
+    };
+
+    let unsigned = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint(1),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![payment.clone()],
+    };
+
+    let signed = keychain
+      .sign(
+        &unsigned,
+        &[(outpoint(1), prevout.clone())].into_iter().collect(),
+        TapSighashType::SinglePlusAnyoneCanPay,
+      )
+      .unwrap();
+
+    let signature = taproot::Signature::from_slice(&signed.input[0].witness[0]).unwrap();
+
+    assert_eq!(signature.hash_ty, TapSighashType::SinglePlusAnyoneCanPay);
+
+    let completed = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![
+        TxIn {
+          previous_output: outpoint(2),
+          script_sig: ScriptBuf::new(),
+          sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+          witness: Witness::new(),
+        },
+        signed.input[0].clone(),
+      ],
+      output: vec![tx_out(20_000, address()), payment, tx_out(1_000, address())],
+    };
+
+    let sighash = SighashCache::new(&completed)
+      .taproot_key_spend_signature_hash(
+        1,
+        &Prevouts::One(1, &prevout),
+        TapSighashType::SinglePlusAnyoneCanPay,
+      )
+      .unwrap();
+
+    Secp256k1::verification_only()
+      .verify_schnorr(
+        &signature.sig,
+        &Message::from(sighash),
+        &XOnlyPublicKey::from_slice(&prevout.script_pubkey.as_bytes()[2..]).unwrap(),
+      )
+      .unwrap();
+  }
+
+  #[test]
+  fn foreign_outputs_are_not_signed() {
+    let (_tempdir, _database, keychain) = keychain();
+
+    let unsigned = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: outpoint(1),
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: Vec::new(),
+    };
+
+    assert_eq!(
+      keychain
+        .sign(
+          &unsigned,
+          &[(outpoint(1), tx_out(10_000, address()))]
+            .into_iter()
+            .collect(),
+          TapSighashType::Default,
+        )
+        .unwrap_err()
+        .to_string(),
+      format!("output {} is not spendable by this wallet", outpoint(1)),
+    );
+  }
+}
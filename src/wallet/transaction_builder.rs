@@ -103,6 +103,7 @@ pub struct TransactionBuilder {
   outputs: Vec<(Address, Amount)>,
   recipient: Address,
   runic_utxos: BTreeSet<OutPoint>,
+  satisfaction_weight: usize,
   target: Target,
   unused_change_addresses: Vec<Address>,
   utxos: BTreeSet<OutPoint>,
@@ -111,9 +112,9 @@ pub struct TransactionBuilder {
 type Result<T> = std::result::Result<T, Error>;
 
 impl TransactionBuilder {
-  const ADDITIONAL_INPUT_VBYTES: usize = 58;
   const ADDITIONAL_OUTPUT_VBYTES: usize = 43;
   const SCHNORR_SIGNATURE_SIZE: usize = 64;
+  pub(crate) const KEY_PATH_SATISFACTION_WEIGHT: usize = 1 + Self::SCHNORR_SIGNATURE_SIZE;
   pub(crate) const MAX_POSTAGE: Amount = Amount::from_sat(2 * 10_000);
 
   pub fn new(
@@ -139,11 +140,34 @@ impl TransactionBuilder {
       outputs: Vec::new(),
       recipient,
       runic_utxos,
+      satisfaction_weight: Self::KEY_PATH_SATISFACTION_WEIGHT,
       target,
       unused_change_addresses: change.to_vec(),
     }
   }
 
+  /// Set the weight of the witness needed to spend one of the wallet's
+  /// inputs, as computed by miniscript's `max_weight_to_satisfy`. Defaults to
+  /// that of a taproot key path spend.
+  pub(crate) fn satisfaction_weight(self, satisfaction_weight: usize) -> Self {
+    Self {
+      satisfaction_weight,
+      ..self
+    }
+  }
+
+  /// Build a placeholder witness with the same weight as one satisfying an
+  /// input with the given satisfaction weight, for use in fee estimation.
+  pub(crate) fn dummy_witness(satisfaction_weight: usize) -> Witness {
+    let len = if satisfaction_weight <= 0xfd {
+      satisfaction_weight.saturating_sub(1)
+    } else {
+      max(satisfaction_weight - 3, 0xfd)
+    };
+
+    Witness::from_slice(&[vec![0; len]])
+  }
+
   pub fn build_transaction(self) -> Result<Transaction> {
     if self.change_addresses.len() < 2 {
       return Err(Error::DuplicateAddress(
@@ -302,7 +326,7 @@ impl TransactionBuilder {
 
     if let Some(mut deficit) = total.checked_sub(self.outputs.last().unwrap().1) {
       while deficit > Amount::ZERO {
-        let additional_fee = self.fee_rate.fee(Self::ADDITIONAL_INPUT_VBYTES);
+        let additional_fee = self.fee_rate.fee(self.additional_input_vbytes());
 
         let needed = deficit
           .checked_add(additional_fee)
@@ -416,12 +440,13 @@ impl TransactionBuilder {
   }
 
   /// Estimate the size in virtual bytes of the transaction under construction.
-  /// We initialize wallets with taproot descriptors only, so we know that all
-  /// inputs are taproot key path spends, which allows us to know that witnesses
-  /// will all consist of single Schnorr signatures.
+  /// All inputs come from the same wallet, so we assume that each of them is
+  /// satisfied by a witness of `satisfaction_weight`, which for single-key
+  /// `ord` wallets is a taproot key path spend with a single Schnorr signature.
   fn estimate_vbytes(&self) -> usize {
     Self::estimate_vbytes_with(
       self.inputs.len(),
+      self.satisfaction_weight,
       self
         .outputs
         .iter()
@@ -431,7 +456,11 @@ impl TransactionBuilder {
     )
   }
 
-  fn estimate_vbytes_with(inputs: usize, outputs: Vec<Address>) -> usize {
+  fn estimate_vbytes_with(
+    inputs: usize,
+    satisfaction_weight: usize,
+    outputs: Vec<Address>,
+  ) -> usize {
     Transaction {
       version: 2,
       lock_time: LockTime::ZERO,
@@ -440,7 +469,7 @@ impl TransactionBuilder {
           previous_output: OutPoint::null(),
           script_sig: ScriptBuf::new(),
           sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
-          witness: Witness::from_slice(&[&[0; Self::SCHNORR_SIGNATURE_SIZE]]),
+          witness: Self::dummy_witness(satisfaction_weight),
         })
         .collect(),
       output: outputs
@@ -454,6 +483,11 @@ impl TransactionBuilder {
     .vsize()
   }
 
+  fn additional_input_vbytes(&self) -> usize {
+    Self::estimate_vbytes_with(1, self.satisfaction_weight, Vec::new())
+      - Self::estimate_vbytes_with(0, self.satisfaction_weight, Vec::new())
+  }
+
   fn estimate_fee(&self) -> Amount {
     self.fee_rate.fee(self.estimate_vbytes())
   }
@@ -614,7 +648,7 @@ impl TransactionBuilder {
 
     let mut modified_tx = transaction.clone();
     for input in &mut modified_tx.input {
-      input.witness = Witness::from_slice(&[&[0; 64]]);
+      input.witness = Self::dummy_witness(self.satisfaction_weight);
     }
     let expected_fee = self.fee_rate.fee(modified_tx.vsize());
 
@@ -777,6 +811,7 @@ mod tests {
       inscriptions: BTreeMap::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      satisfaction_weight: TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       recipient: recipient(),
       unused_change_addresses: vec![change(0), change(1)],
       change_addresses: vec![change(0), change(1)].into_iter().collect(),
@@ -1314,6 +1349,7 @@ mod tests {
       utxos: BTreeSet::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      satisfaction_weight: TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       recipient: recipient(),
@@ -1345,6 +1381,7 @@ mod tests {
       utxos: BTreeSet::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      satisfaction_weight: TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       recipient: recipient(),
@@ -1598,16 +1635,87 @@ mod tests {
 
   #[test]
   fn additional_input_size_is_correct() {
-    let before = TransactionBuilder::estimate_vbytes_with(0, Vec::new());
-    let after = TransactionBuilder::estimate_vbytes_with(1, Vec::new());
-    assert_eq!(after - before, TransactionBuilder::ADDITIONAL_INPUT_VBYTES);
+    let before = TransactionBuilder::estimate_vbytes_with(
+      0,
+      TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+      Vec::new(),
+    );
+    let after = TransactionBuilder::estimate_vbytes_with(
+      1,
+      TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+      Vec::new(),
+    );
+    assert_eq!(after - before, 58);
+  }
+
+  #[test]
+  fn dummy_witness_has_satisfaction_weight() {
+    for satisfaction_weight in [1, 65, 100, 253, 256, 300] {
+      assert_eq!(
+        TransactionBuilder::dummy_witness(satisfaction_weight).serialized_len(),
+        satisfaction_weight + 1,
+      );
+    }
+
+    for satisfaction_weight in [254, 255] {
+      assert!(
+        TransactionBuilder::dummy_witness(satisfaction_weight).serialized_len()
+          > satisfaction_weight + 1,
+      );
+    }
+  }
+
+  #[test]
+  fn build_transaction_with_script_path_inputs() {
+    let satisfaction_weight = miniscript::Descriptor::<bitcoin::PublicKey>::from_str(
+      "wsh(multi(2,\
+        0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798,\
+        02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5,\
+        02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9))",
+    )
+    .unwrap()
+    .max_weight_to_satisfy()
+    .unwrap();
+
+    let utxos = vec![(outpoint(1), tx_out(10_000, address()))];
+
+    let fee_rate = FeeRate::try_from(1.0).unwrap();
+
+    let transaction = TransactionBuilder::new(
+      satpoint(1, 0),
+      BTreeMap::new(),
+      utxos.into_iter().collect(),
+      BTreeSet::new(),
+      BTreeSet::new(),
+      recipient(),
+      [change(0), change(1)],
+      fee_rate,
+      Target::Postage,
+    )
+    .satisfaction_weight(satisfaction_weight)
+    .build_transaction()
+    .unwrap();
+
+    let mut signed = transaction.clone();
+    signed.input[0].witness = TransactionBuilder::dummy_witness(satisfaction_weight);
+
+    assert!(satisfaction_weight > TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT);
+    assert_eq!(
+      10_000 - transaction.output[0].value,
+      fee_rate.fee(signed.vsize()).to_sat()
+    );
   }
 
   #[test]
   fn additional_output_size_is_correct() {
-    let before = TransactionBuilder::estimate_vbytes_with(0, Vec::new());
+    let before = TransactionBuilder::estimate_vbytes_with(
+      0,
+      TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
+      Vec::new(),
+    );
     let after = TransactionBuilder::estimate_vbytes_with(
       0,
+      TransactionBuilder::KEY_PATH_SATISFACTION_WEIGHT,
       vec![
         "bc1pxwww0ct9ue7e8tdnlmug5m2tamfn7q06sahstg39ys4c9f3340qqxrdu9k"
           .parse::<Address<NetworkUnchecked>>()
@@ -46,7 +46,20 @@ impl WalletConstructor {
   pub(crate) fn build(self) -> Result<Wallet> {
     let database = Wallet::open_database(&self.name, &self.settings)?;
 
-    let bitcoin_client = {
+    let keychain = Keychain::load(&database, self.settings.chain().network())?;
+
+    let (bitcoin_client, spending_descriptor, watch_only) = if let Some(keychain) = &keychain {
+      ensure!(
+        self.settings.chain() != Chain::Mainnet,
+        "native wallets store private keys unencrypted and are not supported on mainnet"
+      );
+
+      (
+        Wallet::check_version(self.settings.bitcoin_rpc_client(None)?)?,
+        Some(keychain.receive_descriptor().clone()),
+        false,
+      )
+    } else {
       let client =
         Wallet::check_version(self.settings.bitcoin_rpc_client(Some(self.name.clone()))?)?;
 
@@ -54,11 +67,19 @@ impl WalletConstructor {
         client.load_wallet(&self.name)?;
       }
 
-      if client.get_wallet_info()?.private_keys_enabled {
-        Wallet::check_descriptors(&self.name, client.list_descriptors(None)?.descriptors)?;
+      let private_keys_enabled = client.get_wallet_info()?.private_keys_enabled;
+
+      let descriptors = client.list_descriptors(None)?.descriptors;
+
+      if private_keys_enabled {
+        Wallet::check_descriptors(&self.name, descriptors.clone())?;
       }
 
-      client
+      (
+        client,
+        Wallet::spending_descriptor(&descriptors)?,
+        !private_keys_enabled,
+      )
     };
 
     let chain_block_count = bitcoin_client.get_block_count().unwrap() + 1;
@@ -81,21 +102,38 @@ impl WalletConstructor {
       }
     }
 
-    let mut utxos = Self::get_utxos(&bitcoin_client)?;
-    let locked_utxos = Self::get_locked_utxos(&bitcoin_client)?;
-    utxos.extend(locked_utxos.clone());
+    let status = self.get_server_status()?;
+
+    let (utxos, locked_utxos) = if let Some(keychain) = &keychain {
+      ensure!(
+        status.address_index,
+        "native wallets require `ord server` to be run with `--index-addresses`"
+      );
+
+      (
+        Self::remove_pending_spends(
+          &bitcoin_client,
+          &database,
+          self.get_keychain_utxos(&database, keychain)?,
+        )?,
+        BTreeMap::new(),
+      )
+    } else {
+      let mut utxos = Self::get_utxos(&bitcoin_client)?;
+      let locked_utxos = Self::get_locked_utxos(&bitcoin_client)?;
+      utxos.extend(locked_utxos.clone());
+      (utxos, locked_utxos)
+    };
 
     let output_info = self.get_output_info(utxos.clone().into_keys().collect())?;
 
     let inscriptions = output_info
-      .iter()
-      .flat_map(|(_output, info)| info.inscriptions.clone())
+      .values()
+      .flat_map(|info| info.inscriptions.clone())
       .collect::<Vec<InscriptionId>>();
 
     let (inscriptions, inscription_info) = self.get_inscriptions(&inscriptions)?;
 
-    let status = self.get_server_status()?;
-
     Ok(Wallet {
       bitcoin_client,
       database,
@@ -103,12 +141,16 @@ impl WalletConstructor {
       has_sat_index: status.sat_index,
       inscription_info,
       inscriptions,
+      keychain,
       locked_utxos,
+      name: self.name,
       ord_client: self.ord_client,
       output_info,
       rpc_url: self.rpc_url,
       settings: self.settings,
+      spending_descriptor,
       utxos,
+      watch_only,
     })
   }
 
@@ -160,6 +202,78 @@ impl WalletConstructor {
     Ok((inscriptions, inscription_infos))
   }
 
+  fn get_keychain_utxos(
+    &self,
+    database: &Database,
+    keychain: &Keychain,
+  ) -> Result<BTreeMap<OutPoint, TxOut>> {
+    let mut script_pubkeys = BTreeMap::new();
+
+    keychain.scan(database, |address| {
+      let response = self.get(&format!("/address/{address}"))?;
+
+      if !response.status().is_success() {
+        bail!("wallet failed get address {address}: {}", response.text()?);
+      }
+
+      let outputs = serde_json::from_str::<api::AddressInfo>(&response.text()?)?.outputs;
+
+      for output in &outputs {
+        script_pubkeys.insert(*output, address.script_pubkey());
+      }
+
+      Ok(!outputs.is_empty())
+    })?;
+
+    Ok(
+      self
+        .get_output_info(script_pubkeys.keys().cloned().collect())?
+        .into_iter()
+        .map(|(outpoint, info)| {
+          (
+            outpoint,
+            TxOut {
+              value: info.value,
+              script_pubkey: script_pubkeys[&outpoint].clone(),
+            },
+          )
+        })
+        .collect(),
+    )
+  }
+
+  /// The `ord` server only knows about confirmed outputs, so remove outputs
+  /// spent by transactions the wallet has broadcast, and forget transactions
+  /// which are no longer in the mempool, because they were either confirmed or
+  /// evicted. Like bitcoind wallets, outputs of unconfirmed transactions are
+  /// not spendable until they confirm.
+  fn remove_pending_spends(
+    bitcoin_client: &Client,
+    database: &Database,
+    mut utxos: BTreeMap<OutPoint, TxOut>,
+  ) -> Result<BTreeMap<OutPoint, TxOut>> {
+    for transaction in Wallet::pending_transactions(database)? {
+      let txid = transaction.txid();
+
+      match bitcoin_client.get_mempool_entry(&txid) {
+        Ok(_) => {}
+        Err(bitcoincore_rpc::Error::JsonRpc(bitcoincore_rpc::jsonrpc::Error::Rpc(
+          bitcoincore_rpc::jsonrpc::error::RpcError { code: -5, .. },
+        ))) => {
+          Wallet::clear_pending_transaction(database, txid)?;
+          continue;
+        }
+        Err(err) => return Err(err.into()),
+      }
+
+      for input in &transaction.input {
+        utxos.remove(&input.previous_output);
+      }
+    }
+
+    Ok(utxos)
+  }
+
   fn get_utxos(bitcoin_client: &Client) -> Result<BTreeMap<OutPoint, TxOut>> {
     Ok(
       bitcoin_client
@@ -90,3 +90,51 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn events_requires_event_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("index events --from-height 0")
+    .core(&core)
+    .expected_stderr(
+      "error: `ord index events` requires index created with `--index-events` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn events_are_written_as_json_lines() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _) = inscribe(&core, &ord);
+
+  let height = u32::try_from(core.height()).unwrap();
+
+  let stdout = CommandBuilder::new(format!(
+    "--index-events index events --from-height {height} --to-height {height}"
+  ))
+  .core(&core)
+  .stdout_regex(".*")
+  .run_and_extract_stdout();
+
+  let lines = stdout
+    .lines()
+    .map(|line| serde_json::from_str::<ord::subcommand::index::events::EventLine>(line).unwrap())
+    .collect::<Vec<_>>();
+
+  assert_eq!(lines.len(), 1);
+  assert_eq!(lines[0].height, height);
+  assert_eq!(lines[0].event.inscription_ids(), [inscription]);
+
+  CommandBuilder::new(format!(
+    "--index-events index events --from-height {}",
+    height + 1
+  ))
+  .core(&core)
+  .run_and_extract_stdout();
+}
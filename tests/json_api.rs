@@ -32,6 +32,7 @@ fn get_sat_without_sat_index() {
       timestamp: 0,
       inscriptions: Vec::new(),
       charms: vec![Charm::Uncommon],
+      satributes: vec![Satribute::Uncommon],
     }
   )
 }
@@ -70,6 +71,7 @@ fn get_sat_with_inscription_and_sat_index() {
       timestamp: 1,
       inscriptions: vec![inscription_id],
       charms: vec![Charm::Coin, Charm::Uncommon],
+      satributes: vec![Satribute::Coin, Satribute::Uncommon, Satribute::Vintage],
     }
   )
 }
@@ -126,6 +128,7 @@ fn get_sat_with_inscription_on_common_sat_and_more_inscriptions() {
       timestamp: 3,
       inscriptions: vec![inscription_id],
       charms: Vec::new(),
+      satributes: vec![Satribute::Vintage],
     }
   )
 }
@@ -488,6 +491,7 @@ fn get_status() {
   pretty_assert_eq!(
     status_json,
     api::Status {
+      address_index: false,
       blessed_inscriptions: 1,
       chain: Chain::Regtest,
       content_type_counts: vec![(Some("text/plain;charset=utf-8".into()), 1)],
@@ -524,7 +528,7 @@ fn get_runes() {
 
   core.mine_blocks(1);
 
-  let response = ord.json_request(format!("/rune/{}", a.output.rune.unwrap().rune));
+  let response = ord.json_request(format!("/rune/{}", a.output.rune.clone().unwrap().rune));
   assert_eq!(response.status(), StatusCode::OK);
 
   let rune_json: api::Rune = serde_json::from_str(&response.text().unwrap()).unwrap();
@@ -532,6 +536,7 @@ fn get_runes() {
   pretty_assert_eq!(
     rune_json,
     api::Rune {
+      circulating: 1000,
       entry: RuneEntry {
         block: a.id.block,
         burned: 0,
@@ -549,12 +554,14 @@ fn get_runes() {
         timestamp: 10,
         turbo: false,
       },
+      holders: 1,
       id: RuneId { block: 10, tx: 1 },
       mintable: false,
       parent: Some(InscriptionId {
         txid: a.output.reveal,
         index: 0,
       }),
+      top_holders: vec![(a.output.rune.clone().unwrap().location.unwrap(), 1000)],
     }
   );
 
@@ -16,7 +16,8 @@ use {
     InscriptionId, RuneEntry,
   },
   ordinals::{
-    Artifact, Charm, Edict, Pile, Rarity, Rune, RuneId, Runestone, Sat, SatPoint, SpacedRune,
+    Artifact, Charm, Edict, Pile, Rarity, Rune, RuneId, Runestone, Sat, SatPoint, Satribute,
+    SpacedRune,
   },
   pretty_assertions::assert_eq as pretty_assert_eq,
   regex::Regex,
@@ -64,11 +65,14 @@ mod json_api;
 mod list;
 mod parse;
 mod runes;
+mod scan;
 mod server;
 mod settings;
 mod subsidy;
 mod supply;
+mod trace;
 mod traits;
+mod verify_collection;
 mod version;
 mod wallet;
 
@@ -160,6 +164,7 @@ fn etch(core: &mockcore::Handle, ord: &TestServer, rune: Rune) -> Etched {
     ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         supply: "1000".parse().unwrap(),
         divisibility: 0,
         terms: None,
@@ -220,6 +225,7 @@ fn batch(core: &mockcore::Handle, ord: &TestServer, batchfile: batch::File) -> E
   let parent = output.inscriptions[0].id;
 
   let batch::Etching {
+    allocations,
     divisibility,
     premine,
     rune,
@@ -338,6 +344,8 @@ fn batch(core: &mockcore::Handle, ord: &TestServer, batchfile: batch::File) -> E
   <dd>{premine} {symbol}</dd>
   <dt>burned</dt>
   <dd>0 {symbol}</dd>
+  <dt>circulating</dt>
+  <dd>{premine} {symbol}</dd>.*
   <dt>divisibility</dt>
   <dd>{divisibility}</dd>
   <dt>symbol</dt>
@@ -354,12 +362,27 @@ fn batch(core: &mockcore::Handle, ord: &TestServer, batchfile: batch::File) -> E
   );
 
   let batch::RuneInfo {
+    allocations: allocation_infos,
     destination,
     location,
     rune: _,
   } = output.rune.clone().unwrap();
 
-  if premine.to_integer(divisibility).unwrap() > 0 {
+  assert_eq!(allocation_infos.len(), allocations.len());
+
+  let mut allocated = 0;
+
+  for (allocation, info) in allocations.iter().zip(&allocation_infos) {
+    assert_eq!(info.address, allocation.address);
+    assert_eq!(info.amount, allocation.amount);
+    assert_eq!(
+      core.address(info.location),
+      allocation.address.clone().assume_checked()
+    );
+    allocated += allocation.amount.to_integer(divisibility).unwrap();
+  }
+
+  if premine.to_integer(divisibility).unwrap() > allocated {
     let destination = destination
       .unwrap()
       .clone()
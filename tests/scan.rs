@@ -0,0 +1,95 @@
+use super::*;
+
+#[test]
+fn scan_outpoint() {
+  let core = mockcore::spawn();
+
+  core.mine_blocks(1);
+
+  let outpoint = OutPoint {
+    txid: core.tx(1, 0).into(),
+    vout: 0,
+  };
+
+  let scan = CommandBuilder::new(format!("--index-sats scan {outpoint}"))
+    .core(&core)
+    .run_and_deserialize_output::<Vec<api::ScanOutput>>();
+
+  assert_eq!(scan.len(), 1);
+  assert_eq!(scan[0].output, outpoint);
+
+  let sats = scan[0].sats.as_ref().unwrap();
+
+  assert_eq!(sats.len(), 50);
+
+  pretty_assert_eq!(
+    sats[0],
+    api::ScanRange {
+      charms: vec![Charm::Coin, Charm::Uncommon],
+      offset: 0,
+      rarity: Rarity::Uncommon,
+      sat: Sat(50 * COIN_VALUE),
+      size: 1,
+    },
+  );
+}
+
+#[test]
+fn scan_file_of_outpoints() {
+  let core = mockcore::spawn();
+
+  core.mine_blocks(2);
+
+  let first = OutPoint {
+    txid: core.tx(1, 0).into(),
+    vout: 0,
+  };
+
+  let second = OutPoint {
+    txid: core.tx(2, 0).into(),
+    vout: 0,
+  };
+
+  let scan = CommandBuilder::new("--index-sats scan outpoints.txt")
+    .write(
+      "outpoints.txt",
+      format!("# deposits\n{first}\n\n{second}\n"),
+    )
+    .core(&core)
+    .run_and_deserialize_output::<Vec<api::ScanOutput>>();
+
+  assert_eq!(
+    scan
+      .iter()
+      .map(|output| (output.output, output.sats.as_ref().unwrap()[0].sat))
+      .collect::<Vec<(OutPoint, Sat)>>(),
+    [
+      (first, Sat(50 * COIN_VALUE)),
+      (second, Sat(100 * COIN_VALUE))
+    ],
+  );
+}
+
+#[test]
+fn scan_address_requires_address_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--index-sats scan bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+    .core(&core)
+    .expected_stderr(
+      "error: scanning an address requires index created with `--index-addresses` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn scan_requires_sat_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(format!("scan {}", OutPoint::null()))
+    .core(&core)
+    .expected_stderr("error: scan requires index created with `--index-sats` flag\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
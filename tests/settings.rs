@@ -20,11 +20,17 @@ fn default() {
   "height_limit": null,
   "hidden": \[\],
   "index": ".*index\.redb",
+  "index_addresses": false,
   "index_cache_size": \d+,
+  "index_content_hashes": false,
+  "index_events": false,
   "index_runes": false,
   "index_sats": false,
+  "index_search": false,
+  "index_spent_runes": false,
   "index_spent_sats": false,
   "index_transactions": false,
+  "index_transfer_history": false,
   "integration_test": false,
   "no_index_inscriptions": false,
   "server_password": null,
@@ -0,0 +1,66 @@
+use {super::*, ord::api::SatTrace};
+
+#[test]
+fn trace_lists_every_output_that_held_sat() {
+  let core = mockcore::spawn();
+
+  core.mine_blocks(1);
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(1, 0, 0, Default::default())],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  pretty_assert_eq!(
+    CommandBuilder::new(format!("--index-spent-sats trace {}", 50 * COIN_VALUE))
+      .core(&core)
+      .run_and_deserialize_output::<SatTrace>(),
+    SatTrace {
+      sat: Sat(50 * COIN_VALUE),
+      locations: vec![
+        ord::api::SatLocation {
+          height: 1,
+          satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid: core.tx(1, 0).into(),
+              vout: 0,
+            },
+            offset: 0,
+          },
+        },
+        ord::api::SatLocation {
+          height: 2,
+          satpoint: SatPoint {
+            outpoint: OutPoint { txid, vout: 0 },
+            offset: 0,
+          },
+        },
+      ],
+      lost: false,
+    }
+  );
+}
+
+#[test]
+fn trace_requires_spent_sat_index() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("--index-sats trace 0")
+    .core(&core)
+    .expected_stderr("error: trace requires index created with `--index-spent-sats` flag\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn trace_unmined_sat_is_an_error() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new(format!("--index-spent-sats trace {}", 50 * COIN_VALUE))
+    .core(&core)
+    .expected_stderr("error: sat has not been mined as of index height\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
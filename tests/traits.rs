@@ -1,4 +1,8 @@
-use {super::*, ord::subcommand::traits::Output, ordinals::Rarity};
+use {
+  super::*,
+  ord::subcommand::traits::Output,
+  ordinals::{Rarity, Satribute},
+};
 
 #[test]
 fn traits_command_prints_sat_traits() {
@@ -15,6 +19,12 @@ fn traits_command_prints_sat_traits() {
       period: 0,
       offset: 0,
       rarity: Rarity::Mythic,
+      satributes: vec![
+        Satribute::Coin,
+        Satribute::Mythic,
+        Satribute::Palindrome,
+        Satribute::Vintage,
+      ],
     }
   );
 }
@@ -33,6 +43,7 @@ fn traits_command_for_last_sat() {
       period: 3437,
       offset: 0,
       rarity: Rarity::Uncommon,
+      satributes: vec![Satribute::Uncommon],
     }
   );
 }
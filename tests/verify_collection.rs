@@ -0,0 +1,142 @@
+use {
+  super::*,
+  bitcoin::hashes::{sha256, Hash},
+  ord::{manifest, Manifest},
+};
+
+#[test]
+fn collection_with_manifest_can_be_verified() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (parent, _reveal) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new("wallet batch --fee-rate 1 --batch batch.yaml")
+    .write("a.txt", "foo")
+    .write("b.txt", "bar")
+    .write(
+      "batch.yaml",
+      format!(
+        "parent: {parent}\nmode: separate-outputs\nmanifest: true\ninscriptions:\n- file: a.txt\n  metadata:\n    eyes: red\n- file: b.txt\n"
+      ),
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  assert_eq!(output.inscriptions.len(), 3);
+
+  let children = [output.inscriptions[0].id, output.inscriptions[1].id];
+  let manifest = output.inscriptions[2].id;
+
+  let response = ord.request(format!("/content/{manifest}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<Manifest>(&response.text().unwrap()).unwrap(),
+    Manifest {
+      items: vec![
+        manifest::Item {
+          sha256: sha256::Hash::hash(b"foo"),
+          traits: Some(serde_json::json!({"eyes": "red"})),
+        },
+        manifest::Item {
+          sha256: sha256::Hash::hash(b"bar"),
+          traits: None,
+        },
+      ],
+    }
+  );
+
+  pretty_assert_eq!(
+    CommandBuilder::new(format!("verify-collection {parent}"))
+      .core(&core)
+      .run_and_deserialize_output::<api::CollectionVerification>(),
+    api::CollectionVerification {
+      manifest,
+      mismatched: Vec::new(),
+      missing: Vec::new(),
+      parent,
+      parent_number: 0,
+      verified: children.into(),
+    }
+  );
+
+  let impostor = CommandBuilder::new(format!(
+    "wallet inscribe --fee-rate 1 --parent {parent} --file c.txt"
+  ))
+  .write("c.txt", "baz")
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Batch>()
+  .inscriptions[0]
+    .id;
+
+  core.mine_blocks(1);
+
+  let expected = api::CollectionVerification {
+    manifest,
+    mismatched: vec![impostor],
+    missing: Vec::new(),
+    parent,
+    parent_number: 0,
+    verified: children.into(),
+  };
+
+  pretty_assert_eq!(
+    CommandBuilder::new(format!("verify-collection {parent}"))
+      .core(&core)
+      .run_and_deserialize_output::<api::CollectionVerification>(),
+    expected
+  );
+
+  let response = ord.json_request(format!("/verify-collection/{parent}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  pretty_assert_eq!(
+    serde_json::from_str::<api::CollectionVerification>(&response.text().unwrap()).unwrap(),
+    expected
+  );
+
+  ord.assert_response_regex(
+    format!("/verify-collection/{parent}"),
+    format!(
+      ".*<h1><a href=/inscription/{parent}>Inscription 0</a> Collection</h1>.*<dt>items</dt>\\s*<dd>2</dd>\\s*<dt>verified</dt>\\s*<dd>2</dd>\\s*<dt>mismatched</dt>.*{impostor}.*"
+    ),
+  );
+}
+
+#[test]
+fn collection_without_manifest_is_an_error() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (parent, _reveal) = inscribe(&core, &ord);
+
+  CommandBuilder::new(format!("verify-collection {parent}"))
+    .core(&core)
+    .expected_stderr(format!(
+      "error: inscription {parent} has no collection manifest\n"
+    ))
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  let response = ord.request(format!("/verify-collection/{parent}"));
+
+  assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+  assert_eq!(
+    response.text().unwrap(),
+    format!("collection manifest for inscription {parent} not found"),
+  );
+}
@@ -3,13 +3,20 @@ use super::*;
 mod authentication;
 mod balance;
 mod batch_command;
+mod bump_fee;
+mod burn;
+mod cancel;
 mod cardinals;
+mod consolidate;
 mod create;
 mod dump;
+mod finalize_psbt;
 mod inscribe;
 mod inscriptions;
 mod label;
 mod mint;
+mod native;
+mod offer;
 mod outputs;
 mod receive;
 mod restore;
@@ -18,4 +25,5 @@ mod resume;
 mod sats;
 mod selection;
 mod send;
+mod split;
 mod transactions;
@@ -1431,6 +1431,7 @@ fn batch_can_etch_rune() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune,
         supply: "1000".parse().unwrap(),
@@ -1543,6 +1544,7 @@ fn batch_can_etch_turbo_rune() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune,
         supply: "1000".parse().unwrap(),
@@ -1589,6 +1591,7 @@ fn batch_can_etch_rune_without_premine() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune,
         supply: "1000".parse().unwrap(),
@@ -1669,6 +1672,7 @@ fn batch_inscribe_can_etch_rune_with_offset() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune: SpacedRune {
           rune: Rune(RUNE),
@@ -1743,6 +1747,7 @@ fn batch_inscribe_can_etch_rune_with_height() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune: SpacedRune {
           rune: Rune(RUNE),
@@ -1818,6 +1823,7 @@ fn etch_existing_rune_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -1860,6 +1866,7 @@ fn etch_reserved_rune_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune::reserved(0, 0),
@@ -1902,6 +1909,7 @@ fn etch_sub_minimum_rune_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(0),
@@ -1928,6 +1936,51 @@ fn etch_sub_minimum_rune_error() {
     .run_and_extract_stdout();
 }
 
+#[test]
+fn etch_with_psbt_error() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--regtest --index-runes wallet batch --fee-rate 0 --batch batch.yaml --psbt",
+  )
+  .write("inscription.txt", "foo")
+  .write(
+    "batch.yaml",
+    serde_yaml::to_string(&batch::File {
+      etching: Some(batch::Etching {
+        allocations: Vec::new(),
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        supply: "1000".parse().unwrap(),
+        premine: "1000".parse().unwrap(),
+        symbol: '¢',
+        terms: None,
+        turbo: false,
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.txt".into()),
+        ..default()
+      }],
+      ..default()
+    })
+    .unwrap(),
+  )
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: `--psbt` cannot be used to etch runes\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
 #[test]
 fn etch_requires_rune_index() {
   let core = mockcore::builder().network(Network::Regtest).build();
@@ -1944,6 +1997,7 @@ fn etch_requires_rune_index() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -1986,6 +2040,7 @@ fn etch_divisibility_over_maximum_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 39,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2028,6 +2083,7 @@ fn etch_mintable_overflow_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2078,6 +2134,7 @@ fn etch_mintable_plus_premine_overflow_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2128,6 +2185,7 @@ fn incorrect_supply_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2178,6 +2236,7 @@ fn zero_offset_interval_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2228,6 +2287,7 @@ fn zero_height_interval_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2278,6 +2338,7 @@ fn invalid_start_height_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2330,6 +2391,7 @@ fn invalid_end_height_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2382,6 +2444,7 @@ fn zero_supply_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2424,6 +2487,7 @@ fn zero_cap_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2471,6 +2535,7 @@ fn zero_amount_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2518,6 +2583,7 @@ fn oversize_runestone_error() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(6402364363415443603228541259936211926 - 1),
@@ -2573,6 +2639,7 @@ fn oversize_runestones_are_allowed_with_no_limit() {
     "batch.yaml",
     serde_yaml::to_string(&batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune: SpacedRune {
           rune: Rune(6402364363415443603228541259936211926 - 1),
@@ -2626,6 +2693,7 @@ fn batch_inscribe_errors_if_pending_etchings() {
 
   let batchfile = batch::File {
     etching: Some(batch::Etching {
+      allocations: Vec::new(),
       divisibility: 0,
       rune: SpacedRune {
         rune: Rune(RUNE),
@@ -2716,6 +2784,7 @@ fn forbid_etching_below_rune_activation_height() {
       "batch.yaml",
       serde_yaml::to_string(&batch::File {
         etching: Some(batch::Etching {
+          allocations: Vec::new(),
           divisibility: 0,
           rune: SpacedRune {
             rune: Rune(RUNE),
@@ -2741,3 +2810,229 @@ fn forbid_etching_below_rune_activation_height() {
     .expected_exit_code(1)
     .run_and_extract_stdout();
 }
+
+#[test]
+fn etching_with_premine_allocations() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let rune = SpacedRune {
+    rune: Rune(RUNE),
+    spacers: 0,
+  };
+
+  let treasury = "bcrt1pyrmadgg78e38ewfv0an8c6eppk2fttv5vnuvz04yza60qau5va0saknu8k"
+    .parse::<Address<NetworkUnchecked>>()
+    .unwrap();
+
+  let team = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw"
+    .parse::<Address<NetworkUnchecked>>()
+    .unwrap();
+
+  let etched = batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        allocations: vec![
+          batch::Allocation {
+            address: treasury,
+            amount: "300.5".parse().unwrap(),
+            postage: None,
+          },
+          batch::Allocation {
+            address: team,
+            amount: "200".parse().unwrap(),
+            postage: Some(546),
+          },
+        ],
+        divisibility: 1,
+        rune,
+        supply: "1000".parse().unwrap(),
+        premine: "1000".parse().unwrap(),
+        symbol: '¢',
+        terms: None,
+        turbo: false,
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let rune_info = etched.output.rune.unwrap();
+
+  assert_eq!(
+    core.tx_by_id(etched.output.reveal).output
+      [usize::try_from(rune_info.allocations[1].location.vout).unwrap()]
+    .value,
+    546
+  );
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  let pile = |amount| Pile {
+    amount,
+    divisibility: 1,
+    symbol: Some('¢'),
+  };
+
+  pretty_assert_eq!(
+    balances.runes[&rune],
+    [
+      (rune_info.allocations[0].location, pile(3005)),
+      (rune_info.allocations[1].location, pile(2000)),
+      (rune_info.location.unwrap(), pile(4995)),
+    ]
+    .into_iter()
+    .collect()
+  );
+}
+
+#[test]
+fn etching_with_fully_allocated_premine() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let rune = SpacedRune {
+    rune: Rune(RUNE),
+    spacers: 0,
+  };
+
+  let etched = batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        allocations: vec![batch::Allocation {
+          address: "bcrt1pyrmadgg78e38ewfv0an8c6eppk2fttv5vnuvz04yza60qau5va0saknu8k"
+            .parse()
+            .unwrap(),
+          amount: "1000".parse().unwrap(),
+          postage: None,
+        }],
+        divisibility: 0,
+        rune,
+        supply: "1000".parse().unwrap(),
+        premine: "1000".parse().unwrap(),
+        symbol: '¢',
+        terms: None,
+        turbo: false,
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let rune_info = etched.output.rune.unwrap();
+
+  assert_eq!(rune_info.destination, None);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  pretty_assert_eq!(
+    balances.runes[&rune],
+    [(
+      rune_info.allocations[0].location,
+      Pile {
+        amount: 1000,
+        divisibility: 0,
+        symbol: Some('¢'),
+      }
+    )]
+    .into_iter()
+    .collect()
+  );
+}
+
+#[test]
+fn invalid_premine_allocations_are_errors() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let address = "bcrt1pyrmadgg78e38ewfv0an8c6eppk2fttv5vnuvz04yza60qau5va0saknu8k";
+
+  for (amount, postage, error) in [
+    ("600", None, "premine allocations exceed `premine`"),
+    (
+      "0",
+      None,
+      &*format!("premine allocation to {address} must be greater than zero"),
+    ),
+    ("1.5", None, "excessive precision"),
+    (
+      "1",
+      Some(329),
+      &*format!("premine allocation to {address} has postage below dust limit of 330sat"),
+    ),
+  ] {
+    CommandBuilder::new("--regtest --index-runes wallet batch --fee-rate 0 --batch batch.yaml")
+      .write("inscription.txt", "foo")
+      .write(
+        "batch.yaml",
+        serde_yaml::to_string(&batch::File {
+          etching: Some(batch::Etching {
+            allocations: vec![
+              batch::Allocation {
+                address: address.parse().unwrap(),
+                amount: "500".parse().unwrap(),
+                postage: None,
+              },
+              batch::Allocation {
+                address: address.parse().unwrap(),
+                amount: amount.parse().unwrap(),
+                postage,
+              },
+            ],
+            divisibility: 0,
+            rune: SpacedRune {
+              rune: Rune(RUNE),
+              spacers: 0,
+            },
+            supply: "1000".parse().unwrap(),
+            premine: "1000".parse().unwrap(),
+            symbol: '¢',
+            terms: None,
+            turbo: false,
+          }),
+          inscriptions: vec![batch::Entry {
+            file: Some("inscription.txt".into()),
+            ..default()
+          }],
+          ..default()
+        })
+        .unwrap(),
+      )
+      .core(&core)
+      .ord(&ord)
+      .expected_stderr(format!("error: {error}\n"))
+      .expected_exit_code(1)
+      .run_and_extract_stdout();
+  }
+}
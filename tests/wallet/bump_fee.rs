@@ -0,0 +1,254 @@
+use {super::*, bitcoin::Transaction, ord::subcommand::wallet::bump_fee::Output};
+
+fn transaction(core: &mockcore::Handle, txid: Txid) -> Transaction {
+  core
+    .mempool()
+    .into_iter()
+    .find(|tx| tx.txid() == txid)
+    .unwrap_or_else(|| core.tx_by_id(txid))
+}
+
+fn fee(core: &mockcore::Handle, txid: Txid) -> u64 {
+  let tx = transaction(core, txid);
+
+  tx.input
+    .iter()
+    .map(|txin| {
+      transaction(core, txin.previous_output.txid).output[txin.previous_output.vout as usize].value
+    })
+    .sum::<u64>()
+    - tx.output.iter().map(|txout| txout.value).sum::<u64>()
+}
+
+fn inscribe_unconfirmed(core: &mockcore::Handle, ord: &TestServer) -> Batch {
+  core.mine_blocks(2);
+
+  CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(core)
+    .ord(ord)
+    .run_and_deserialize_output::<Batch>()
+}
+
+#[test]
+fn replacing_commit_re_signs_reveal() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let inscribe = inscribe_unconfirmed(&core, &ord);
+
+  let commit_fee = fee(&core, inscribe.commit);
+
+  let original_reveal = transaction(&core, inscribe.reveal);
+
+  let output = CommandBuilder::new(format!("wallet bump-fee --fee-rate 10 {}", inscribe.commit))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.original, inscribe.commit);
+  assert_eq!(output.descendants.len(), 1);
+  assert!(output.fee > commit_fee);
+  assert_eq!(fee(&core, output.txid), output.fee);
+
+  let reveal = output.descendants[0];
+
+  assert_eq!(
+    core
+      .mempool()
+      .iter()
+      .map(|tx| tx.txid())
+      .collect::<Vec<Txid>>(),
+    [output.txid, reveal],
+  );
+
+  let new_reveal = transaction(&core, reveal);
+
+  assert_eq!(new_reveal.input[0].previous_output.txid, output.txid);
+  assert_eq!(new_reveal.output, original_reveal.output);
+  assert_eq!(
+    new_reveal.input[0].witness.nth(1),
+    original_reveal.input[0].witness.nth(1),
+  );
+  assert_ne!(
+    new_reveal.input[0].witness.nth(0),
+    original_reveal.input[0].witness.nth(0),
+  );
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!(
+      "/inscription/{}",
+      InscriptionId {
+        txid: reveal,
+        index: 0
+      }
+    ),
+    ".*<h1>Inscription 0</h1>.*",
+  );
+}
+
+#[test]
+fn replacing_reveal_keeps_inscription() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let inscribe = inscribe_unconfirmed(&core, &ord);
+
+  let reveal_fee = fee(&core, inscribe.reveal);
+
+  let output = CommandBuilder::new(format!("wallet bump-fee --fee-rate 5 {}", inscribe.reveal))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert!(output.descendants.is_empty());
+  assert!(output.fee > reveal_fee);
+
+  let replacement = transaction(&core, output.txid);
+
+  assert_eq!(replacement.input[0].previous_output.txid, inscribe.commit);
+  assert_eq!(replacement.input.len(), 2);
+  assert_eq!(replacement.output.len(), 2);
+
+  assert_eq!(
+    core
+      .mempool()
+      .iter()
+      .map(|tx| tx.txid())
+      .collect::<Vec<Txid>>(),
+    [inscribe.commit, output.txid],
+  );
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!(
+      "/inscription/{}",
+      InscriptionId {
+        txid: output.txid,
+        index: 0
+      }
+    ),
+    ".*<h1>Inscription 0</h1>.*",
+  );
+}
+
+#[test]
+fn child_pays_for_parent() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let inscribe = inscribe_unconfirmed(&core, &ord);
+
+  let commit = transaction(&core, inscribe.commit);
+
+  let output = CommandBuilder::new(format!(
+    "wallet bump-fee --cpfp --fee-rate 10 {}",
+    inscribe.commit
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  let child = transaction(&core, output.txid);
+
+  assert_eq!(
+    child.input[0].previous_output,
+    OutPoint {
+      txid: inscribe.commit,
+      vout: 1,
+    },
+  );
+  assert_eq!(child.output[0], commit.output[1]);
+  assert_eq!(fee(&core, output.txid), output.fee);
+  assert_eq!(core.mempool().len(), 3);
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!("/inscription/{}", inscribe.inscriptions[0].id),
+    ".*<h1>Inscription 0</h1>.*",
+  );
+}
+
+#[test]
+fn child_pays_for_unconfirmed_ancestors() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let inscribe = inscribe_unconfirmed(&core, &ord);
+
+  let output = CommandBuilder::new(format!(
+    "wallet bump-fee --cpfp --fee-rate 10 {}",
+    inscribe.reveal
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  let package = [inscribe.commit, inscribe.reveal, output.txid];
+
+  let vsize = package
+    .iter()
+    .map(|txid| transaction(&core, *txid).vsize())
+    .sum::<usize>();
+
+  let fees = package.iter().map(|txid| fee(&core, *txid)).sum::<u64>();
+
+  assert!(fees >= 10 * u64::try_from(vsize).unwrap());
+}
+
+#[test]
+fn fee_rate_must_increase() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let inscribe = inscribe_unconfirmed(&core, &ord);
+
+  CommandBuilder::new(format!("wallet bump-fee --fee-rate 1 {}", inscribe.commit))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .stderr_regex("error: fee rate of 1 sats/vB does not exceed transaction .*'s current fee rate of .* sats/vB\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn confirmed_transactions_cannot_be_bumped() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let inscribe = inscribe_unconfirmed(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new(format!("wallet bump-fee --fee-rate 10 {}", inscribe.commit))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: transaction {} is already confirmed\n",
+      inscribe.commit
+    ))
+    .run_and_extract_stdout();
+}
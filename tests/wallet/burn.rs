@@ -0,0 +1,199 @@
+use {super::*, ord::subcommand::wallet::burn::Output};
+
+#[test]
+fn inscriptions_can_be_burned() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _reveal) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new(format!("wallet burn --fee-rate 1 {inscription}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.inscription, Some(inscription));
+  assert_eq!(output.rune, None);
+
+  let transaction = core.mempool()[0].clone();
+
+  assert_eq!(transaction.txid(), output.txid);
+  assert!(transaction.output[0].script_pubkey.is_op_return());
+  assert_eq!(transaction.output[0].value, 1);
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/inscription/{inscription}"));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let inscription_json: api::Inscription = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert_eq!(
+    inscription_json.satpoint,
+    SatPoint {
+      outpoint: OutPoint::new(output.txid, 0),
+      offset: 0,
+    }
+  );
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert!(inscriptions.is_empty());
+}
+
+#[test]
+fn sats_before_inscription_are_not_burned() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  assert_eq!(core.tx(2, 2).txid(), reveal);
+
+  core.mine_blocks(1);
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[(3, 0, 0, Default::default()), (2, 2, 0, Default::default())],
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let address = core.address(OutPoint { txid, vout: 0 });
+
+  core.state().receive_addresses.push(address);
+
+  let output = CommandBuilder::new(format!("wallet burn --fee-rate 1 {inscription}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  let transaction = core.mempool()[0].clone();
+
+  assert_eq!(transaction.txid(), output.txid);
+  assert_eq!(transaction.output[0].value, 50 * COIN_VALUE);
+  assert!(!transaction.output[0].script_pubkey.is_op_return());
+  assert!(transaction.output[1].script_pubkey.is_op_return());
+  assert_eq!(transaction.output[1].value, 1);
+}
+
+#[test]
+fn runes_can_be_burned() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet burn --fee-rate 1 --rune 111:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  let pile = |amount| Pile {
+    amount,
+    divisibility: 0,
+    symbol: Some('¢'),
+  };
+
+  assert_eq!(output.burned, Some(pile(111)));
+  assert_eq!(output.total_burned, Some(pile(111)));
+  assert_eq!(
+    output.rune,
+    Some(SpacedRune {
+      rune: Rune(RUNE),
+      spacers: 0
+    })
+  );
+
+  core.mine_blocks(1);
+
+  let response = ord.json_request(format!("/rune/{}", Rune(RUNE)));
+
+  assert_eq!(response.status(), StatusCode::OK);
+
+  let rune_json: api::Rune = serde_json::from_str(&response.text().unwrap()).unwrap();
+
+  assert_eq!(rune_json.entry.burned, 111);
+
+  let balance = CommandBuilder::new("--regtest --index-runes wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+
+  assert_eq!(
+    balance.runes.unwrap()[&SpacedRune {
+      rune: Rune(RUNE),
+      spacers: 0
+    }]
+      .value,
+    889
+  );
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet burn --fee-rate 1 --rune 9:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.total_burned, Some(pile(120)));
+}
+
+#[test]
+fn burning_zero_runes_is_an_error() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet burn --fee-rate 1 --rune 0:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr("error: burn amount must be greater than zero\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn burning_more_runes_than_in_wallet_is_an_error() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet burn --fee-rate 1 --rune 1001:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr("error: insufficient `AAAAAAAAAAAAA` balance, only 1000\u{A0}¢ in wallet\n")
+  .run_and_extract_stdout();
+}
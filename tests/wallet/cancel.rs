@@ -0,0 +1,206 @@
+use {
+  super::*,
+  nix::{
+    sys::signal::{self, Signal},
+    unistd::Pid,
+  },
+  ord::subcommand::wallet::cancel::Output,
+};
+
+#[test]
+fn cancel_unconfirmed_inscription_replaces_reveal() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let inscribe = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  let output = CommandBuilder::new(format!("wallet cancel --fee-rate 5 {}", inscribe.commit))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.commit, inscribe.commit);
+  assert_eq!(output.evicted, [inscribe.reveal]);
+  assert_eq!(output.etching, None);
+  assert_eq!(
+    output.inputs,
+    [OutPoint {
+      txid: inscribe.commit,
+      vout: 0
+    }]
+  );
+
+  assert_eq!(
+    core
+      .mempool()
+      .iter()
+      .map(|tx| tx.txid())
+      .collect::<Vec<Txid>>(),
+    [inscribe.commit, output.txid],
+  );
+
+  let sweep = core.mempool()[1].clone();
+
+  assert_eq!(sweep.input[0].witness.len(), 1);
+  assert_eq!(sweep.output[0].value, output.value);
+
+  core.mine_blocks(1);
+
+  assert_eq!(
+    ord
+      .request(format!("/inscription/{}", inscribe.inscriptions[0].id))
+      .status(),
+    StatusCode::NOT_FOUND,
+  );
+}
+
+#[test]
+fn cancel_requires_backed_up_recovery_key() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let inscribe = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt --no-backup")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  CommandBuilder::new(format!("wallet cancel --fee-rate 5 {}", inscribe.commit))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: transaction {} has no outputs spendable with a recovery key in wallet\n",
+      inscribe.commit
+    ))
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn cancel_fails_once_revealed() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (_inscription, reveal) = inscribe(&core, &ord);
+
+  let commit = core.tx_by_id(reveal).input[0].previous_output;
+
+  CommandBuilder::new(format!("wallet cancel --fee-rate 5 {}", commit.txid))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: commit output {commit} has already been spent\n"
+    ))
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn cancel_pending_etching() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-runes"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let batchfile = batch::File {
+    etching: Some(batch::Etching {
+      allocations: Vec::new(),
+      divisibility: 0,
+      rune: SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0,
+      },
+      supply: "1000".parse().unwrap(),
+      premine: "1000".parse().unwrap(),
+      symbol: '¢',
+      ..default()
+    }),
+    inscriptions: vec![batch::Entry {
+      file: Some("inscription.jpeg".into()),
+      ..default()
+    }],
+    ..default()
+  };
+
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let commit = {
+    let mut spawn =
+      CommandBuilder::new("--regtest --index-runes wallet batch --fee-rate 0 --batch batch.yaml")
+        .temp_dir(tempdir.clone())
+        .write("batch.yaml", serde_yaml::to_string(&batchfile).unwrap())
+        .write("inscription.jpeg", "inscription")
+        .core(&core)
+        .ord(&ord)
+        .expected_exit_code(1)
+        .spawn();
+
+    let mut buffer = String::new();
+
+    BufReader::new(spawn.child.stderr.as_mut().unwrap())
+      .read_line(&mut buffer)
+      .unwrap();
+
+    let commit = buffer
+      .strip_prefix("Waiting for rune AAAAAAAAAAAAA commitment ")
+      .unwrap()
+      .split(' ')
+      .next()
+      .unwrap()
+      .parse::<Txid>()
+      .unwrap();
+
+    core.mine_blocks(1);
+
+    signal::kill(
+      Pid::from_raw(spawn.child.id().try_into().unwrap()),
+      Signal::SIGINT,
+    )
+    .unwrap();
+
+    spawn.child.wait().unwrap();
+
+    commit
+  };
+
+  let output = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet cancel --fee-rate 1 {commit}"
+  ))
+  .temp_dir(tempdir.clone())
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(output.etching.unwrap().rune, Rune(RUNE));
+  assert!(output.evicted.is_empty());
+
+  core.mine_blocks(6);
+
+  let resume = CommandBuilder::new("--regtest --index-runes wallet resume")
+    .temp_dir(tempdir)
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::resume::ResumeOutput>();
+
+  assert!(resume.etchings.is_empty());
+}
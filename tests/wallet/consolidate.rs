@@ -0,0 +1,75 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{consolidate::Output, split},
+};
+
+#[test]
+fn rare_sat_outputs_are_not_consolidated() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  CommandBuilder::new("--index-sats wallet consolidate --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: wallet contains fewer than two cardinal outputs to consolidate\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn consolidate_cardinal_outputs() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(2);
+
+  let split = CommandBuilder::new(format!(
+    "--index-sats wallet split --fee-rate 1 {} {}",
+    50 * COIN_VALUE,
+    100 * COIN_VALUE
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<split::Output>();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("--index-sats wallet consolidate --fee-rate 1 --dry-run")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert!(core.mempool().is_empty());
+
+  let cardinal = [split.outputs[1].output, split.outputs[3].output];
+
+  assert_eq!(output.inputs, {
+    let mut inputs = cardinal.to_vec();
+    inputs.sort();
+    inputs
+  });
+
+  let output = CommandBuilder::new("--index-sats wallet consolidate --fee-rate 1")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output.value,
+    split.outputs[1].value + split.outputs[3].value - output.fee
+  );
+
+  let transaction = &core.mempool()[0];
+
+  assert_eq!(transaction.txid(), output.txid);
+  assert_eq!(transaction.input.len(), 2);
+  assert_eq!(transaction.output.len(), 1);
+}
@@ -0,0 +1,227 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::psbt::Psbt,
+  ord::subcommand::wallet::{finalize_psbt, mint},
+};
+
+fn decode_psbt(psbt: &str) -> Psbt {
+  Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt)
+      .unwrap(),
+  )
+  .unwrap()
+}
+
+#[test]
+fn inscribe_psbt_can_be_finalized() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let batch = CommandBuilder::new("wallet inscribe --psbt --file foo.txt --fee-rate 1")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  assert!(core.mempool().is_empty());
+  assert!(!batch.reveal_broadcast);
+
+  let commit_psbt = batch.commit_psbt.unwrap();
+  let reveal_psbt = batch.reveal_psbt.unwrap();
+
+  assert!(decode_psbt(&commit_psbt)
+    .outputs
+    .iter()
+    .any(|output| output.tap_tree.is_some() && output.tap_internal_key.is_some()));
+
+  let reveal = decode_psbt(&reveal_psbt);
+
+  assert_eq!(reveal.inputs[0].tap_scripts.len(), 1);
+  assert_eq!(reveal.inputs[0].tap_script_sigs.len(), 1);
+  assert!(reveal.inputs[0].witness_utxo.is_some());
+
+  let output = CommandBuilder::new(format!("wallet finalize-psbt {commit_psbt} {reveal_psbt}"))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<finalize_psbt::Output>();
+
+  assert_eq!(output.txids, [batch.commit, batch.reveal]);
+
+  core.mine_blocks(1);
+
+  ord.assert_response_regex(
+    format!("/inscription/{}", batch.inscriptions[0].id),
+    ".*<title>Inscription 0</title>.*",
+  );
+}
+
+#[test]
+fn send_psbt_can_be_finalized() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let send = CommandBuilder::new(
+    "wallet send --psbt --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  assert!(core.mempool().is_empty());
+  assert_eq!(decode_psbt(&send.psbt).unsigned_tx.txid(), send.txid);
+
+  let output = CommandBuilder::new(format!("wallet finalize-psbt {}", send.psbt))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<finalize_psbt::Output>();
+
+  assert_eq!(output.txids, [send.txid]);
+  assert_eq!(core.mempool().len(), 1);
+}
+
+#[test]
+fn mint_psbt_is_not_broadcast() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        allocations: Vec::new(),
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        symbol: '¢',
+        supply: "10".parse().unwrap(),
+        terms: Some(batch::Terms {
+          cap: 10,
+          offset: None,
+          amount: "1".parse().unwrap(),
+          height: None,
+        }),
+        turbo: false,
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --psbt --fee-rate 1 --rune {}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert!(core.mempool().is_empty());
+  assert_eq!(
+    decode_psbt(&output.psbt.unwrap()).unsigned_tx.txid(),
+    output.mint
+  );
+}
+
+#[test]
+fn chained_mint_psbts_include_witness_utxos() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  batch(
+    &core,
+    &ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        allocations: Vec::new(),
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        symbol: '¢',
+        supply: "10".parse().unwrap(),
+        terms: Some(batch::Terms {
+          cap: 10,
+          offset: None,
+          amount: "1".parse().unwrap(),
+          height: None,
+        }),
+        turbo: false,
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --psbt --fee-rate 1 --rune {} --count 3",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert!(core.mempool().is_empty());
+
+  let transactions = output
+    .mints
+    .iter()
+    .map(|mint| decode_psbt(mint.psbt.as_ref().unwrap()))
+    .collect::<Vec<Psbt>>();
+
+  for (parent, child) in transactions.iter().zip(&transactions[1..]) {
+    for (input, txin) in child.inputs.iter().zip(&child.unsigned_tx.input) {
+      assert_eq!(txin.previous_output.txid, parent.unsigned_tx.txid());
+      assert_eq!(
+        input.witness_utxo.as_ref().unwrap(),
+        &parent.unsigned_tx.output[usize::try_from(txin.previous_output.vout).unwrap()],
+      );
+    }
+  }
+}
+
+#[test]
+fn finalize_psbt_rejects_invalid_psbt() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &[], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet finalize-psbt foo")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .stderr_regex("error: PSBT is not valid base64.*")
+    .run_and_extract_stdout();
+}
@@ -15,6 +15,7 @@ fn minting_rune_and_fails_if_after_end() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 1,
         rune: SpacedRune {
           rune: Rune(RUNE),
@@ -112,6 +113,7 @@ fn minting_rune_fails_if_not_mintable() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 1,
         rune: SpacedRune {
           rune: Rune(RUNE),
@@ -178,6 +180,7 @@ fn minting_rune_and_then_sending_works() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune: SpacedRune {
           rune: Rune(RUNE),
@@ -278,6 +281,7 @@ fn minting_rune_with_destination() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune: SpacedRune {
           rune: Rune(RUNE),
@@ -383,6 +387,7 @@ fn minting_rune_with_postage() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune: SpacedRune {
           rune: Rune(RUNE),
@@ -452,6 +457,7 @@ fn minting_rune_with_postage_dust() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 0,
         rune: SpacedRune {
           rune: Rune(RUNE),
@@ -489,3 +495,195 @@ fn minting_rune_with_postage_dust() {
   .expected_stderr("error: postage below dust limit of 330sat\n")
   .run_and_extract_stdout();
 }
+
+fn etch_mintable_rune(core: &mockcore::Handle, ord: &TestServer, cap: u128) {
+  batch(
+    core,
+    ord,
+    batch::File {
+      etching: Some(batch::Etching {
+        allocations: Vec::new(),
+        divisibility: 0,
+        rune: SpacedRune {
+          rune: Rune(RUNE),
+          spacers: 0,
+        },
+        premine: "0".parse().unwrap(),
+        supply: (cap * 21).to_string().parse().unwrap(),
+        symbol: '¢',
+        turbo: false,
+        terms: Some(batch::Terms {
+          cap,
+          offset: None,
+          amount: "21".parse().unwrap(),
+          height: None,
+        }),
+      }),
+      inscriptions: vec![batch::Entry {
+        file: Some("inscription.jpeg".into()),
+        ..default()
+      }],
+      ..default()
+    },
+  );
+}
+
+#[test]
+fn minting_rune_multiple_times_gathers_piles() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  etch_mintable_rune(&core, &ord, 10);
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --count 3",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  assert_eq!(output.mints.len(), 3);
+  assert_eq!(output.mint, output.mints[2].txid);
+  assert_eq!(output.mints[1].pile.amount, 42);
+
+  for (parent, child) in output.mints.iter().zip(&output.mints[1..]) {
+    let transaction = core
+      .mempool()
+      .into_iter()
+      .find(|tx| tx.txid() == child.txid)
+      .unwrap();
+
+    assert_eq!(
+      transaction
+        .input
+        .iter()
+        .map(|txin| txin.previous_output)
+        .collect::<Vec<OutPoint>>(),
+      vec![
+        parent.output,
+        OutPoint {
+          txid: parent.txid,
+          vout: 2
+        }
+      ],
+    );
+  }
+
+  core.mine_blocks(1);
+
+  pretty_assert_eq!(
+    output.pile,
+    Pile {
+      amount: 63,
+      divisibility: 0,
+      symbol: Some('¢'),
+    }
+  );
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--regtest --index-runes balances")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<ord::subcommand::balances::Output>(),
+    ord::subcommand::balances::Output {
+      runes: vec![(
+        output.rune,
+        vec![(
+          OutPoint {
+            txid: output.mint,
+            vout: 1
+          },
+          output.pile,
+        )]
+        .into_iter()
+        .collect()
+      )]
+      .into_iter()
+      .collect(),
+    }
+  );
+}
+
+#[test]
+fn minting_rune_multiple_times_with_split_keeps_piles_separate() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  etch_mintable_rune(&core, &ord, 10);
+
+  let output = CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --count 3 --split",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<mint::Output>();
+
+  core.mine_blocks(1);
+
+  assert_eq!(output.pile.amount, 63);
+
+  pretty_assert_eq!(
+    CommandBuilder::new("--regtest --index-runes balances")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<ord::subcommand::balances::Output>(),
+    ord::subcommand::balances::Output {
+      runes: vec![(
+        output.rune,
+        output
+          .mints
+          .iter()
+          .map(|mint| (mint.output, mint.pile))
+          .collect()
+      )]
+      .into_iter()
+      .collect(),
+    }
+  );
+
+  let balance = CommandBuilder::new("--chain regtest --index-runes wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::balance::Output>();
+
+  assert_eq!(balance.runic.unwrap(), 30000);
+}
+
+#[test]
+fn minting_rune_more_times_than_remaining_cap_fails() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  core.mine_blocks(1);
+
+  create_wallet(&core, &ord);
+
+  etch_mintable_rune(&core, &ord, 2);
+
+  CommandBuilder::new(format!(
+    "--chain regtest --index-runes wallet mint --fee-rate 1 --rune {} --count 3",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_exit_code(1)
+  .expected_stderr(
+    "error: rune AAAAAAAAAAAAA has 2 mints remaining, fewer than requested count of 3\n",
+  )
+  .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+}
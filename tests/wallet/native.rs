@@ -0,0 +1,347 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{create, receive, send},
+};
+
+fn create_native_wallet(
+  core: &mockcore::Handle,
+  ord: &TestServer,
+  tempdir: &Arc<TempDir>,
+) -> create::Output {
+  CommandBuilder::new("--regtest wallet create --native")
+    .core(core)
+    .ord(ord)
+    .temp_dir(tempdir.clone())
+    .stderr_regex("warning: native wallet private keys are stored UNENCRYPTED in `.*ord.redb`.*")
+    .run_and_deserialize_output()
+}
+
+fn receive(core: &mockcore::Handle, ord: &TestServer, tempdir: &Arc<TempDir>) -> Address {
+  CommandBuilder::new("--regtest wallet receive")
+    .core(core)
+    .ord(ord)
+    .temp_dir(tempdir.clone())
+    .run_and_deserialize_output::<receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap()
+    .require_network(Network::Regtest)
+    .unwrap()
+}
+
+fn balance(core: &mockcore::Handle, ord: &TestServer, tempdir: &Arc<TempDir>) -> Balance {
+  CommandBuilder::new("--regtest wallet balance")
+    .core(core)
+    .ord(ord)
+    .temp_dir(tempdir.clone())
+    .run_and_deserialize_output()
+}
+
+#[test]
+fn create_does_not_use_bitcoind_wallet() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-addresses"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  create_native_wallet(&core, &ord, &tempdir);
+
+  assert!(core.wallets().is_empty());
+  assert!(core.loaded_wallets().is_empty());
+  assert!(core.descriptors().is_empty());
+
+  assert!(tempdir.path().join("regtest/wallets/ord.redb").exists());
+
+  CommandBuilder::new("--regtest wallet create --native")
+    .core(&core)
+    .ord(&ord)
+    .temp_dir(tempdir)
+    .expected_stderr("error: wallet `ord` already exists\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn receive_and_balance() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-addresses"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  create_native_wallet(&core, &ord, &tempdir);
+
+  let address = receive(&core, &ord, &tempdir);
+
+  assert_ne!(address, receive(&core, &ord, &tempdir));
+
+  assert_eq!(balance(&core, &ord, &tempdir).cardinal, 0);
+
+  core.mine_blocks_to(2, &address);
+
+  assert_eq!(
+    balance(&core, &ord, &tempdir),
+    Balance {
+      cardinal: 100 * COIN_VALUE,
+      ordinal: 0,
+      runic: None,
+      runes: None,
+      total: 100 * COIN_VALUE,
+    }
+  );
+}
+
+#[test]
+fn send_amount() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-addresses"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  create_native_wallet(&core, &ord, &tempdir);
+
+  core.mine_blocks_to(1, &receive(&core, &ord, &tempdir));
+
+  let output = CommandBuilder::new(
+    "--regtest wallet send --fee-rate 1 bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .temp_dir(tempdir.clone())
+  .run_and_deserialize_output::<send::Output>();
+
+  let transaction = core.mempool()[0].clone();
+
+  assert_eq!(transaction.txid(), output.txid);
+  assert!(transaction.input.iter().all(|txin| txin.witness.len() == 1));
+  assert_eq!(transaction.output[0].value, COIN_VALUE);
+  assert_eq!(
+    transaction.output[0].script_pubkey,
+    "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked()
+      .script_pubkey()
+  );
+
+  core.mine_blocks(1);
+
+  assert_eq!(
+    balance(&core, &ord, &tempdir).cardinal,
+    49 * COIN_VALUE - output.fee
+  );
+}
+
+#[test]
+fn outputs_spent_by_pending_transactions_are_not_reused() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-addresses"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  create_native_wallet(&core, &ord, &tempdir);
+
+  core.mine_blocks_to(1, &receive(&core, &ord, &tempdir));
+
+  let output = CommandBuilder::new(
+    "--regtest wallet send --fee-rate 1 bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .temp_dir(tempdir.clone())
+  .run_and_deserialize_output::<send::Output>();
+
+  assert_eq!(balance(&core, &ord, &tempdir).cardinal, 0);
+
+  CommandBuilder::new(
+    "--regtest wallet send --fee-rate 1 bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .temp_dir(tempdir.clone())
+  .expected_stderr("error: wallet contains no cardinal utxos\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  core.mine_blocks(1);
+
+  assert_eq!(
+    balance(&core, &ord, &tempdir).cardinal,
+    49 * COIN_VALUE - output.fee
+  );
+}
+
+#[test]
+fn inscribe_without_backup() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-addresses"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  create_native_wallet(&core, &ord, &tempdir);
+
+  core.mine_blocks_to(1, &receive(&core, &ord, &tempdir));
+
+  CommandBuilder::new("--regtest wallet inscribe --fee-rate 1 --file foo.txt")
+    .temp_dir(tempdir.clone())
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(
+      "error: native wallets cannot back up the commit transaction recovery key, use `--no-backup`\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  let output =
+    CommandBuilder::new("--regtest wallet inscribe --fee-rate 1 --file foo.txt --no-backup")
+      .temp_dir(tempdir.clone())
+      .write("foo.txt", "FOO")
+      .core(&core)
+      .ord(&ord)
+      .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  let inscription = output.inscriptions[0].id;
+
+  ord.assert_response_regex(
+    format!("/inscription/{inscription}"),
+    ".*<h1>Inscription 0</h1>.*",
+  );
+
+  let balance = balance(&core, &ord, &tempdir);
+
+  assert_eq!(balance.ordinal, 10_000);
+  assert_eq!(balance.total, 50 * COIN_VALUE - output.total_fees);
+}
+
+#[test]
+fn unsupported_commands_return_errors() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-addresses"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  create_native_wallet(&core, &ord, &tempdir);
+
+  CommandBuilder::new("--regtest wallet transactions")
+    .core(&core)
+    .ord(&ord)
+    .temp_dir(tempdir)
+    .expected_stderr("error: `ord wallet transactions` is not supported by native wallets\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn address_index_is_required() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  create_native_wallet(&core, &ord, &tempdir);
+
+  CommandBuilder::new("--regtest wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .temp_dir(tempdir)
+    .expected_stderr(
+      "error: native wallets require `ord server` to be run with `--index-addresses`\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn restore_derives_same_addresses() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-addresses"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let create::Output { mnemonic, .. } = create_native_wallet(&core, &ord, &tempdir);
+
+  let address = receive(&core, &ord, &tempdir);
+
+  core.mine_blocks_to(1, &address);
+
+  let restored = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new([
+    "--regtest",
+    "wallet",
+    "restore",
+    "--native",
+    "--from",
+    "mnemonic",
+  ])
+  .stdin(mnemonic.to_string().into())
+  .core(&core)
+  .ord(&ord)
+  .temp_dir(restored.clone())
+  .stderr_regex("warning: native wallet private keys are stored UNENCRYPTED.*")
+  .run_and_extract_stdout();
+
+  assert!(core.wallets().is_empty());
+
+  assert_eq!(balance(&core, &ord, &restored).cardinal, 50 * COIN_VALUE);
+
+  // the address with outputs is not handed out again
+  assert_eq!(
+    receive(&core, &ord, &restored),
+    receive(&core, &ord, &tempdir)
+  );
+}
+
+#[test]
+fn restore_finds_outputs_past_gap_limit() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-addresses"], &[]);
+  let tempdir = Arc::new(TempDir::new().unwrap());
+
+  let create::Output { mnemonic, .. } = create_native_wallet(&core, &ord, &tempdir);
+
+  let addresses = CommandBuilder::new("--regtest wallet receive --number 47")
+    .core(&core)
+    .ord(&ord)
+    .temp_dir(tempdir.clone())
+    .run_and_deserialize_output::<receive::Output>()
+    .addresses
+    .into_iter()
+    .map(|address| address.require_network(Network::Regtest).unwrap())
+    .collect::<Vec<Address>>();
+
+  core.mine_blocks_to(1, &addresses[15]);
+  core.mine_blocks_to(1, &addresses[30]);
+  core.mine_blocks_to(1, &addresses[45]);
+
+  let restored = Arc::new(TempDir::new().unwrap());
+
+  CommandBuilder::new([
+    "--regtest",
+    "wallet",
+    "restore",
+    "--native",
+    "--from",
+    "mnemonic",
+  ])
+  .stdin(mnemonic.to_string().into())
+  .core(&core)
+  .ord(&ord)
+  .temp_dir(restored.clone())
+  .stderr_regex("warning: native wallet private keys are stored UNENCRYPTED.*")
+  .run_and_extract_stdout();
+
+  assert_eq!(balance(&core, &ord, &restored).cardinal, 150 * COIN_VALUE);
+
+  assert_eq!(receive(&core, &ord, &restored), addresses[46]);
+}
+
+#[test]
+fn native_wallets_are_refused_on_mainnet() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-addresses"], &[]);
+
+  CommandBuilder::new("wallet create --native")
+    .core(&core)
+    .ord(&ord)
+    .expected_stderr(
+      "error: native wallets store private keys unencrypted and are not supported on mainnet\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
@@ -0,0 +1,284 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{absolute::LockTime, psbt::Psbt, Amount, ScriptBuf, Transaction, TxIn, TxOut},
+  ord::subcommand::wallet::{offer, receive},
+};
+
+fn psbt(outpoint: OutPoint, value: u64, signature: &[u8]) -> String {
+  let mut psbt = Psbt::from_unsigned_tx(Transaction {
+    version: 2,
+    lock_time: LockTime::ZERO,
+    input: vec![TxIn {
+      previous_output: outpoint,
+      script_sig: ScriptBuf::new(),
+      sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+      witness: Witness::new(),
+    }],
+    output: vec![TxOut {
+      script_pubkey: ScriptBuf::new(),
+      value,
+    }],
+  })
+  .unwrap();
+
+  psbt.inputs[0].final_script_witness = Some(Witness::from_slice(&[signature]));
+
+  base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+}
+
+fn single_anyone_can_pay() -> Vec<u8> {
+  let mut signature = vec![0; 64];
+  signature.push(0x83);
+  signature
+}
+
+#[test]
+fn inscription_offer_can_be_accepted() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let create = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer create --inscription {inscription} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<offer::create::Output>();
+
+  assert_eq!(create.amount, COIN_VALUE);
+  assert_eq!(create.inscriptions, [inscription]);
+  assert_eq!(create.outpoint, OutPoint::new(reveal, 0));
+  assert!(create.runes.is_empty());
+
+  let accept = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --inscription {inscription} --amount 1btc --fee-rate 1 {}",
+    create.psbt,
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<offer::accept::Output>();
+
+  assert_eq!(accept.inscriptions, [inscription]);
+  assert_eq!(accept.outpoint, create.outpoint);
+
+  let transaction = core.mempool()[0].clone();
+
+  assert_eq!(transaction.txid(), accept.txid);
+  assert_eq!(transaction.input[1].previous_output, create.outpoint);
+  assert_eq!(
+    transaction.input[1].witness.to_vec(),
+    [single_anyone_can_pay()]
+  );
+  assert_eq!(transaction.output[1].value, COIN_VALUE);
+
+  core.mine_blocks(1);
+
+  let inscriptions = CommandBuilder::new("--regtest --index-runes wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Inscriptions>();
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(inscriptions[0].inscription, inscription);
+  assert_eq!(
+    inscriptions[0].location.outpoint,
+    OutPoint::new(accept.txid, 0)
+  );
+}
+
+#[test]
+fn rune_offer_can_be_accepted() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  etch(&core, &ord, Rune(RUNE));
+
+  let address = CommandBuilder::new("--regtest --index-runes wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<receive::Output>()
+    .addresses
+    .into_iter()
+    .next()
+    .unwrap()
+    .assume_checked();
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet send --fee-rate 1 {address} 400:{}",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  core.mine_blocks(1);
+
+  let create = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer create --rune 400:{} --amount 50000sat",
+    Rune(RUNE)
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<offer::create::Output>();
+
+  assert!(create.inscriptions.is_empty());
+  assert_eq!(
+    create.runes,
+    [(
+      SpacedRune {
+        rune: Rune(RUNE),
+        spacers: 0
+      },
+      Pile {
+        amount: 400,
+        divisibility: 0,
+        symbol: Some('¢'),
+      }
+    )]
+  );
+
+  let accept = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --rune 400:{} --amount 50000sat --fee-rate 1 {}",
+    Rune(RUNE),
+    create.psbt,
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<offer::accept::Output>();
+
+  assert_eq!(accept.runes, create.runes);
+
+  core.mine_blocks(1);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  assert_eq!(
+    balances.runes[&SpacedRune {
+      rune: Rune(RUNE),
+      spacers: 0
+    }][&OutPoint::new(accept.txid, 0)],
+    Pile {
+      amount: 400,
+      divisibility: 0,
+      symbol: Some('¢'),
+    }
+  );
+}
+
+#[test]
+fn accepting_offer_checks_for_runes() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let inscription = etched.output.inscriptions[0].id;
+
+  let height = core.height().try_into().unwrap();
+
+  let txid = core.broadcast_tx(TransactionTemplate {
+    inputs: &[
+      (height, 1, 0, Witness::new()),
+      (height, 1, 1, Witness::new()),
+    ],
+    outputs: 1,
+    ..default()
+  });
+
+  core.mine_blocks(1);
+
+  let outpoint = OutPoint::new(txid, 0);
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --inscription {inscription} --amount 1btc --fee-rate 1 {}",
+    psbt(outpoint, COIN_VALUE, &single_anyone_can_pay()),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!("error: offer output {outpoint} contains runes\n"))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn accepting_offer_checks_inscription() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let create = CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer create --inscription {inscription} --amount 1btc"
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<offer::create::Output>();
+
+  let (other, _reveal) = inscribe(&core, &ord);
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --inscription {other} --amount 1btc --fee-rate 1 {}",
+    create.psbt,
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: offer output {reveal}:0 does not contain inscription {other}\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn accepting_offer_checks_amount_and_signature() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let outpoint = OutPoint::new(reveal, 0);
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --inscription {inscription} --amount 1btc --fee-rate 1 {}",
+    psbt(outpoint, 2 * COIN_VALUE, &single_anyone_can_pay()),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr(format!(
+    "error: offer PSBT asks {} but expected {}\n",
+    Amount::from_sat(2 * COIN_VALUE),
+    Amount::from_sat(COIN_VALUE),
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--regtest --index-runes wallet offer accept --inscription {inscription} --amount 1btc --fee-rate 1 {}",
+    psbt(outpoint, COIN_VALUE, &[0; 64]),
+  ))
+  .core(&core)
+  .ord(&ord)
+  .expected_stderr("error: offer PSBT input is not signed with SIGHASH_SINGLE|ANYONECANPAY\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
@@ -1,4 +1,11 @@
-use {super::*, ord::subcommand::wallet::create};
+use {
+  super::*,
+  bitcoin::{
+    bip32::{ExtendedPrivKey, ExtendedPubKey},
+    secp256k1::Secp256k1,
+  },
+  ord::subcommand::wallet::{create, finalize_psbt, sign_psbt},
+};
 
 #[test]
 fn restore_generates_same_descriptors() {
@@ -86,6 +93,57 @@ fn restore_to_existing_wallet_fails() {
   );
 }
 
+#[test]
+fn restore_watch_only_from_dump() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet dump")
+    .core(&core)
+    .ord(&ord)
+    .stderr_regex(".*")
+    .run_and_deserialize_output::<ListDescriptorsResult>();
+
+  assert!(output
+    .descriptors
+    .iter()
+    .any(|descriptor| descriptor.desc.starts_with("rawtr(")));
+
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  CommandBuilder::new("wallet restore --watch-only --from descriptor")
+    .stdin(serde_json::to_string(&output).unwrap().as_bytes().to_vec())
+    .core(&core)
+    .ord(&ord)
+    .run_and_extract_stdout();
+
+  let descriptors = core.descriptors();
+
+  assert_eq!(descriptors.len(), output.descriptors.len());
+
+  for descriptor in &descriptors {
+    assert!(!descriptor.contains("prv"), "{descriptor}");
+  }
+
+  CommandBuilder::new("wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+}
+
 #[test]
 fn restore_with_wrong_descriptors_fails() {
   let core = mockcore::spawn();
@@ -194,3 +252,270 @@ fn passphrase_conflicts_with_descriptor() {
   .expected_stderr("error: descriptor does not take a passphrase\n")
   .run_and_extract_stdout();
 }
+
+#[test]
+fn restore_watch_only_from_xpub() {
+  let descriptors = {
+    let core = mockcore::spawn();
+
+    CommandBuilder::new(["wallet", "restore", "--watch-only", "--from", "mnemonic"])
+      .stdin(
+        "oil oil oil oil oil oil oil oil oil oil oil oil"
+          .as_bytes()
+          .to_vec(),
+      )
+      .core(&core)
+      .run_and_extract_stdout();
+
+    core.descriptors()
+  };
+
+  assert_eq!(descriptors.len(), 2);
+
+  for descriptor in &descriptors {
+    assert!(descriptor.contains("xpub"));
+    assert!(!descriptor.contains("xprv"));
+  }
+
+  let xpub = descriptors[0]
+    .strip_prefix("tr(")
+    .unwrap()
+    .split_once("/0/*")
+    .unwrap()
+    .0
+    .to_string();
+
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  CommandBuilder::new("wallet restore --from xpub")
+    .stdin(xpub.clone().into())
+    .core(&core)
+    .run_and_extract_stdout();
+
+  assert_eq!(core.descriptors(), descriptors);
+
+  core.mine_blocks(1);
+
+  CommandBuilder::new("wallet balance")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Balance>();
+
+  CommandBuilder::new("wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 1btc")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: watch-only wallets cannot sign transactions, use `--psbt` to create unsigned PSBTs\n",
+    )
+    .run_and_extract_stdout();
+
+  CommandBuilder::new(
+    "wallet send --psbt --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  CommandBuilder::new("wallet inscribe --psbt --fee-rate 1 --file foo.txt")
+    .write("foo.txt", "FOO")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: watch-only wallets cannot back up the commit transaction recovery key, use `--no-backup`\n",
+    )
+    .run_and_extract_stdout();
+
+  assert!(core.mempool().is_empty());
+
+  CommandBuilder::new("wallet restore --from xpub")
+    .stdin(xpub.split_once(']').unwrap().1.into())
+    .core(&mockcore::spawn())
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: extended public key must be prefixed with its key origin, e.g. `[fingerprint/86'/0'/0']xpub…`\n",
+    )
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn restore_from_xpub_rejects_single_key() {
+  let core = mockcore::spawn();
+
+  CommandBuilder::new("wallet restore --from xpub")
+    .stdin(
+      "02e6642fd69bd211f93f7f1f36ca51a26a5290eb2dd1b0d8279a87bb0d480c8443"
+        .as_bytes()
+        .to_vec(),
+    )
+    .core(&core)
+    .expected_exit_code(1)
+    .stderr_regex("error: invalid extended public key.*")
+    .run_and_extract_stdout();
+}
+
+fn cosigner_keys() -> Vec<(ExtendedPrivKey, ExtendedPubKey)> {
+  let secp = Secp256k1::new();
+
+  (0..3)
+    .map(|i| {
+      let xprv = ExtendedPrivKey::new_master(Network::Bitcoin, &[i; 32]).unwrap();
+      (xprv, ExtendedPubKey::from_priv(&secp, &xprv))
+    })
+    .collect()
+}
+
+#[test]
+fn restore_multisig_from_output_descriptor() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  let keys = cosigner_keys();
+
+  CommandBuilder::new("wallet restore --from output-descriptor")
+    .stdin(
+      format!(
+        "wsh(multi(2,{}/<0;1>/*,{}/<0;1>/*,{}/<0;1>/*))",
+        keys[0].0, keys[1].1, keys[2].1
+      )
+      .into(),
+    )
+    .core(&core)
+    .run_and_extract_stdout();
+
+  let descriptors = core.descriptors();
+
+  assert_eq!(descriptors.len(), 2);
+
+  for (descriptor, path) in descriptors.iter().zip(["/0/*", "/1/*"]) {
+    assert!(descriptor.starts_with("wsh(multi(2,"));
+    assert!(descriptor.contains(&format!("{}{path}", keys[0].0)));
+    assert!(descriptor.contains(&format!("{}{path}", keys[1].1)));
+    assert!(!descriptor.contains(&keys[1].0.to_string()));
+  }
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>();
+
+  assert!(output.addresses[0]
+    .clone()
+    .assume_checked()
+    .to_string()
+    .starts_with("bc1q"));
+
+  CommandBuilder::new("wallet send --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 1btc")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(
+      "error: multisig and miniscript wallets must be signed by their cosigners, use `--psbt` to create unsigned PSBTs\n",
+    )
+    .run_and_extract_stdout();
+
+  let send = CommandBuilder::new(
+    "wallet send --psbt --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  let signed = CommandBuilder::new(format!("wallet sign-psbt {}", send.psbt))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<sign_psbt::Output>();
+
+  assert!(signed.complete);
+
+  let output = CommandBuilder::new(format!("wallet finalize-psbt {}", signed.psbt))
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<finalize_psbt::Output>();
+
+  assert_eq!(output.txids, [send.txid]);
+  assert_eq!(core.mempool().len(), 1);
+}
+
+#[test]
+fn restore_taproot_script_tree_without_private_keys_is_watch_only() {
+  let core = mockcore::spawn();
+  let ord = TestServer::spawn(&core);
+
+  let keys = cosigner_keys();
+
+  CommandBuilder::new("wallet restore --from output-descriptor")
+    .stdin(
+      format!(
+        "tr({}/<0;1>/*,multi_a(2,{}/<0;1>/*,{}/<0;1>/*))",
+        keys[0].1, keys[1].1, keys[2].1
+      )
+      .into(),
+    )
+    .core(&core)
+    .run_and_extract_stdout();
+
+  for descriptor in core.descriptors() {
+    assert!(descriptor.starts_with("tr("));
+    assert!(descriptor.contains("multi_a(2,"));
+    assert!(!descriptor.contains("xprv"));
+  }
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet receive")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::receive::Output>();
+
+  assert!(output.addresses[0]
+    .clone()
+    .assume_checked()
+    .to_string()
+    .starts_with("bc1p"));
+
+  let send = CommandBuilder::new(
+    "wallet send --psbt --fee-rate 1 bc1qcqgs2pps4u4yedfyl5pysdjjncs8et5utseepv 1btc",
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Send>();
+
+  CommandBuilder::new(format!("wallet sign-psbt {}", send.psbt))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: watch-only wallets cannot sign PSBTs\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn restore_from_output_descriptor_requires_supported_descriptor() {
+  let keys = cosigner_keys();
+
+  for (descriptor, error) in [
+    (
+      format!("wpkh({}/<0;1>/*)", keys[0].1),
+      "output descriptor must be a `tr()` or `wsh()` descriptor",
+    ),
+    (
+      format!("wsh(multi(2,{}/0/*,{}/0/*))", keys[0].1, keys[1].1),
+      "output descriptor must contain receive and change paths, e.g. `/<0;1>/*`",
+    ),
+  ] {
+    let core = mockcore::spawn();
+
+    CommandBuilder::new("wallet restore --from output-descriptor")
+      .stdin(descriptor.into())
+      .core(&core)
+      .expected_exit_code(1)
+      .expected_stderr(format!("error: {error}\n"))
+      .run_and_extract_stdout();
+
+    assert!(core.descriptors().is_empty());
+  }
+}
@@ -18,6 +18,7 @@ fn wallet_resume() {
 
   let batchfile = batch::File {
     etching: Some(batch::Etching {
+      allocations: Vec::new(),
       divisibility: 0,
       rune: SpacedRune {
         rune: Rune(RUNE),
@@ -117,6 +118,7 @@ fn resume_suspended() {
 
   let batchfile = batch::File {
     etching: Some(batch::Etching {
+      allocations: Vec::new(),
       divisibility: 0,
       rune: SpacedRune {
         rune: Rune(RUNE),
@@ -218,6 +220,7 @@ fn commitment_output_is_locked() {
 
   let batchfile = batch::File {
     etching: Some(batch::Etching {
+      allocations: Vec::new(),
       divisibility: 0,
       rune: SpacedRune {
         rune: Rune(RUNE),
@@ -1,6 +1,6 @@
 use {
   super::*,
-  ord::subcommand::wallet::sats::{OutputRare, OutputTsv},
+  ord::subcommand::wallet::sats::{OutputRare, OutputSatribute, OutputTsv},
 };
 
 #[test]
@@ -38,6 +38,70 @@ fn sats() {
   assert_eq!(output[0].output.to_string(), format!("{second_coinbase}:0"));
 }
 
+#[test]
+fn sats_with_rarity() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let second_coinbase = core.mine_blocks(1)[0].txdata[0].txid();
+
+  let output = CommandBuilder::new("--index-sats wallet sats --rarity vintage")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<OutputSatribute>>();
+
+  assert_eq!(output.len(), 1);
+  assert_eq!(output[0].sat, 50 * COIN_VALUE);
+  assert_eq!(output[0].size, 50 * COIN_VALUE);
+  assert_eq!(output[0].output.to_string(), format!("{second_coinbase}:0"));
+  assert_eq!(output[0].offset, 0);
+
+  let output = CommandBuilder::new("--index-sats wallet sats --rarity omega")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<OutputSatribute>>();
+
+  assert_eq!(output.len(), 50);
+  assert_eq!(output[0].sat, 51 * COIN_VALUE - 1);
+  assert_eq!(output[0].size, 1);
+  assert_eq!(output[0].offset, COIN_VALUE - 1);
+}
+
+#[test]
+fn mainnet_satributes_are_rejected_on_other_chains() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--regtest", "--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("--regtest --index-sats wallet sats --rarity vintage")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: satribute `vintage` only exists on mainnet\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn sats_with_invalid_rarity() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("--index-sats wallet sats --rarity foo")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(2)
+    .stderr_regex(".*invalid satribute `foo`.*")
+    .run_and_extract_stdout();
+}
+
 #[test]
 fn sats_from_tsv_success() {
   let core = mockcore::spawn();
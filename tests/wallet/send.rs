@@ -860,6 +860,7 @@ fn sending_rune_with_divisibility_works() {
     &ord,
     batch::File {
       etching: Some(batch::Etching {
+        allocations: Vec::new(),
         divisibility: 1,
         rune: SpacedRune { rune, spacers: 0 },
         premine: "1000".parse().unwrap(),
@@ -1110,3 +1111,252 @@ fn error_messages_use_spaced_runes() {
     .expected_stderr("error: rune `FOO` has not been etched\n")
     .run_and_extract_stdout();
 }
+
+#[test]
+fn batch_sends_inscriptions_runes_and_amounts_in_one_transaction() {
+  let core = mockcore::builder().network(Network::Regtest).build();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-runes", "--regtest"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let etched = etch(&core, &ord, Rune(RUNE));
+
+  let (inscription, _reveal) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new(
+    "--chain regtest --index-runes wallet send --fee-rate 1 --batch batch.yaml",
+  )
+  .write(
+    "batch.yaml",
+    format!(
+      "sends:
+- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  outgoing: {inscription}
+- address: bcrt1pyrmadgg78e38ewfv0an8c6eppk2fttv5vnuvz04yza60qau5va0saknu8k
+  outgoing: 400:{rune}
+- address: bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw
+  outgoing: 600:{rune}
+- address: bcrt1pyrmadgg78e38ewfv0an8c6eppk2fttv5vnuvz04yza60qau5va0saknu8k
+  outgoing: 1btc
+",
+      rune = Rune(RUNE),
+    ),
+  )
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<ord::subcommand::wallet::send::batch::Output>();
+
+  assert_eq!(output.transactions.len(), 1);
+  assert_eq!(output.transactions[0].sends.len(), 4);
+
+  let txid = output.transactions[0].txid;
+
+  let transaction = core.mempool()[0].clone();
+
+  assert_eq!(transaction.txid(), txid);
+
+  let address = |address: &str| {
+    address
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .require_network(Network::Regtest)
+      .unwrap()
+  };
+
+  assert_eq!(
+    transaction.output[0].script_pubkey,
+    address("bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw").script_pubkey()
+  );
+
+  assert_eq!(
+    Runestone::decipher(&transaction),
+    Some(Artifact::Runestone(Runestone {
+      edicts: vec![
+        Edict {
+          amount: 400,
+          id: etched.id,
+          output: 1,
+        },
+        Edict {
+          amount: 600,
+          id: etched.id,
+          output: 2,
+        },
+      ],
+      pointer: Some(3),
+      ..default()
+    })),
+  );
+
+  assert_eq!(transaction.output[5].value, COIN_VALUE);
+
+  core.mine_blocks(1);
+
+  let balances = CommandBuilder::new("--regtest --index-runes balances")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::balances::Output>();
+
+  assert_eq!(
+    balances.runes[&SpacedRune::new(Rune(RUNE), 0)]
+      .iter()
+      .map(|(outpoint, pile)| (*outpoint, pile.amount))
+      .collect::<Vec<(OutPoint, u128)>>(),
+    [
+      (OutPoint { txid, vout: 1 }, 400),
+      (OutPoint { txid, vout: 2 }, 600)
+    ],
+  );
+
+  assert_eq!(
+    ord
+      .json_request(format!("/inscription/{inscription}"))
+      .json::<api::Inscription>()
+      .unwrap()
+      .satpoint,
+    SatPoint {
+      outpoint: OutPoint { txid, vout: 0 },
+      offset: 0,
+    }
+  );
+}
+
+#[test]
+fn batch_send_dry_run_does_not_broadcast() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, _reveal) = inscribe(&core, &ord);
+
+  let output = CommandBuilder::new("wallet send --fee-rate 1 --dry-run --batch batch.yaml")
+    .write(
+      "batch.yaml",
+      format!(
+        "sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  outgoing: {inscription}
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  outgoing: 1000 sat
+"
+      ),
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::send::batch::Output>();
+
+  assert!(core.mempool().is_empty());
+  assert_eq!(output.transactions.len(), 1);
+  assert!(output.transactions[0].psbt.is_some());
+}
+
+#[test]
+fn batch_send_rejects_duplicate_sends() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  CommandBuilder::new("wallet send --fee-rate 1 --batch batch.yaml")
+    .write(
+      "batch.yaml",
+      format!(
+        "sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  outgoing: {inscription}
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  outgoing: {inscription}
+"
+      ),
+    )
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: batch sends satpoint {reveal}:0:0 more than once\n"
+    ))
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn batch_send_keeps_inscriptions_in_shared_output_aligned() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let inscribe = CommandBuilder::new("wallet batch --fee-rate 0 --batch batch.yaml")
+    .write("inscription.txt", "INSCRIPTION")
+    .write(
+      "batch.yaml",
+      "\
+mode: shared-output
+
+inscriptions:
+- file: inscription.txt
+- file: inscription.txt
+- file: inscription.txt
+",
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Batch>();
+
+  let reveal = inscribe.reveal;
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet send --fee-rate 1 --batch batch.yaml")
+    .write(
+      "batch.yaml",
+      format!(
+        "sends:
+- address: bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+  outgoing: {reveal}i0
+- address: bc1pxwww0ct9ue7e8tdnlmug5m2tamfn7q06sahstg39ys4c9f3340qqxrdu9k
+  outgoing: {reveal}i2
+"
+      ),
+    )
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<ord::subcommand::wallet::send::batch::Output>();
+
+  let txid = output.transactions[0].txid;
+
+  core.mine_blocks(1);
+
+  for (index, vout) in [(0, 0), (1, 1), (2, 2)] {
+    assert_eq!(
+      ord
+        .json_request(format!("/inscription/{reveal}i{index}"))
+        .json::<api::Inscription>()
+        .unwrap()
+        .satpoint,
+      SatPoint {
+        outpoint: OutPoint { txid, vout },
+        offset: 0,
+      }
+    );
+  }
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<ord::subcommand::wallet::inscriptions::Output>>();
+
+  assert_eq!(inscriptions.len(), 1);
+  assert_eq!(
+    inscriptions[0].location.outpoint,
+    OutPoint { txid, vout: 1 }
+  );
+}
@@ -0,0 +1,149 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{sats::OutputRare, split::Output},
+};
+
+#[test]
+fn requires_sat_index() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn(&core);
+
+  create_wallet(&core, &ord);
+
+  CommandBuilder::new("wallet split --fee-rate 1 5000000000")
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr("error: split requires index created with `--index-sats` flag\n")
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn split_rare_sat_into_own_output() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("--index-sats wallet split --fee-rate 1 5000000000")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert_eq!(core.mempool()[0].txid(), output.txid);
+  assert_eq!(output.psbt, None);
+  assert_eq!(output.outputs.len(), 2);
+
+  assert_eq!(output.outputs[0].selection, Some("5000000000".into()));
+  assert_eq!(output.outputs[0].value, 10_000);
+  assert_eq!(
+    output.outputs[0].sat_ranges,
+    [(50 * COIN_VALUE, 50 * COIN_VALUE + 10_000)]
+  );
+
+  assert_eq!(output.outputs[1].selection, None);
+  assert_eq!(
+    output.outputs[1].value,
+    50 * COIN_VALUE - 10_000 - output.fee
+  );
+
+  core.mine_blocks(1);
+
+  let sats = CommandBuilder::new("--index-sats wallet sats")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Vec<OutputRare>>();
+
+  assert!(sats.iter().any(|rare| rare.sat == 50 * COIN_VALUE
+    && rare.output == output.outputs[0].output
+    && rare.offset == 0));
+}
+
+#[test]
+fn split_sat_range() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "--index-sats wallet split --fee-rate 1 {}-{}",
+    50 * COIN_VALUE + 20_000,
+    50 * COIN_VALUE + 25_000
+  ))
+  .core(&core)
+  .ord(&ord)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(
+    output
+      .outputs
+      .iter()
+      .map(|output| (output.selection.is_some(), output.value))
+      .collect::<Vec<(bool, u64)>>(),
+    [
+      (false, 20_000),
+      (true, 5_000),
+      (false, 50 * COIN_VALUE - 25_000 - output.fee),
+    ]
+  );
+
+  assert_eq!(
+    output.outputs[1].sat_ranges,
+    [(50 * COIN_VALUE + 20_000, 50 * COIN_VALUE + 25_000)]
+  );
+}
+
+#[test]
+fn dry_run_does_not_broadcast() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  core.mine_blocks(1);
+
+  let output = CommandBuilder::new("--index-sats wallet split --fee-rate 1 --dry-run 5000000000")
+    .core(&core)
+    .ord(&ord)
+    .run_and_deserialize_output::<Output>();
+
+  assert!(core.mempool().is_empty());
+  assert!(output.psbt.is_some());
+  assert_eq!(output.outputs[0].value, 10_000);
+}
+
+#[test]
+fn inscribed_outputs_cannot_be_split() {
+  let core = mockcore::spawn();
+
+  let ord = TestServer::spawn_with_server_args(&core, &["--index-sats"], &[]);
+
+  create_wallet(&core, &ord);
+
+  let (inscription, reveal) = inscribe(&core, &ord);
+
+  let sat = ord
+    .json_request(format!("/inscription/{inscription}"))
+    .json::<api::Inscription>()
+    .unwrap()
+    .sat
+    .unwrap();
+
+  CommandBuilder::new(format!("--index-sats wallet split --fee-rate 1 {sat}"))
+    .core(&core)
+    .ord(&ord)
+    .expected_exit_code(1)
+    .expected_stderr(format!(
+      "error: output {reveal}:0 contains inscriptions and cannot be split\n"
+    ))
+    .run_and_extract_stdout();
+}